@@ -2,6 +2,7 @@
 use spacelox_vm::vm::{default_native_vm, ExecuteResult};
 use std::env;
 use std::fs::read_to_string;
+use std::path::Path;
 use std::process;
 
 fn main() {
@@ -14,7 +15,7 @@ fn main() {
       process::exit(0);
     }
     [_, file_path] => match read_to_string(file_path) {
-      Ok(source) => match vm.run(&source) {
+      Ok(source) => match vm.run_module(&source, Path::new(file_path)) {
         ExecuteResult::Ok => process::exit(0),
         ExecuteResult::FunResult(_) => panic!("Fun result should only be returned internally"),
         ExecuteResult::CompileError => process::exit(2),
@@ -1,5 +1,5 @@
-use spacelox_core::managed::Managed;
-use spacelox_core::value::Closure;
+use crate::managed::Managed;
+use crate::value::Closure;
 
 /// A call frame in the space lox interpreter
 #[derive(Clone, Copy, PartialEq)]
@@ -27,6 +27,33 @@ pub enum AlignedByteCode {
   /// Divide the top two operands on the stack
   Divide,
 
+  /// Take the remainder of the top two operands on the stack
+  Modulo,
+
+  /// Raise the second operand on the stack to the power of the top
+  Power,
+
+  /// Divide the top two operands on the stack rounding down
+  IntegerDivide,
+
+  /// Bitwise and the top two integral operands on the stack
+  BitAnd,
+
+  /// Bitwise or the top two integral operands on the stack
+  BitOr,
+
+  /// Bitwise xor the top two integral operands on the stack
+  BitXor,
+
+  /// Shift the second operand on the stack left by the top
+  ShiftLeft,
+
+  /// Shift the second operand on the stack right by the top
+  ShiftRight,
+
+  /// Bitwise not the integral top of the stack
+  BitNot,
+
   /// Apply Not operator to top stack element
   Not,
 
@@ -48,21 +75,45 @@ pub enum AlignedByteCode {
   /// Initialize list from literal
   ListInit(u16),
 
+  /// Spread a list into the list below it
+  ListSpread,
+
   /// Create an empty map
   Map,
 
   /// Initial map from literal
   MapInit(u16),
 
+  /// Spread a map into the map below it
+  MapSpread,
+
   /// Get from an index
   GetIndex,
 
   /// Set to an index
   SetIndex,
 
+  /// Slice a list or string
+  Slice,
+
+  /// Create an exclusive range
+  Range,
+
+  /// Create an inclusive range
+  RangeInclusive,
+
   /// Pop ByteCode
   Pop,
 
+  /// Duplicate the top of the stack
+  Dup,
+
+  /// Duplicate the top two values of the stack
+  DupTwo,
+
+  /// Move the top of the stack below the given number of values
+  Rotate(u8),
+
   /// Define a global in the globals table at a index
   DefineGlobal(u8),
 
@@ -93,6 +144,9 @@ pub enum AlignedByteCode {
   /// Jump to end of if block if false
   JumpIfFalse(u16),
 
+  /// Jump past an expression if nil
+  JumpIfNil(u16),
+
   /// Jump conditionally to the ip
   Jump(u16),
 
@@ -102,12 +156,21 @@ pub enum AlignedByteCode {
   /// Call a function
   Call(u8),
 
+  /// Call a function with a list of arguments
+  CallSpread,
+
   /// Invoke a method
   Invoke((u8, u8)),
 
+  /// Invoke a method with a list of arguments
+  InvokeSpread(u8),
+
   /// Invoke a method on a super class
   SuperInvoke((u8, u8)),
 
+  /// Invoke a method on a super class with a list of arguments
+  SuperInvokeSpread(u8),
+
   /// Create a closure
   Closure(u8),
 
@@ -123,6 +186,18 @@ pub enum AlignedByteCode {
   /// Add inheritance to class
   Inherit,
 
+  /// Create a trait
+  Trait(u8),
+
+  /// Add a required method name to a trait
+  Require(u8),
+
+  /// Mix traits into a class
+  Mixin(u8),
+
+  /// Attach a doc comment to a class
+  Doc(u8),
+
   /// Close an upvalue by moving it to the stack
   CloseUpvalue,
 
@@ -137,6 +212,69 @@ pub enum AlignedByteCode {
 
   /// Less greater between the top two operands on the stack
   Less,
+
+  /// Push an exception handler jumping to the catch block
+  PushHandler(u16),
+
+  /// Pop the current exception handler
+  PopHandler,
+
+  /// Throw the value on top of the stack
+  Throw,
+
+  /// Create a static method
+  StaticMethod(u8),
+
+  /// Create a property getter
+  Getter(u8),
+
+  /// Create a property setter
+  Setter(u8),
+
+  /// Import a module
+  Import(u8),
+
+  /// Export a global from the current module
+  Export(u8),
+
+  /// Check the top of the stack is a list of exactly this length
+  MatchList(u8),
+
+  /// Check the top of the stack is a list of at least this length
+  MatchListRest(u8),
+
+  /// Check the top of the stack is a map
+  MatchMap,
+
+  /// Check the map on top of the stack has this key
+  MatchKey(u8),
+
+  /// Check a value is an instance of the class on top of the stack
+  MatchClass,
+
+  /// Check the instance on top of the stack has this field
+  MatchField(u8),
+
+  /// Take the elements of a list from an index onwards
+  ListRest(u8),
+
+  /// Check a value destructures into a list of this length
+  DestructureList(u8),
+
+  /// Check a value destructures into a list of at least this length
+  DestructureListRest(u8),
+
+  /// Check a value destructures as a map
+  DestructureMap,
+
+  /// Suspend the current generator yielding a value
+  Yield,
+
+  /// Close the iterator on top of the stack once a for-in loop is left
+  CloseIterator,
+
+  /// Spread a list into the call arguments collected below it
+  ArgumentSpread,
 }
 
 impl AlignedByteCode {
@@ -150,20 +288,58 @@ impl AlignedByteCode {
       Self::Subtract => push_op(code, ByteCode::Subtract),
       Self::Multiply => push_op(code, ByteCode::Multiply),
       Self::Divide => push_op(code, ByteCode::Divide),
+      Self::Modulo => push_op(code, ByteCode::Modulo),
+      Self::Power => push_op(code, ByteCode::Power),
+      Self::IntegerDivide => push_op(code, ByteCode::IntegerDivide),
+      Self::BitAnd => push_op(code, ByteCode::BitAnd),
+      Self::BitOr => push_op(code, ByteCode::BitOr),
+      Self::BitXor => push_op(code, ByteCode::BitXor),
+      Self::ShiftLeft => push_op(code, ByteCode::ShiftLeft),
+      Self::ShiftRight => push_op(code, ByteCode::ShiftRight),
+      Self::BitNot => push_op(code, ByteCode::BitNot),
       Self::Not => push_op(code, ByteCode::Not),
       Self::Nil => push_op(code, ByteCode::Nil),
       Self::True => push_op(code, ByteCode::True),
       Self::False => push_op(code, ByteCode::False),
       Self::List => push_op(code, ByteCode::List),
       Self::ListInit(slot) => push_op_u16(code, ByteCode::ListInit, slot),
+      Self::ListSpread => push_op(code, ByteCode::ListSpread),
       Self::Map => push_op(code, ByteCode::Map),
       Self::MapInit(slot) => push_op_u16(code, ByteCode::MapInit, slot),
+      Self::MapSpread => push_op(code, ByteCode::MapSpread),
       Self::GetIndex => push_op(code, ByteCode::GetIndex),
       Self::SetIndex => push_op(code, ByteCode::SetIndex),
+      Self::Slice => push_op(code, ByteCode::Slice),
+      Self::Range => push_op(code, ByteCode::Range),
+      Self::RangeInclusive => push_op(code, ByteCode::RangeInclusive),
       Self::Equal => push_op(code, ByteCode::Equal),
       Self::Greater => push_op(code, ByteCode::Greater),
       Self::Less => push_op(code, ByteCode::Less),
+      Self::PushHandler(slot) => push_op_u16(code, ByteCode::PushHandler, slot),
+      Self::PopHandler => push_op(code, ByteCode::PopHandler),
+      Self::Throw => push_op(code, ByteCode::Throw),
+      Self::StaticMethod(slot) => push_op_u8(code, ByteCode::StaticMethod, slot),
+      Self::Getter(slot) => push_op_u8(code, ByteCode::Getter, slot),
+      Self::Setter(slot) => push_op_u8(code, ByteCode::Setter, slot),
+      Self::Import(slot) => push_op_u8(code, ByteCode::Import, slot),
+      Self::Export(slot) => push_op_u8(code, ByteCode::Export, slot),
+      Self::MatchList(slot) => push_op_u8(code, ByteCode::MatchList, slot),
+      Self::MatchListRest(slot) => push_op_u8(code, ByteCode::MatchListRest, slot),
+      Self::MatchMap => push_op(code, ByteCode::MatchMap),
+      Self::MatchKey(slot) => push_op_u8(code, ByteCode::MatchKey, slot),
+      Self::MatchClass => push_op(code, ByteCode::MatchClass),
+      Self::MatchField(slot) => push_op_u8(code, ByteCode::MatchField, slot),
+      Self::ListRest(slot) => push_op_u8(code, ByteCode::ListRest, slot),
+      Self::DestructureList(slot) => push_op_u8(code, ByteCode::DestructureList, slot),
+      Self::DestructureListRest(slot) => push_op_u8(code, ByteCode::DestructureListRest, slot),
+      Self::DestructureMap => push_op(code, ByteCode::DestructureMap),
+      Self::Yield => push_op(code, ByteCode::Yield),
+      Self::CloseIterator => push_op(code, ByteCode::CloseIterator),
+      Self::ArgumentSpread => push_op(code, ByteCode::ArgumentSpread),
       Self::Pop => push_op(code, ByteCode::Pop),
+      Self::Dup => push_op(code, ByteCode::Dup),
+      Self::DupTwo => push_op(code, ByteCode::DupTwo),
+      Self::Rotate(slot) => push_op_u8(code, ByteCode::Rotate, slot),
       Self::Constant(slot) => push_op_u8(code, ByteCode::Constant, slot),
       Self::DefineGlobal(slot) => push_op_u8(code, ByteCode::DefineGlobal, slot),
       Self::GetGlobal(slot) => push_op_u8(code, ByteCode::GetGlobal, slot),
@@ -175,18 +351,26 @@ impl AlignedByteCode {
       Self::GetProperty(slot) => push_op_u8(code, ByteCode::GetProperty, slot),
       Self::SetProperty(slot) => push_op_u8(code, ByteCode::SetProperty, slot),
       Self::JumpIfFalse(slot) => push_op_u16(code, ByteCode::JumpIfFalse, slot),
+      Self::JumpIfNil(slot) => push_op_u16(code, ByteCode::JumpIfNil, slot),
       Self::Jump(slot) => push_op_u16(code, ByteCode::Jump, slot),
       Self::Loop(slot) => push_op_u16(code, ByteCode::Loop, slot),
       Self::Call(slot) => push_op_u8(code, ByteCode::Call, slot),
+      Self::CallSpread => push_op(code, ByteCode::CallSpread),
       Self::Invoke((slot1, slot2)) => push_op_u8_tuple(code, ByteCode::Invoke, slot1, slot2),
+      Self::InvokeSpread(slot) => push_op_u8(code, ByteCode::InvokeSpread, slot),
       Self::SuperInvoke((slot1, slot2)) => {
         push_op_u8_tuple(code, ByteCode::SuperInvoke, slot1, slot2)
       }
+      Self::SuperInvokeSpread(slot) => push_op_u8(code, ByteCode::SuperInvokeSpread, slot),
       Self::Closure(slot) => push_op_u8(code, ByteCode::Closure, slot),
       Self::Method(slot) => push_op_u8(code, ByteCode::Method, slot),
       Self::Class(slot) => push_op_u8(code, ByteCode::Class, slot),
       Self::GetSuper(slot) => push_op_u8(code, ByteCode::GetSuper, slot),
       Self::Inherit => push_op(code, ByteCode::Inherit),
+      Self::Trait(slot) => push_op_u8(code, ByteCode::Trait, slot),
+      Self::Require(slot) => push_op_u8(code, ByteCode::Require, slot),
+      Self::Doc(slot) => push_op_u8(code, ByteCode::Doc, slot),
+      Self::Mixin(count) => push_op_u8(code, ByteCode::Mixin, count),
       Self::CloseUpvalue => push_op(code, ByteCode::CloseUpvalue),
       Self::UpvalueIndex(index) => {
         let encoded: u16 = unsafe { mem::transmute(index) };
@@ -208,6 +392,15 @@ impl AlignedByteCode {
       ByteCode::Subtract => (AlignedByteCode::Subtract, offset + 1),
       ByteCode::Multiply => (AlignedByteCode::Multiply, offset + 1),
       ByteCode::Divide => (AlignedByteCode::Divide, offset + 1),
+      ByteCode::Modulo => (AlignedByteCode::Modulo, offset + 1),
+      ByteCode::Power => (AlignedByteCode::Power, offset + 1),
+      ByteCode::IntegerDivide => (AlignedByteCode::IntegerDivide, offset + 1),
+      ByteCode::BitAnd => (AlignedByteCode::BitAnd, offset + 1),
+      ByteCode::BitOr => (AlignedByteCode::BitOr, offset + 1),
+      ByteCode::BitXor => (AlignedByteCode::BitXor, offset + 1),
+      ByteCode::ShiftLeft => (AlignedByteCode::ShiftLeft, offset + 1),
+      ByteCode::ShiftRight => (AlignedByteCode::ShiftRight, offset + 1),
+      ByteCode::BitNot => (AlignedByteCode::BitNot, offset + 1),
       ByteCode::Not => (AlignedByteCode::Not, offset + 1),
       ByteCode::Constant => (AlignedByteCode::Constant(store[offset + 1]), offset + 2),
       ByteCode::Nil => (AlignedByteCode::Nil, offset + 1),
@@ -218,14 +411,22 @@ impl AlignedByteCode {
         AlignedByteCode::ListInit(decode_u16(&store[offset + 1..offset + 3])),
         offset + 3,
       ),
+      ByteCode::ListSpread => (AlignedByteCode::ListSpread, offset + 1),
       ByteCode::Map => (AlignedByteCode::Map, offset + 1),
       ByteCode::MapInit => (
         AlignedByteCode::MapInit(decode_u16(&store[offset + 1..offset + 3])),
         offset + 3,
       ),
+      ByteCode::MapSpread => (AlignedByteCode::MapSpread, offset + 1),
       ByteCode::GetIndex => (AlignedByteCode::GetIndex, offset + 1),
       ByteCode::SetIndex => (AlignedByteCode::SetIndex, offset + 1),
+      ByteCode::Slice => (AlignedByteCode::Slice, offset + 1),
+      ByteCode::Range => (AlignedByteCode::Range, offset + 1),
+      ByteCode::RangeInclusive => (AlignedByteCode::RangeInclusive, offset + 1),
       ByteCode::Pop => (AlignedByteCode::Pop, offset + 1),
+      ByteCode::Dup => (AlignedByteCode::Dup, offset + 1),
+      ByteCode::DupTwo => (AlignedByteCode::DupTwo, offset + 1),
+      ByteCode::Rotate => (AlignedByteCode::Rotate(store[offset + 1]), offset + 2),
       ByteCode::DefineGlobal => (AlignedByteCode::DefineGlobal(store[offset + 1]), offset + 2),
       ByteCode::GetGlobal => (AlignedByteCode::GetGlobal(store[offset + 1]), offset + 2),
       ByteCode::SetGlobal => (AlignedByteCode::SetGlobal(store[offset + 1]), offset + 2),
@@ -239,6 +440,10 @@ impl AlignedByteCode {
         AlignedByteCode::JumpIfFalse(decode_u16(&store[offset + 1..offset + 3])),
         offset + 3,
       ),
+      ByteCode::JumpIfNil => (
+        AlignedByteCode::JumpIfNil(decode_u16(&store[offset + 1..offset + 3])),
+        offset + 3,
+      ),
       ByteCode::Jump => (
         AlignedByteCode::Jump(decode_u16(&store[offset + 1..offset + 3])),
         offset + 3,
@@ -248,23 +453,66 @@ impl AlignedByteCode {
         offset + 3,
       ),
       ByteCode::Call => (AlignedByteCode::Call(store[offset + 1]), offset + 2),
+      ByteCode::CallSpread => (AlignedByteCode::CallSpread, offset + 1),
       ByteCode::Invoke => (
         AlignedByteCode::Invoke((store[offset + 1], store[offset + 2])),
         offset + 3,
       ),
+      ByteCode::InvokeSpread => (AlignedByteCode::InvokeSpread(store[offset + 1]), offset + 2),
       ByteCode::SuperInvoke => (
         AlignedByteCode::SuperInvoke((store[offset + 1], store[offset + 2])),
         offset + 3,
       ),
+      ByteCode::SuperInvokeSpread => (
+        AlignedByteCode::SuperInvokeSpread(store[offset + 1]),
+        offset + 2,
+      ),
       ByteCode::Closure => (AlignedByteCode::Closure(store[offset + 1]), offset + 2),
       ByteCode::Method => (AlignedByteCode::Method(store[offset + 1]), offset + 2),
       ByteCode::Class => (AlignedByteCode::Class(store[offset + 1]), offset + 2),
       ByteCode::GetSuper => (AlignedByteCode::GetSuper(store[offset + 1]), offset + 2),
       ByteCode::Inherit => (AlignedByteCode::Inherit, offset + 1),
+      ByteCode::Trait => (AlignedByteCode::Trait(store[offset + 1]), offset + 2),
+      ByteCode::Require => (AlignedByteCode::Require(store[offset + 1]), offset + 2),
+      ByteCode::Doc => (AlignedByteCode::Doc(store[offset + 1]), offset + 2),
+      ByteCode::Mixin => (AlignedByteCode::Mixin(store[offset + 1]), offset + 2),
       ByteCode::CloseUpvalue => (AlignedByteCode::CloseUpvalue, offset + 1),
       ByteCode::Equal => (AlignedByteCode::Equal, offset + 1),
       ByteCode::Greater => (AlignedByteCode::Greater, offset + 1),
       ByteCode::Less => (AlignedByteCode::Less, offset + 1),
+      ByteCode::PushHandler => (
+        AlignedByteCode::PushHandler(decode_u16(&store[offset + 1..offset + 3])),
+        offset + 3,
+      ),
+      ByteCode::PopHandler => (AlignedByteCode::PopHandler, offset + 1),
+      ByteCode::Throw => (AlignedByteCode::Throw, offset + 1),
+      ByteCode::StaticMethod => (AlignedByteCode::StaticMethod(store[offset + 1]), offset + 2),
+      ByteCode::Getter => (AlignedByteCode::Getter(store[offset + 1]), offset + 2),
+      ByteCode::Setter => (AlignedByteCode::Setter(store[offset + 1]), offset + 2),
+      ByteCode::Import => (AlignedByteCode::Import(store[offset + 1]), offset + 2),
+      ByteCode::Export => (AlignedByteCode::Export(store[offset + 1]), offset + 2),
+      ByteCode::MatchList => (AlignedByteCode::MatchList(store[offset + 1]), offset + 2),
+      ByteCode::MatchListRest => (
+        AlignedByteCode::MatchListRest(store[offset + 1]),
+        offset + 2,
+      ),
+      ByteCode::MatchMap => (AlignedByteCode::MatchMap, offset + 1),
+      ByteCode::MatchKey => (AlignedByteCode::MatchKey(store[offset + 1]), offset + 2),
+      ByteCode::MatchClass => (AlignedByteCode::MatchClass, offset + 1),
+      ByteCode::MatchField => (AlignedByteCode::MatchField(store[offset + 1]), offset + 2),
+      ByteCode::ListRest => (AlignedByteCode::ListRest(store[offset + 1]), offset + 2),
+      ByteCode::DestructureList => (
+        AlignedByteCode::DestructureList(store[offset + 1]),
+        offset + 2,
+      ),
+      ByteCode::DestructureListRest => (
+        AlignedByteCode::DestructureListRest(store[offset + 1]),
+        offset + 2,
+      ),
+      ByteCode::DestructureMap => (AlignedByteCode::DestructureMap, offset + 1),
+      ByteCode::Yield => (AlignedByteCode::Yield, offset + 1),
+      ByteCode::CloseIterator => (AlignedByteCode::CloseIterator, offset + 1),
+      ByteCode::ArgumentSpread => (AlignedByteCode::ArgumentSpread, offset + 1),
     }
   }
 }
@@ -293,6 +541,33 @@ pub enum ByteCode {
   /// Divide the top two operands on the stack
   Divide,
 
+  /// Take the remainder of the top two operands on the stack
+  Modulo,
+
+  /// Raise the second operand on the stack to the power of the top
+  Power,
+
+  /// Divide the top two operands on the stack rounding down
+  IntegerDivide,
+
+  /// Bitwise and the top two integral operands on the stack
+  BitAnd,
+
+  /// Bitwise or the top two integral operands on the stack
+  BitOr,
+
+  /// Bitwise xor the top two integral operands on the stack
+  BitXor,
+
+  /// Shift the second operand on the stack left by the top
+  ShiftLeft,
+
+  /// Shift the second operand on the stack right by the top
+  ShiftRight,
+
+  /// Bitwise not the integral top of the stack
+  BitNot,
+
   /// Apply Not operator to top stack element
   Not,
 
@@ -314,21 +589,45 @@ pub enum ByteCode {
   /// Initialize List
   ListInit,
 
+  /// Spread into a list
+  ListSpread,
+
   /// Empty Map
   Map,
 
   /// Initialize map
   MapInit,
 
+  /// Spread into a map
+  MapSpread,
+
   /// Get an index
   GetIndex,
 
   /// Set an index
   SetIndex,
 
+  /// Slice a list or string
+  Slice,
+
+  /// Create an exclusive range
+  Range,
+
+  /// Create an inclusive range
+  RangeInclusive,
+
   /// Pop ByteCode
   Pop,
 
+  /// Duplicate the top of the stack
+  Dup,
+
+  /// Duplicate the top two values of the stack
+  DupTwo,
+
+  /// Move the top of the stack below the given number of values
+  Rotate,
+
   /// Define a global in the globals table at a index
   DefineGlobal,
 
@@ -359,6 +658,9 @@ pub enum ByteCode {
   /// Jump to end of if block if false
   JumpIfFalse,
 
+  /// Jump past an expression if nil
+  JumpIfNil,
+
   /// Jump conditionally to the ip
   Jump,
 
@@ -368,12 +670,21 @@ pub enum ByteCode {
   /// Call a function
   Call,
 
+  /// Call a function with a list of arguments
+  CallSpread,
+
   /// Invoke a method
   Invoke,
 
+  /// Invoke a method with a list of arguments
+  InvokeSpread,
+
   /// Invoke a method on a super class
   SuperInvoke,
 
+  /// Invoke a method on a super class with a list of arguments
+  SuperInvokeSpread,
+
   /// Create a closure
   Closure,
 
@@ -389,6 +700,18 @@ pub enum ByteCode {
   /// Add inheritance to class
   Inherit,
 
+  /// Create a trait
+  Trait,
+
+  /// Add a required method name to a trait
+  Require,
+
+  /// Mix traits into a class
+  Mixin,
+
+  /// Attach a doc comment to a class
+  Doc,
+
   /// Close an upvalue by moving it to the stack
   CloseUpvalue,
 
@@ -400,6 +723,69 @@ pub enum ByteCode {
 
   /// Less greater between the top two operands on the stack
   Less,
+
+  /// Push an exception handler
+  PushHandler,
+
+  /// Pop the current exception handler
+  PopHandler,
+
+  /// Throw the value on top of the stack
+  Throw,
+
+  /// Create a static method
+  StaticMethod,
+
+  /// Create a property getter
+  Getter,
+
+  /// Create a property setter
+  Setter,
+
+  /// Import a module
+  Import,
+
+  /// Export a global from the current module
+  Export,
+
+  /// Check the top of the stack is a list of exactly this length
+  MatchList,
+
+  /// Check the top of the stack is a list of at least this length
+  MatchListRest,
+
+  /// Check the top of the stack is a map
+  MatchMap,
+
+  /// Check the map on top of the stack has this key
+  MatchKey,
+
+  /// Check a value is an instance of the class on top of the stack
+  MatchClass,
+
+  /// Check the instance on top of the stack has this field
+  MatchField,
+
+  /// Take the elements of a list from an index onwards
+  ListRest,
+
+  /// Destructure a list
+  DestructureList,
+
+  /// Destructure a list with a rest pattern
+  DestructureListRest,
+
+  /// Destructure a map
+  DestructureMap,
+
+  /// Suspend the current generator
+  Yield,
+
+  /// Close the iterator of a for-in loop
+  CloseIterator,
+
+  /// Spread a list into call arguments
+  ArgumentSpread,
 }
 
 impl ByteCode {
@@ -618,13 +1004,30 @@ mod test {
         (1, AlignedByteCode::Subtract),
         (1, AlignedByteCode::Multiply),
         (1, AlignedByteCode::Divide),
+        (1, AlignedByteCode::Modulo),
+        (1, AlignedByteCode::Power),
+        (1, AlignedByteCode::IntegerDivide),
+        (1, AlignedByteCode::BitAnd),
+        (1, AlignedByteCode::BitOr),
+        (1, AlignedByteCode::BitXor),
+        (1, AlignedByteCode::ShiftLeft),
+        (1, AlignedByteCode::ShiftRight),
+        (1, AlignedByteCode::BitNot),
         (1, AlignedByteCode::Not),
         (2, AlignedByteCode::Constant(173)),
         (1, AlignedByteCode::Nil),
         (1, AlignedByteCode::True),
         (1, AlignedByteCode::False),
         (3, AlignedByteCode::ListInit(13444)),
+        (1, AlignedByteCode::ListSpread),
+        (1, AlignedByteCode::MapSpread),
+        (1, AlignedByteCode::Slice),
+        (1, AlignedByteCode::Range),
+        (1, AlignedByteCode::RangeInclusive),
         (1, AlignedByteCode::Pop),
+        (1, AlignedByteCode::Dup),
+        (1, AlignedByteCode::DupTwo),
+        (2, AlignedByteCode::Rotate(173)),
         (2, AlignedByteCode::DefineGlobal(173)),
         (2, AlignedByteCode::GetGlobal(173)),
         (2, AlignedByteCode::SetGlobal(173)),
@@ -635,20 +1038,49 @@ mod test {
         (2, AlignedByteCode::GetProperty(173)),
         (2, AlignedByteCode::SetProperty(173)),
         (3, AlignedByteCode::JumpIfFalse(13444)),
+        (3, AlignedByteCode::JumpIfNil(13444)),
         (3, AlignedByteCode::Jump(13444)),
         (3, AlignedByteCode::Loop(13444)),
         (2, AlignedByteCode::Call(173)),
+        (1, AlignedByteCode::CallSpread),
         (3, AlignedByteCode::Invoke((173, 173))),
+        (2, AlignedByteCode::InvokeSpread(173)),
         (3, AlignedByteCode::SuperInvoke((173, 173))),
+        (2, AlignedByteCode::SuperInvokeSpread(173)),
         (2, AlignedByteCode::Closure(173)),
         (2, AlignedByteCode::Method(173)),
         (2, AlignedByteCode::Class(173)),
         (2, AlignedByteCode::GetSuper(173)),
         (1, AlignedByteCode::Inherit),
+        (2, AlignedByteCode::Trait(173)),
+        (2, AlignedByteCode::Require(173)),
+        (2, AlignedByteCode::Doc(173)),
+        (2, AlignedByteCode::Mixin(3)),
         (1, AlignedByteCode::CloseUpvalue),
         (1, AlignedByteCode::Equal),
         (1, AlignedByteCode::Greater),
         (1, AlignedByteCode::Less),
+        (3, AlignedByteCode::PushHandler(13444)),
+        (1, AlignedByteCode::PopHandler),
+        (1, AlignedByteCode::Throw),
+        (2, AlignedByteCode::StaticMethod(173)),
+        (2, AlignedByteCode::Getter(173)),
+        (2, AlignedByteCode::Setter(173)),
+        (2, AlignedByteCode::Import(173)),
+        (2, AlignedByteCode::Export(173)),
+        (2, AlignedByteCode::MatchList(173)),
+        (2, AlignedByteCode::MatchListRest(173)),
+        (1, AlignedByteCode::MatchMap),
+        (2, AlignedByteCode::MatchKey(173)),
+        (1, AlignedByteCode::MatchClass),
+        (2, AlignedByteCode::MatchField(173)),
+        (2, AlignedByteCode::ListRest(173)),
+        (2, AlignedByteCode::DestructureList(173)),
+        (2, AlignedByteCode::DestructureListRest(173)),
+        (1, AlignedByteCode::DestructureMap),
+        (1, AlignedByteCode::Yield),
+        (1, AlignedByteCode::CloseIterator),
+        (1, AlignedByteCode::ArgumentSpread),
       ];
 
       let mut buffer: Vec<u8> = Vec::new();
@@ -701,7 +1133,7 @@ mod test {
 
       assert_eq!(chunk.instructions.len(), 1);
       match chunk.instructions[0] {
-        9 => assert!(true),
+        18 => assert!(true),
         _ => assert!(false),
       }
     }
@@ -1,5 +1,54 @@
 pub const INIT: &str = "init";
 pub const SUPER: &str = "super";
 pub const THIS: &str = "this";
+pub const STR: &str = "str";
+pub const LAMBDA: &str = "lambda";
+pub const ITER: &str = "iter";
+pub const NEXT: &str = "next";
+pub const CURRENT: &str = "current";
+pub const SEND: &str = "send";
+pub const RETURN: &str = "return";
+pub const RESUME: &str = "resume";
+pub const YIELD: &str = "yield";
+pub const SPAWN: &str = "spawn";
+pub const SLEEP: &str = "sleep";
+pub const RECV: &str = "recv";
+pub const GET: &str = "get";
+pub const SET: &str = "set";
+pub const AS: &str = "as";
+pub const FROM: &str = "from";
 pub const SCRIPT: &str = "script";
 pub const PLACEHOLDER_NAME: &str = "placeholder";
+
+// special methods user classes define to overload operators
+pub const ADD: &str = "__add__";
+pub const REFLECTED_ADD: &str = "__radd__";
+pub const SUB: &str = "__sub__";
+pub const REFLECTED_SUB: &str = "__rsub__";
+pub const MUL: &str = "__mul__";
+pub const REFLECTED_MUL: &str = "__rmul__";
+pub const DIV: &str = "__div__";
+pub const REFLECTED_DIV: &str = "__rdiv__";
+pub const MOD: &str = "__mod__";
+pub const REFLECTED_MOD: &str = "__rmod__";
+pub const POW: &str = "__pow__";
+pub const REFLECTED_POW: &str = "__rpow__";
+pub const INT_DIV: &str = "__intdiv__";
+pub const REFLECTED_INT_DIV: &str = "__rintdiv__";
+pub const BIT_AND: &str = "__and__";
+pub const REFLECTED_BIT_AND: &str = "__rand__";
+pub const BIT_OR: &str = "__or__";
+pub const REFLECTED_BIT_OR: &str = "__ror__";
+pub const BIT_XOR: &str = "__xor__";
+pub const REFLECTED_BIT_XOR: &str = "__rxor__";
+pub const SHIFT_LEFT: &str = "__lshift__";
+pub const REFLECTED_SHIFT_LEFT: &str = "__rlshift__";
+pub const SHIFT_RIGHT: &str = "__rshift__";
+pub const REFLECTED_SHIFT_RIGHT: &str = "__rrshift__";
+pub const LESS: &str = "__lt__";
+pub const GREATER: &str = "__gt__";
+pub const EQUAL: &str = "__eq__";
+pub const NEG: &str = "__neg__";
+pub const BIT_NOT: &str = "__invert__";
+pub const INDEX: &str = "__index__";
+pub const SET_INDEX: &str = "__setindex__";
@@ -10,6 +10,12 @@ pub enum DynamicMap<K: Ord + Hash, V> {
 
 const SIZE_THRESHOLD: usize = 12;
 
+impl<K: Ord + Hash, V> Default for DynamicMap<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl<K: Ord + Hash, V> DynamicMap<K, V> {
   pub fn new() -> Self {
     Self::Linear(LinearMap::new())
@@ -33,7 +33,7 @@ impl Io for NativeIo {
   }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct NativeStdIo();
 
 impl NativeStdIo {
@@ -0,0 +1,65 @@
+use crate::{
+  hooks::Hooks,
+  io::StdIo,
+  managed::{Manage, Trace},
+  value::Value,
+};
+use std::fmt;
+use std::mem;
+
+pub trait SlIter: Trace {
+  /// The name of the iterator mostly for debugging purposes
+  fn name(&self) -> &str;
+
+  /// The current value of the iterator, nil before the first call to next
+  fn current(&self) -> Value;
+
+  /// Advance the iterator returning whether a new current value is available
+  fn next(&mut self, hooks: &Hooks) -> bool;
+
+  /// The size of the iterator in bytes beyond the box
+  fn size(&self) -> usize;
+}
+
+impl fmt::Debug for dyn SlIter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("SlIter")
+      .field("name", &self.name())
+      .field("current", &self.current())
+      .finish()
+  }
+}
+
+impl fmt::Display for dyn SlIter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "<iterator {}>", self.name())
+  }
+}
+
+impl Trace for Box<dyn SlIter> {
+  fn trace(&self) -> bool {
+    (**self).trace()
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    (**self).trace_debug(stdio)
+  }
+}
+
+impl Manage for Box<dyn SlIter> {
+  fn alloc_type(&self) -> &str {
+    "iterator"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    format!("{}", self)
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>() + (**self).size()
+  }
+}
@@ -1,10 +1,17 @@
 #![deny(clippy::all)]
+#![cfg_attr(
+  test,
+  allow(clippy::assertions_on_constants, clippy::bool_assert_comparison)
+)]
+pub mod call_frame;
 pub mod chunk;
 pub mod constants;
 pub mod dynamic_map;
 pub mod io;
+pub mod iterator;
 pub mod managed;
 pub mod memory;
+pub mod module;
 pub mod native;
 pub mod token;
 pub mod utils;
@@ -136,7 +136,7 @@ impl<T: 'static + Manage> Trace for Managed<T> {
       return true;
     }
 
-    stdio.println(&format!("{:p} mark {}", &*self.obj(), self.debug()));
+    stdio.println(&format!("{:p} mark {}", self.obj(), self.debug()));
 
     self.obj().data.trace_debug(stdio);
     true
@@ -176,7 +176,7 @@ impl Trace for Managed<dyn Manage> {
       return true;
     }
 
-    stdio.println(&format!("{:p} mark {}", &*self.obj(), self.debug()));
+    stdio.println(&format!("{:p} mark {}", self.obj(), self.debug()));
 
     self.obj().data.trace_debug(stdio);
     true
@@ -230,8 +230,8 @@ impl<T: 'static + Manage> DerefMut for Managed<T> {
 
 impl<T: 'static + Manage> PartialEq for Managed<T> {
   fn eq(&self, other: &Managed<T>) -> bool {
-    let left_inner: &T = &*self;
-    let right_inner: &T = &*other;
+    let left_inner: &T = self;
+    let right_inner: &T = other;
 
     ptr::eq(left_inner, right_inner)
   }
@@ -241,7 +241,7 @@ impl<T: 'static + Manage> Eq for Managed<T> {}
 
 impl<T: 'static + Manage> Hash for Managed<T> {
   fn hash<H: Hasher>(&self, state: &mut H) {
-    let inner: &T = &*self;
+    let inner: &T = self;
     ptr::hash(inner, state)
   }
 }
@@ -260,14 +260,14 @@ impl<T: 'static + Manage> Ord for Managed<T> {
 
 impl<T: 'static + Manage + fmt::Display> fmt::Display for Managed<T> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    let inner: &T = &*self;
+    let inner: &T = self;
     write!(f, "{}", inner)
   }
 }
 
 impl<T: 'static + Manage + fmt::Debug> fmt::Debug for Managed<T> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    let inner: &T = &*self;
+    let inner: &T = self;
 
     f.debug_struct("Managed").field("ptr", inner).finish()
   }
@@ -34,7 +34,7 @@ pub struct Gc {
 
 const GC_HEAP_GROW_FACTOR: usize = 2;
 
-impl<'a> Gc {
+impl Gc {
   /// Create a new manged heap for spacelox for objects.
   ///
   /// # Examples
@@ -64,17 +64,12 @@ impl<'a> Gc {
   /// ```
   /// use spacelox_core::memory::{Gc, NO_GC};
   /// use spacelox_core::value::{Value, Fun, ArityKind};
-  /// use spacelox_core::chunk::Chunk;
   /// use spacelox_core::io::NativeStdIo;
   /// use spacelox_core::managed::Managed;
   ///
   /// let gc = Gc::new(Box::new(NativeStdIo::new()));
-  /// let fun: Fun = Fun {
-  ///   arity: ArityKind::Fixed(3),
-  ///   upvalue_count: 0,
-  ///   chunk: Chunk::default(),
-  ///   name: gc.manage_str("fun".to_string(), &NO_GC),
-  /// };
+  /// let mut fun = Fun::new(gc.manage_str("fun".to_string(), &NO_GC));
+  /// fun.arity = ArityKind::Fixed(3);
   ///
   /// let managed_fun = gc.manage(fun, &NO_GC);
   ///
@@ -109,7 +104,7 @@ impl<'a> Gc {
 
     let managed = self.allocate(string, context);
     let static_str: &'static str = unsafe { &*(&**managed as *const str) };
-    self.intern_cache.borrow_mut().insert(&static_str, managed);
+    self.intern_cache.borrow_mut().insert(static_str, managed);
     managed
   }
 
@@ -160,11 +155,16 @@ impl<'a> Gc {
 
     // get the size delta before and after the action
     // this would occur because of some resize
-    let delta = after - before;
+    let allocated = self.bytes_allocated.get();
+    if after < before {
+      self
+        .bytes_allocated
+        .set(allocated.saturating_sub(before - after));
+      return result;
+    }
 
-    let allocated = self
-      .bytes_allocated
-      .replace(self.bytes_allocated.get() + delta);
+    let delta = after - before;
+    self.bytes_allocated.set(allocated + delta);
 
     // collect if need be
     #[cfg(feature = "debug_stress_gc")]
@@ -241,7 +241,7 @@ impl<'a> Gc {
       self.stdio.println("-- gc end");
       self.stdio.println(&format!(
         "   collected {} bytes (from {} to {}) next at {}",
-        _before - now,
+        _before.saturating_sub(now),
         _before,
         now,
         self.next_gc.get()
@@ -262,7 +262,7 @@ impl<'a> Gc {
   /// Remove unmarked objects from the heap. This calculates the remaining
   /// memory present in the heap
   fn sweep(&self) -> usize {
-    if self.gc_count.get() % 10 == 0 {
+    if self.gc_count.get().is_multiple_of(10) {
       self.sweep_full()
     } else {
       self.sweep_nursery()
@@ -315,7 +315,7 @@ impl<'a> Gc {
       let retain = (*obj).unmark();
 
       #[cfg(feature = "debug_gc")]
-      self.debug_free(&obj, !retain);
+      self.debug_free(obj, !retain);
 
       if retain {
         remaining += obj.size();
@@ -369,16 +369,16 @@ impl<'a> Gc {
 
   /// Debug logging for free an object.
   #[cfg(feature = "debug_gc")]
-  fn debug_free(&self, obj: &Box<Allocation<dyn Manage>>, free: bool) {
+  fn debug_free(&self, obj: &Allocation<dyn Manage>, free: bool) {
     if free {
       self
         .stdio
-        .println(&format!("{:p} free {}", &**obj, (**obj).debug_free()));
+        .println(&format!("{:p} free {}", obj, obj.debug_free()));
     }
   }
 }
 
-impl<'a> Default for Gc {
+impl Default for Gc {
   fn default() -> Self {
     Gc::new(Box::new(NativeStdIo::new()))
   }
@@ -416,4 +416,22 @@ mod test {
     let dyn_manged_str = gc.manage(String::from("managed"), &*dyn_trace);
     assert_eq!(*dyn_manged_str, String::from("managed"));
   }
+
+  #[test]
+  fn resize_shrink() {
+    let gc = Gc::new(Box::new(NativeStdIo()));
+
+    let mut managed = gc.manage(String::from("a managed string to shrink"), &NO_GC);
+    let before = gc.bytes_allocated.get();
+
+    gc.resize(&mut *managed, &NO_GC, |string| {
+      string.clear();
+      string.shrink_to_fit();
+    });
+    assert!(gc.bytes_allocated.get() < before);
+
+    let mut unmanaged = String::with_capacity(before * 2);
+    gc.resize(&mut unmanaged, &NO_GC, |string| string.shrink_to_fit());
+    assert_eq!(gc.bytes_allocated.get(), 0);
+  }
 }
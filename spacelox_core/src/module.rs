@@ -0,0 +1,145 @@
+use crate::{
+  hooks::Hooks,
+  io::StdIo,
+  managed::{Manage, Managed, Trace},
+  value::Value,
+};
+use fnv::{FnvHashMap, FnvHashSet};
+use std::fmt;
+use std::mem;
+use std::path::{Path, PathBuf};
+
+/// A spacelox module holding its own set of globals
+#[derive(PartialEq, Clone)]
+pub struct Module {
+  /// The name of this module
+  pub name: Managed<String>,
+
+  /// The file this module was loaded from if any
+  path: Option<PathBuf>,
+
+  /// The global variables defined in this module
+  globals: FnvHashMap<Managed<String>, Value>,
+
+  /// The names of the globals visible to importing modules
+  exports: FnvHashSet<Managed<String>>,
+}
+
+impl Module {
+  pub fn new(name: Managed<String>, path: Option<PathBuf>) -> Self {
+    Self {
+      name,
+      path,
+      globals: FnvHashMap::default(),
+      exports: FnvHashSet::default(),
+    }
+  }
+
+  /// The file this module was loaded from
+  pub fn path(&self) -> Option<&Path> {
+    self.path.as_deref()
+  }
+
+  /// Set the file this module should resolve relative imports from
+  pub fn set_path(&mut self, path: Option<PathBuf>) {
+    self.path = path;
+  }
+
+  pub fn get_global(&self, name: &Managed<String>) -> Option<&Value> {
+    self.globals.get(name)
+  }
+
+  pub fn define_global(&mut self, hooks: &Hooks, name: Managed<String>, value: Value) {
+    hooks.resize(self, |module| {
+      module.globals.insert(name, value);
+    });
+  }
+
+  /// Assign to an existing global returning false if it was never defined
+  pub fn set_global(&mut self, name: Managed<String>, value: Value) -> bool {
+    match self.globals.get_mut(&name) {
+      Some(global) => {
+        *global = value;
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Mark a global as visible to importing modules
+  pub fn export(&mut self, hooks: &Hooks, name: Managed<String>) {
+    hooks.resize(self, |module| {
+      module.exports.insert(name);
+    });
+  }
+
+  /// Get an exported global from this module
+  pub fn get_export(&self, name: &Managed<String>) -> Option<&Value> {
+    if self.exports.contains(name) {
+      self.globals.get(name)
+    } else {
+      None
+    }
+  }
+}
+
+impl fmt::Debug for Module {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Module")
+      .field("name", &self.name)
+      .field("path", &self.path)
+      .field("globals", &"Globals: { ... }")
+      .field("exports", &"Exports: { ... }")
+      .finish()
+  }
+}
+
+impl fmt::Display for Module {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "<module {}>", self.name.as_str())
+  }
+}
+
+impl Trace for Module {
+  fn trace(&self) -> bool {
+    self.name.trace();
+
+    self.globals.iter().for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.name.trace_debug(stdio);
+
+    self.globals.iter().for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    true
+  }
+}
+
+impl Manage for Module {
+  fn alloc_type(&self) -> &str {
+    "module"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    String::from("Module: {{ name: {{...}}, globals: {{...}}, exports: {{...}} }}")
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Module>()
+      + (mem::size_of::<Managed<String>>() + mem::size_of::<Value>()) * self.globals.capacity()
+      + mem::size_of::<Managed<String>>() * self.exports.capacity()
+  }
+}
@@ -22,12 +22,35 @@ pub enum TokenKind {
   RightBracket,
   Comma,
   Dot,
+  DotDot,
+  DotDotEqual,
+  Ellipsis,
   Minus,
   Plus,
   Colon,
   Semicolon,
   Slash,
   Star,
+  Percent,
+  PlusEqual,
+  MinusEqual,
+  StarEqual,
+  SlashEqual,
+  PercentEqual,
+  PlusPlus,
+  MinusMinus,
+  StarStar,
+  TildeSlash,
+  Ampersand,
+  Pipe,
+  Caret,
+  Tilde,
+  LessLess,
+  GreaterGreater,
+  Arrow,
+  Question,
+  QuestionDot,
+  QuestionQuestion,
 
   // logical operators
   Bang,
@@ -42,28 +65,47 @@ pub enum TokenKind {
   // sequences
   Identifier,
   String,
+  Interpolation,
   Number,
+  Integer,
   MapOpen,
 
   // keywords
   And,
+  Break,
+  Case,
+  Catch,
   Class,
+  Const,
+  Continue,
   Else,
+  Export,
   False,
+  Finally,
   For,
   Fun,
   If,
+  Import,
+  In,
+  Match,
   Nil,
   Or,
   Print,
   Return,
+  Static,
   Super,
   This,
+  Throw,
+  Trait,
   True,
+  Try,
   Var,
   While,
+  With,
+  Yield,
 
   // meta
+  DocComment,
   Error,
   Eof,
 }
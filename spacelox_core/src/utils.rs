@@ -52,9 +52,29 @@ pub fn do_if_some<T, F: FnOnce(T)>(val: Option<T>, op: F) {
   }
 }
 
+/// The integer a float is exactly equal to if one exists
+///
+/// # Examples
+/// ```
+/// use spacelox_core::utils::exact_integer;
+///
+/// assert_eq!(exact_integer(3.0), Some(3));
+/// assert_eq!(exact_integer(-0.0), Some(0));
+/// assert_eq!(exact_integer(1.5), None);
+/// assert_eq!(exact_integer(f64::INFINITY), None);
+/// ```
+pub fn exact_integer(val: f64) -> Option<i64> {
+  // i64::MAX as f64 rounds up to 2^63 which is out of range
+  if val.fract() == 0.0 && val >= i64::MIN as f64 && val < i64::MAX as f64 {
+    Some(val as i64)
+  } else {
+    None
+  }
+}
+
 pub fn use_sentinel_nan(val: f64) -> f64 {
   if val.is_nan() {
-    std::f64::NAN
+    f64::NAN
   } else {
     val
   }
@@ -1,13 +1,19 @@
+use crate::call_frame::CallFrame;
 use crate::chunk::{AlignedByteCode, Chunk};
 use crate::io::StdIo;
 use crate::{
   constants::INIT,
   dynamic_map::DynamicMap,
   managed::{Manage, Managed, Trace},
+  iterator::SlIter,
+  module::Module,
   native::{NativeFun, NativeMethod},
-  utils::do_if_some, hooks::Hooks,
+  utils::{do_if_some, exact_integer}, hooks::Hooks,
 };
 use fnv::FnvHashMap;
+use num_bigint::BigInt;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
 use std::fmt;
 use std::mem;
 use std::{hash::Hash, ptr::NonNull};
@@ -18,16 +24,25 @@ pub enum Value {
   Bool(bool),
   Nil,
   Number(f64),
+  Int(i64),
+  BigInt(Managed<BigInt>),
   String(Managed<String>),
   List(Managed<Vec<Value>>),
   Map(Managed<FnvHashMap<Value, Value>>),
   Fun(Managed<Fun>),
   Closure(Managed<Closure>),
+  Generator(Managed<Generator>),
+  Fiber(Managed<Fiber>),
+  Channel(Managed<Channel>),
+  Range(Managed<Range>),
   Class(Managed<Class>),
+  Trait(Managed<Trait>),
   Instance(Managed<Instance>),
   Method(Managed<Method>),
   NativeFun(Managed<Box<dyn NativeFun>>),
   NativeMethod(Managed<Box<dyn NativeMethod>>),
+  Iter(Managed<Box<dyn SlIter>>),
+  Module(Managed<Module>),
   Upvalue(Managed<Upvalue>),
 }
 
@@ -42,11 +57,18 @@ pub enum ValueVariant {
   Map,
   Fun,
   Closure,
+  Generator,
+  Fiber,
+  Channel,
+  Range,
   Class,
+  Trait,
   Instance,
   Method,
   NativeFun,
   NativeMethod,
+  Iter,
+  Module,
   Upvalue,
 }
 
@@ -61,10 +83,7 @@ impl Value {
   /// assert_eq!(val1.is_nil(), true);
   /// ```
   pub fn is_nil(&self) -> bool {
-    match self {
-      Value::Nil => true,
-      _ => false,
-    }
+    matches!(self, Value::Nil)
   }
 
   /// Convert spacelox value to number, panics if not a number
@@ -75,10 +94,14 @@ impl Value {
   ///
   /// let val1 = Value::Number(20.0);
   /// assert_eq!(val1.to_num(), 20.0);
+  ///
+  /// let val2 = Value::Int(20);
+  /// assert_eq!(val2.to_num(), 20.0);
   /// ```
   pub fn to_num(&self) -> f64 {
     match self {
       Value::Number(num) => *num,
+      Value::Int(int) => *int as f64,
       _ => panic!("Value is not number"),
     }
   }
@@ -119,6 +142,27 @@ impl Value {
     }
   }
 
+  /// Unwrap a spacelox big int, panics if not a big int
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::Value;
+  /// use spacelox_core::memory::{Gc, NO_GC};
+  /// use num_bigint::BigInt;
+  ///
+  /// let gc = Gc::default();
+  /// let managed = gc.manage(BigInt::from(10), &NO_GC);
+  ///
+  /// let value = Value::BigInt(managed);
+  /// assert_eq!(*value.to_big_int(), BigInt::from(10))
+  /// ```
+  pub fn to_big_int(&self) -> Managed<BigInt> {
+    match self {
+      Self::BigInt(big) => *big,
+      _ => panic!("Expected big int."),
+    }
+  }
+
   /// Unwrap and reference a spacelox list, panics if not a list
   ///
   /// # Examples
@@ -172,15 +216,10 @@ impl Value {
   /// ```
   /// use spacelox_core::value::{Value, Fun, ArityKind};
   /// use spacelox_core::memory::{Gc, NO_GC};
-  /// use spacelox_core::chunk::Chunk;
   ///
   /// let gc = Gc::default();
-  /// let fun: Fun = Fun {
-  ///   name: gc.manage_str(String::from("add"), &NO_GC),
-  ///   arity: ArityKind::Fixed(3),
-  ///   upvalue_count: 0,
-  ///   chunk: Chunk::default()
-  /// };
+  /// let mut fun = Fun::new(gc.manage_str(String::from("add"), &NO_GC));
+  /// fun.arity = ArityKind::Fixed(3);
   /// let managed = gc.manage(fun, &NO_GC);
   ///
   /// let value = Value::Fun(managed);
@@ -201,24 +240,63 @@ impl Value {
     }
   }
 
+  /// Unwrap and reference a spacelox iterator, panics if not an iterator
+  pub fn to_iter(&self) -> Managed<Box<dyn SlIter>> {
+    match self {
+      Self::Iter(iter) => *iter,
+      _ => panic!("Expected iterator!"),
+    }
+  }
+
+  /// Unwrap and reference a spacelox fiber, panics if not a fiber
+  pub fn to_fiber(&self) -> Managed<Fiber> {
+    match self {
+      Self::Fiber(fiber) => *fiber,
+      _ => panic!("Expected fiber!"),
+    }
+  }
+
+  /// Unwrap and reference a spacelox channel, panics if not a channel
+  pub fn to_channel(&self) -> Managed<Channel> {
+    match self {
+      Self::Channel(channel) => *channel,
+      _ => panic!("Expected channel!"),
+    }
+  }
+
+  /// Unwrap and reference a spacelox range, panics if not a range
+  pub fn to_range(&self) -> Managed<Range> {
+    match self {
+      Self::Range(range) => *range,
+      _ => panic!("Expected range!"),
+    }
+  }
+
+  /// Unwrap and reference a spacelox module, panics if not a module
+  pub fn to_module(&self) -> Managed<Module> {
+    match self {
+      Self::Module(module) => *module,
+      _ => panic!("Expected module!"),
+    }
+  }
+
   /// Unwrap and reference a spacelox closure, panics if not a closure
   ///
   /// # Examples
   /// ```
   /// use spacelox_core::value::{Value, Closure, Fun, ArityKind};
+  /// use spacelox_core::module::Module;
   /// use spacelox_core::memory::{Gc, NO_GC};
-  /// use spacelox_core::chunk::Chunk;
   ///
   /// let gc = Gc::default();
-  /// let fun = Fun {
-  ///   name: gc.manage_str("add".to_string(), &NO_GC),
-  ///   arity: ArityKind::Fixed(3),
-  ///   upvalue_count: 0,
-  ///   chunk: Chunk::default()
-  /// };
+  /// let mut fun = Fun::new(gc.manage_str("add".to_string(), &NO_GC));
+  /// fun.arity = ArityKind::Fixed(3);
   /// let managed_fun = gc.manage(fun, &NO_GC);
   ///
-  /// let closure = Closure::new(managed_fun);
+  /// let name = gc.manage_str("module".to_string(), &NO_GC);
+  /// let module = gc.manage(Module::new(name, None), &NO_GC);
+  ///
+  /// let closure = Closure::new(managed_fun, module);
   /// let managed_closure = gc.manage(closure, &NO_GC);
   ///
   /// let value = Value::Closure(managed_closure);
@@ -276,6 +354,27 @@ impl Value {
     }
   }
 
+  /// Unwrap and reference a spacelox trait, panics if not a trait
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::{Value, Trait};
+  /// use spacelox_core::managed::{Managed, Allocation, make_managed};
+  /// use std::ptr::NonNull;
+  ///
+  /// let (name, name_alloc) = make_managed("example".to_string());
+  /// let (trait_, trait_alloc) = make_managed(Trait::new(name));
+  ///
+  /// let value = Value::Trait(trait_);
+  /// assert_eq!(value.to_trait().name, name);
+  /// ```
+  pub fn to_trait(&self) -> Managed<Trait> {
+    match self {
+      Self::Trait(trait_) => *trait_,
+      _ => panic!("Expected trait.",),
+    }
+  }
+
   /// Unwrap and reference a spacelox instance, panics if not a instance
   ///
   /// # Examples
@@ -317,17 +416,26 @@ impl Value {
       Value::Nil => "nil".to_string(),
       Value::Bool(_) => "bool".to_string(),
       Value::Number(_) => "number".to_string(),
+      Value::Int(_) => "number".to_string(),
+      Value::BigInt(_) => "bigint".to_string(),
       Value::String(_) => "string".to_string(),
       Value::List(_) => "list".to_string(),
       Value::Map(_) => "map".to_string(),
       Value::Fun(_) => "function".to_string(),
       Value::Closure(_) => "closure".to_string(),
+      Value::Generator(_) => "generator".to_string(),
+      Value::Fiber(_) => "fiber".to_string(),
+      Value::Channel(_) => "channel".to_string(),
+      Value::Range(_) => "range".to_string(),
       Value::Method(_) => "method".to_string(),
       Value::Class(_) => "class".to_string(),
+      Value::Trait(_) => "trait".to_string(),
       Value::Instance(_) => "instance".to_string(),
       Value::Upvalue(_) => "upvalue".to_string(),
       Value::NativeFun(_) => "native function".to_string(),
       Value::NativeMethod(_) => "native method".to_string(),
+      Value::Iter(_) => "iterator".to_string(),
+      Value::Module(_) => "module".to_string(),
     }
   }
 
@@ -336,17 +444,26 @@ impl Value {
       Value::Nil => builtin.nil,
       Value::Bool(_) => builtin.bool,
       Value::Number(_) => builtin.number,
+      Value::Int(_) => builtin.number,
+      Value::BigInt(_) => builtin.big_int,
       Value::String(_) => builtin.string,
       Value::List(_) => builtin.list,
       Value::Map(_) => builtin.map,
       Value::Fun(_) => builtin.fun,
       Value::Closure(_) => builtin.fun,
+      Value::Generator(_) => builtin.iter,
+      Value::Fiber(_) => builtin.fiber,
+      Value::Channel(_) => builtin.channel,
+      Value::Range(_) => builtin.range,
       Value::Method(_) => builtin.fun,
-      Value::Class(_) => panic!("TODO"),
+      Value::Class(_) => builtin.class,
+      Value::Trait(_) => builtin.trait_,
       Value::Instance(instance) => instance.class,
       Value::Upvalue(upvalue) => upvalue.value().value_class(builtin),
       Value::NativeFun(_) => builtin.native,
       Value::NativeMethod(_) => builtin.native,
+      Value::Iter(_) => builtin.iter,
+      Value::Module(_) => builtin.module,
     }
   }
 }
@@ -356,6 +473,8 @@ impl fmt::Display for Value {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::Number(num) => write!(f, "{}", num),
+      Self::Int(int) => write!(f, "{}", int),
+      Self::BigInt(big) => write!(f, "{}", **big),
       Self::Bool(b) => write!(f, "{}", b),
       Self::Nil => write!(f, "nil"),
       Self::String(string) => write!(f, "'{}'", string.as_str()),
@@ -376,11 +495,18 @@ impl fmt::Display for Value {
         Upvalue::Closed(store) => write!(f, "{}", store),
       },
       Self::Closure(closure) => write!(f, "{}", *closure.fun),
+      Self::Generator(generator) => write!(f, "<generator {}>", generator.closure.fun.name),
+      Self::Fiber(fiber) => write!(f, "<fiber {}>", fiber.closure.fun.name),
+      Self::Channel(_) => write!(f, "<channel>"),
+      Self::Range(range) => write!(f, "{}", &**range),
       Self::Method(bound) => write!(f, "{}.{}", bound.receiver, bound.method),
       Self::Class(class) => write!(f, "{}", &class.name.as_str()),
+      Self::Trait(trait_) => write!(f, "{}", &trait_.name.as_str()),
       Self::Instance(instance) => write!(f, "{} instance", &instance.class.name.as_str()),
       Self::NativeFun(native_fun) => write!(f, "<native {}>", native_fun.meta().name),
       Self::NativeMethod(native_method) => write!(f, "<native {}>", native_method.meta().name),
+      Self::Iter(iter) => write!(f, "{}", &***iter),
+      Self::Module(module) => write!(f, "{}", &**module),
     }
   }
 }
@@ -402,6 +528,17 @@ impl PartialEq for Value {
     // check the the variants have the same value
     match (self, other) {
       (Self::Number(num1), Self::Number(num2)) => num1 == num2,
+      (Self::Int(int1), Self::Int(int2)) => int1 == int2,
+      (Self::Int(int), Self::Number(num)) | (Self::Number(num), Self::Int(int)) => {
+        exact_integer(*num) == Some(*int)
+      }
+      (Self::BigInt(big1), Self::BigInt(big2)) => **big1 == **big2,
+      (Self::BigInt(big), Self::Int(int)) | (Self::Int(int), Self::BigInt(big)) => {
+        **big == BigInt::from(*int)
+      }
+      (Self::BigInt(big), Self::Number(num)) | (Self::Number(num), Self::BigInt(big)) => {
+        exact_integer(*num).is_some_and(|int| **big == BigInt::from(int))
+      }
       (Self::Bool(b1), Self::Bool(b2)) => b1 == b2,
       (Self::Nil, Self::Nil) => true,
       (Self::String(string1), Self::String(string2)) => string1 == string2,
@@ -409,11 +546,18 @@ impl PartialEq for Value {
       (Self::Map(map1), Self::Map(map2)) => map1 == map2,
       (Self::Fun(fun1), Self::Fun(fun2)) => fun1 == fun2,
       (Self::Closure(closure1), Self::Closure(closure2)) => closure1 == closure2,
+      (Self::Generator(generator1), Self::Generator(generator2)) => generator1 == generator2,
+      (Self::Fiber(fiber1), Self::Fiber(fiber2)) => fiber1 == fiber2,
+      (Self::Channel(channel1), Self::Channel(channel2)) => channel1 == channel2,
+      (Self::Range(range1), Self::Range(range2)) => **range1 == **range2,
       (Self::Method(method1), Self::Method(method2)) => method1 == method2,
       (Self::NativeFun(native1), Self::NativeFun(native2)) => native1 == native2,
       (Self::NativeMethod(native1), Self::NativeMethod(native2)) => native1 == native2,
+      (Self::Iter(iter1), Self::Iter(iter2)) => iter1 == iter2,
+      (Self::Module(module1), Self::Module(module2)) => module1 == module2,
       (Self::Upvalue(upvalue1), Self::Upvalue(upvalue2)) => upvalue1 == upvalue2,
       (Self::Class(class1), Self::Class(class2)) => class1 == class2,
+      (Self::Trait(trait1), Self::Trait(trait2)) => trait1 == trait2,
       (Self::Instance(instance1), Self::Instance(instance2)) => instance1 == instance2,
       _ => false,
     }
@@ -426,9 +570,25 @@ impl Hash for Value {
   fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
     // check the the variants have the same value
     match self {
+      // integral numbers hash like ints as they compare equal
       Self::Number(num) => {
         ValueVariant::Number.hash(state);
-        (*num as u64).hash(state);
+        match exact_integer(*num) {
+          Some(int) => int.hash(state),
+          None => num.to_bits().hash(state),
+        }
+      }
+      Self::Int(int) => {
+        ValueVariant::Number.hash(state);
+        int.hash(state);
+      }
+      // big ints in range of an int hash like ints as they compare equal
+      Self::BigInt(big) => {
+        ValueVariant::Number.hash(state);
+        match i64::try_from(&**big) {
+          Ok(int) => int.hash(state),
+          Err(_) => (**big).hash(state),
+        }
       }
       Self::Bool(b) => {
         ValueVariant::Bool.hash(state);
@@ -455,6 +615,23 @@ impl Hash for Value {
         ValueVariant::Closure.hash(state);
         closure.hash(state);
       }
+      Self::Generator(generator) => {
+        ValueVariant::Generator.hash(state);
+        generator.hash(state);
+      }
+      Self::Fiber(fiber) => {
+        ValueVariant::Fiber.hash(state);
+        fiber.hash(state);
+      }
+      Self::Channel(channel) => {
+        ValueVariant::Channel.hash(state);
+        channel.hash(state);
+      }
+      // ranges compare by their bounds so hash by them
+      Self::Range(range) => {
+        ValueVariant::Range.hash(state);
+        (**range).hash(state);
+      }
       Self::Method(method) => {
         ValueVariant::Method.hash(state);
         method.hash(state);
@@ -467,6 +644,14 @@ impl Hash for Value {
         ValueVariant::NativeMethod.hash(state);
         native.hash(state);
       }
+      Self::Iter(iter) => {
+        ValueVariant::Iter.hash(state);
+        iter.hash(state);
+      }
+      Self::Module(module) => {
+        ValueVariant::Module.hash(state);
+        module.hash(state);
+      }
       Self::Upvalue(upvalue) => {
         ValueVariant::Upvalue.hash(state);
         upvalue.hash(state);
@@ -475,6 +660,10 @@ impl Hash for Value {
         ValueVariant::Class.hash(state);
         class.hash(state);
       }
+      Self::Trait(trait_) => {
+        ValueVariant::Trait.hash(state);
+        trait_.hash(state);
+      }
       Self::Instance(instance) => {
         ValueVariant::Instance.hash(state);
         instance.hash(state);
@@ -487,15 +676,24 @@ impl Trace for Value {
   fn trace(&self) -> bool {
     match self {
       Value::String(string) => string.trace(),
+      Value::BigInt(big) => big.trace(),
       Value::List(list) => list.trace(),
+      Value::Map(map) => map.trace(),
       Value::Fun(fun) => fun.trace(),
       Value::Closure(closure) => closure.trace(),
+      Value::Generator(generator) => generator.trace(),
+      Value::Fiber(fiber) => fiber.trace(),
+      Value::Channel(channel) => channel.trace(),
+      Value::Range(range) => range.trace(),
       Value::Method(method) => method.trace(),
       Value::Class(class) => class.trace(),
+      Value::Trait(trait_) => trait_.trace(),
       Value::Instance(instance) => instance.trace(),
       Value::Upvalue(upvalue) => upvalue.trace(),
       Value::NativeFun(native) => native.trace(),
       Value::NativeMethod(native) => native.trace(),
+      Value::Iter(iter) => iter.trace(),
+      Value::Module(module) => module.trace(),
       _ => true,
     }
   }
@@ -503,14 +701,24 @@ impl Trace for Value {
   fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
     match self {
       Value::String(string) => string.trace_debug(stdio),
+      Value::BigInt(big) => big.trace_debug(stdio),
+      Value::List(list) => list.trace_debug(stdio),
+      Value::Map(map) => map.trace_debug(stdio),
       Value::Fun(fun) => fun.trace_debug(stdio),
       Value::Closure(closure) => closure.trace_debug(stdio),
+      Value::Generator(generator) => generator.trace_debug(stdio),
+      Value::Fiber(fiber) => fiber.trace_debug(stdio),
+      Value::Channel(channel) => channel.trace_debug(stdio),
+      Value::Range(range) => range.trace_debug(stdio),
       Value::Method(method) => method.trace_debug(stdio),
       Value::Class(class) => class.trace_debug(stdio),
+      Value::Trait(trait_) => trait_.trace_debug(stdio),
       Value::Instance(instance) => instance.trace_debug(stdio),
       Value::Upvalue(upvalue) => upvalue.trace_debug(stdio),
       Value::NativeFun(native) => native.trace_debug(stdio),
       Value::NativeMethod(native) => native.trace_debug(stdio),
+      Value::Iter(iter) => iter.trace_debug(stdio),
+      Value::Module(module) => module.trace_debug(stdio),
       _ => true,
     }
   }
@@ -520,11 +728,20 @@ pub struct BuiltInClasses {
   pub nil: Managed<Class>,
   pub bool: Managed<Class>,
   pub number: Managed<Class>,
+  pub big_int: Managed<Class>,
   pub string: Managed<Class>,
   pub list: Managed<Class>,
   pub map: Managed<Class>,
   pub fun: Managed<Class>,
   pub native: Managed<Class>,
+  pub error: Managed<Class>,
+  pub iter: Managed<Class>,
+  pub module: Managed<Class>,
+  pub fiber: Managed<Class>,
+  pub channel: Managed<Class>,
+  pub range: Managed<Class>,
+  pub class: Managed<Class>,
+  pub trait_: Managed<Class>,
 }
 
 impl Trace for BuiltInClasses {
@@ -532,11 +749,20 @@ impl Trace for BuiltInClasses {
     self.bool.trace();
     self.nil.trace();
     self.number.trace();
+    self.big_int.trace();
     self.string.trace();
     self.list.trace();
     self.map.trace();
     self.fun.trace();
     self.native.trace();
+    self.error.trace();
+    self.iter.trace();
+    self.module.trace();
+    self.fiber.trace();
+    self.channel.trace();
+    self.range.trace();
+    self.class.trace();
+    self.trait_.trace();
 
     true
   }
@@ -545,11 +771,20 @@ impl Trace for BuiltInClasses {
     self.bool.trace_debug(stdio);
     self.nil.trace_debug(stdio);
     self.number.trace_debug(stdio);
+    self.big_int.trace_debug(stdio);
     self.string.trace_debug(stdio);
     self.list.trace_debug(stdio);
     self.map.trace_debug(stdio);
     self.fun.trace_debug(stdio);
     self.native.trace_debug(stdio);
+    self.error.trace_debug(stdio);
+    self.iter.trace_debug(stdio);
+    self.module.trace_debug(stdio);
+    self.fiber.trace_debug(stdio);
+    self.channel.trace_debug(stdio);
+    self.range.trace_debug(stdio);
+    self.class.trace_debug(stdio);
+    self.trait_.trace_debug(stdio);
 
     true
   }
@@ -583,7 +818,7 @@ impl Upvalue {
     match self {
       Upvalue::Open(stack_ptr) => {
         let value = *unsafe { stack_ptr.as_ref() };
-        mem::replace(self, Upvalue::Closed(Box::new(value)));
+        *self = Upvalue::Closed(Box::new(value));
       }
       Upvalue::Closed(_) => panic!("Attempted to hoist already hoisted upvalue."),
     }
@@ -658,6 +893,8 @@ pub enum FunKind {
   Fun,
   Method,
   Initializer,
+  Getter,
+  Setter,
   Script,
 }
 
@@ -676,11 +913,21 @@ pub struct Fun {
   /// Number of upvalues
   pub upvalue_count: usize,
 
+  /// Instruction offsets to begin at for each number of optional
+  /// arguments supplied, only present with default or rest parameters
+  pub entry_points: Vec<u32>,
+
   /// Code for the function body
   chunk: Chunk,
 
   /// Name if not top-level script
   pub name: Managed<String>,
+
+  /// Does this function contain a yield, calling it produces a generator
+  pub is_generator: bool,
+
+  /// The doc comment written above the function
+  pub doc: Option<Managed<String>>,
 }
 
 impl Fun {
@@ -688,8 +935,11 @@ impl Fun {
     Self {
       arity: ArityKind::Fixed(0),
       upvalue_count: 0,
+      entry_points: Vec::new(),
       chunk: Chunk::default(),
       name,
+      is_generator: false,
+      doc: None,
     }
   }
 
@@ -721,8 +971,10 @@ impl fmt::Debug for Fun {
     f.debug_struct("Fun")
       .field("arity", &self.arity)
       .field("upvalue_count", &self.upvalue_count)
+      .field("entry_points", &self.entry_points)
       .field("chunk", &"Chunk { ... }")
       .field("name", &"Managed(String {...})")
+      .field("is_generator", &self.is_generator)
       .finish()
   }
 }
@@ -730,6 +982,9 @@ impl fmt::Debug for Fun {
 impl Trace for Fun {
   fn trace(&self) -> bool {
     self.name.trace();
+    do_if_some(self.doc, |doc| {
+      doc.trace();
+    });
     self.chunk.constants.iter().for_each(|constant| {
       constant.trace();
     });
@@ -738,6 +993,10 @@ impl Trace for Fun {
   }
 
   fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.name.trace_debug(stdio);
+    do_if_some(self.doc, |doc| {
+      doc.trace_debug(stdio);
+    });
     self.chunk.constants.iter().for_each(|constant| {
       constant.trace_debug(stdio);
     });
@@ -760,7 +1019,9 @@ impl Manage for Fun {
   }
 
   fn size(&self) -> usize {
-    mem::size_of::<Self>() + self.chunk.size()
+    mem::size_of::<Self>()
+      + self.chunk.size()
+      + self.entry_points.capacity() * mem::size_of::<u32>()
   }
 }
 
@@ -810,6 +1071,34 @@ impl Trace for Vec<Value> {
   }
 }
 
+impl Trace for BigInt {
+  fn trace(&self) -> bool {
+    true
+  }
+
+  fn trace_debug(&self, _: &dyn StdIo) -> bool {
+    true
+  }
+}
+
+impl Manage for BigInt {
+  fn alloc_type(&self) -> &str {
+    "big int"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>() + (self.bits() / 8) as usize
+  }
+}
+
 impl Manage for Vec<Value> {
   fn alloc_type(&self) -> &str {
     "list"
@@ -870,34 +1159,37 @@ impl Manage for FnvHashMap<Value, Value> {
 pub struct Closure {
   pub fun: Managed<Fun>,
   pub upvalues: Vec<Value>,
+  pub module: Managed<Module>,
 }
 
 impl Closure {
-  /// Create a new closure using a pointer to an underlying Fun
+  /// Create a new closure using a pointer to an underlying Fun and
+  /// the module its globals resolve against
   ///
   /// # Example
   /// ```
   /// use spacelox_core::value::{Closure, Fun, ArityKind};
+  /// use spacelox_core::module::Module;
   /// use spacelox_core::memory::{Gc, NO_GC};
-  /// use spacelox_core::chunk::Chunk;
   ///
   /// let gc = Gc::default();
-  /// let mut fun = Fun {
-  ///   arity: ArityKind::Fixed(3),
-  ///   upvalue_count: 2,
-  ///   chunk: Chunk::default(),
-  ///   name: gc.manage_str("example".to_string(), &NO_GC)
-  /// };
+  /// let mut fun = Fun::new(gc.manage_str("example".to_string(), &NO_GC));
+  /// fun.arity = ArityKind::Fixed(3);
+  /// fun.upvalue_count = 2;
   ///
   /// let managed_fun = gc.manage(fun, &NO_GC);
   ///
-  /// let closure = Closure::new(managed_fun);
+  /// let name = gc.manage_str("module".to_string(), &NO_GC);
+  /// let module = gc.manage(Module::new(name, None), &NO_GC);
+  ///
+  /// let closure = Closure::new(managed_fun, module);
   /// assert_eq!(&*closure.fun.name, "example");
   /// ```
-  pub fn new(fun: Managed<Fun>) -> Self {
+  pub fn new(fun: Managed<Fun>, module: Managed<Module>) -> Self {
     Closure {
-      upvalues: Vec::with_capacity(fun.upvalue_count as usize),
+      upvalues: Vec::with_capacity(fun.upvalue_count),
       fun,
+      module,
     }
   }
 }
@@ -918,6 +1210,7 @@ impl Trace for Closure {
     });
 
     self.fun.trace();
+    self.module.trace();
     true
   }
 
@@ -927,6 +1220,7 @@ impl Trace for Closure {
     });
 
     self.fun.trace_debug(stdio);
+    self.module.trace_debug(stdio);
     true
   }
 }
@@ -949,93 +1243,115 @@ impl Manage for Closure {
   }
 }
 
-#[derive(PartialEq, Clone)]
-pub struct Class {
-  pub name: Managed<String>,
-  pub init: Option<Value>,
-  methods: DynamicMap<Managed<String>, Value>,
+/// Where a generator is in its lifetime
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneratorState {
+  /// Created by a call but not yet resumed
+  Created,
+
+  /// Paused at a yield
+  Suspended,
+
+  /// Currently executing
+  Running,
+
+  /// Returned, was stopped early or threw
+  Done,
 }
 
-impl Class {
-  pub fn new(name: Managed<String>) -> Self {
-    Class {
-      name,
-      init: None,
-      methods: DynamicMap::new(),
-    }
-  }
+/// A function frame that can be suspended at a yield and resumed later.
+/// While the generator is not running its frame's stack slots, open
+/// upvalues and exception handlers are stored here
+#[derive(PartialEq, Clone)]
+pub struct Generator {
+  /// The generator function
+  pub closure: Managed<Closure>,
 
-  pub fn add_method(&mut self, hooks: &Hooks, name: Managed<String>, method: Value) {
-    if *name == INIT {
-      self.init = Some(method);
-    }
+  /// Where the generator is in its lifetime
+  pub state: GeneratorState,
 
-    hooks.resize(self, |class| {
-      class.methods.insert(name, method);
-    });
-  }
+  /// The instruction to resume at
+  pub ip: u32,
 
-  pub fn get_method(&self, name: &Managed<String>) -> Option<&Value> {
-    self.methods.get(name)
-  }
+  /// The last value yielded, or the returned value once done
+  pub current: Value,
 
-  pub fn inherit(&mut self, hooks: &Hooks, super_class: Managed<Class>) {
-    hooks.resize(self, |class| {
-      super_class.methods.for_each(|(key, value)| {
-        match class.methods.get(&*key) {
-          None => class.methods.insert(*key, *value),
-          _ => None,
-        };
-      });
-    });
+  /// The frame's stack slots while not running
+  pub slots: Vec<Value>,
 
-    self.init = self.init.or(super_class.init);
+  /// Upvalues the frame had open paired with the slot they refer to. They
+  /// are closed while the generator is suspended and reopened on resume
+  pub upvalues: Vec<(usize, Managed<Upvalue>)>,
+
+  /// Exception handlers installed by the frame as the slot height to
+  /// unwind to paired with the instruction to catch at
+  pub handlers: Vec<(usize, u32)>,
+}
+
+impl Generator {
+  /// Create a generator that will begin executing the closure at `ip`
+  /// with the provided frame slots
+  pub fn new(closure: Managed<Closure>, ip: u32, slots: Vec<Value>) -> Self {
+    Generator {
+      closure,
+      state: GeneratorState::Created,
+      ip,
+      current: Value::Nil,
+      slots,
+      upvalues: Vec::new(),
+      handlers: Vec::new(),
+    }
   }
 }
 
-impl fmt::Debug for Class {
+impl fmt::Debug for Generator {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    f.debug_struct("Class")
-      .field("name", &self.name)
-      .field("methods", &"Methods: { ... }")
-      .field("init", &self.init)
+    f.debug_struct("Generator")
+      .field("closure", &self.closure)
+      .field("state", &self.state)
+      .field("ip", &self.ip)
+      .field("current", &self.current)
+      .field("slots", &format!("[Value; {}]", &self.slots.len()))
+      .field("upvalues", &format!("[UpValue; {}]", &self.upvalues.len()))
       .finish()
   }
 }
 
-impl Trace for Class {
+impl Trace for Generator {
   fn trace(&self) -> bool {
-    self.name.trace();
-    do_if_some(self.init, |init| {
-      init.trace();
+    self.closure.trace();
+    self.current.trace();
+
+    self.slots.iter().for_each(|slot| {
+      slot.trace();
     });
 
-    self.methods.for_each(|(key, val)| {
-      key.trace();
-      val.trace();
+    self.upvalues.iter().for_each(|(_, upvalue)| {
+      upvalue.trace();
     });
 
     true
   }
 
   fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
-    self.name.trace_debug(stdio);
-    do_if_some(self.init, |init| {
-      init.trace_debug(stdio);
+    self.closure.trace_debug(stdio);
+    self.current.trace_debug(stdio);
+
+    self.slots.iter().for_each(|slot| {
+      slot.trace_debug(stdio);
     });
 
-    self.methods.for_each(|(key, val)| {
-      key.trace_debug(stdio);
-      val.trace_debug(stdio);
+    self.upvalues.iter().for_each(|(_, upvalue)| {
+      upvalue.trace_debug(stdio);
     });
 
     true
   }
 }
 
-impl Manage for Class {
+impl Manage for Generator {
   fn alloc_type(&self) -> &str {
-    "class"
+    "generator"
   }
 
   fn debug(&self) -> String {
@@ -1043,76 +1359,184 @@ impl Manage for Class {
   }
 
   fn debug_free(&self) -> String {
-    String::from("Class: {{ init: {{...}}, name: {{...}}, methods: {{...}}}}")
+    String::from("Generator: {{ closure: {{ ... }}, slots: {{ ... }}, upvalues: {{ ... }} }}")
   }
 
   fn size(&self) -> usize {
-    mem::size_of::<Class>()
-      + (mem::size_of::<Managed<String>>() + mem::size_of::<Value>()) * self.methods.capacity()
+    mem::size_of::<Self>()
+      + mem::size_of::<Value>() * self.slots.capacity()
+      + mem::size_of::<(usize, Managed<Upvalue>)>() * self.upvalues.capacity()
+      + mem::size_of::<(usize, u32)>() * self.handlers.capacity()
   }
 }
 
+/// Where a fiber is in its lifetime
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum FiberState {
+  /// Created but not yet started
+  Created,
+
+  /// Paused at a yield waiting to be resumed
+  Suspended,
+
+  /// Currently executing or waiting on a fiber it resumed
+  Running,
+
+  /// Parked by the scheduler on a channel, a sleep or its next turn
+  Waiting,
+
+  /// Returned or threw
+  Done,
+}
+
+/// A coroutine with its own value stack and call frames. While a fiber
+/// is not running its stack, frames and the execution state tied to
+/// them are stored here
 #[derive(PartialEq, Clone)]
-pub struct Instance {
-  pub class: Managed<Class>,
-  fields: DynamicMap<Managed<String>, Value>,
+pub struct Fiber {
+  /// The function the fiber runs
+  pub closure: Managed<Closure>,
+
+  /// Where the fiber is in its lifetime
+  pub state: FiberState,
+
+  /// The fiber waiting for this one to yield or return
+  pub caller: Option<Managed<Fiber>>,
+
+  /// The value handed to the fiber when it next runs
+  pub value: Value,
+
+  /// The fiber's value stack
+  pub stack: Vec<Value>,
+
+  /// The height of the value stack while not running
+  pub stack_top: usize,
+
+  /// The fiber's call frames
+  pub frames: Vec<CallFrame>,
+
+  /// The frame depth while not running
+  pub frame_count: usize,
+
+  /// Upvalues the fiber had open paired with the slot they refer to. They
+  /// are closed while the fiber is not running and reopened when it runs
+  pub upvalues: Vec<(usize, Managed<Upvalue>)>,
+
+  /// Exception handlers as the frame depth and stack height they unwind
+  /// to paired with the instruction to catch at
+  pub handlers: Vec<(usize, usize, u32)>,
+
+  /// Modules being imported paired with the frame depth of the import
+  pub imports: Vec<(Managed<Module>, usize)>,
+
+  /// Generators being run paired with the frame depth of their frame and
+  /// whether they were resumed by return
+  pub generators: Vec<(Managed<Generator>, usize, bool)>,
 }
 
-impl Instance {
-  pub fn new(class: Managed<Class>) -> Self {
-    Instance {
-      class,
-      fields: DynamicMap::new(),
+impl Fiber {
+  /// Create a fiber that will run the closure on the provided stack
+  pub fn new(closure: Managed<Closure>, stack: Vec<Value>, frames: Vec<CallFrame>) -> Self {
+    Fiber {
+      closure,
+      state: FiberState::Created,
+      caller: None,
+      value: Value::Nil,
+      stack,
+      stack_top: 0,
+      frames,
+      frame_count: 0,
+      upvalues: Vec::new(),
+      handlers: Vec::new(),
+      imports: Vec::new(),
+      generators: Vec::new(),
     }
   }
 
-  pub fn set_field(&mut self, hooks: &Hooks, name: Managed<String>, value: Value) {
-    hooks.resize(self, |instance: &mut Instance| {
-      instance.fields.insert(name, value);
-    });
-  }
-
-  pub fn get_field(&self, name: &Managed<String>) -> Option<&Value> {
-    self.fields.get(name)
+  /// Has this fiber finished running
+  pub fn is_done(&self) -> bool {
+    self.state == FiberState::Done
   }
 }
 
-impl fmt::Debug for Instance {
+impl fmt::Debug for Fiber {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    f.debug_struct("Instance")
-      .field("class", &self.class)
-      .field("fields", &self.fields)
+    f.debug_struct("Fiber")
+      .field("closure", &self.closure)
+      .field("state", &self.state)
+      .field("value", &self.value)
+      .field("stack", &format!("[Value; {}]", &self.stack_top))
+      .field("frames", &format!("[CallFrame; {}]", &self.frame_count))
+      .field("upvalues", &format!("[UpValue; {}]", &self.upvalues.len()))
       .finish()
   }
 }
 
-impl Trace for Instance {
+impl Trace for Fiber {
   fn trace(&self) -> bool {
-    self.class.trace();
+    self.closure.trace();
+    self.value.trace();
+    do_if_some(self.caller, |caller| {
+      caller.trace();
+    });
 
-    self.fields.for_each(|(key, val)| {
-      key.trace();
-      val.trace();
+    self.stack[0..self.stack_top].iter().for_each(|value| {
+      value.trace();
+    });
+
+    self.frames[0..self.frame_count].iter().for_each(|frame| {
+      frame.closure.trace();
+    });
+
+    self.upvalues.iter().for_each(|(_, upvalue)| {
+      upvalue.trace();
+    });
+
+    self.imports.iter().for_each(|(module, _)| {
+      module.trace();
+    });
+
+    self.generators.iter().for_each(|(generator, _, _)| {
+      generator.trace();
     });
 
     true
   }
 
   fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
-    self.class.trace_debug(stdio);
+    self.closure.trace_debug(stdio);
+    self.value.trace_debug(stdio);
+    do_if_some(self.caller, |caller| {
+      caller.trace_debug(stdio);
+    });
 
-    self.fields.for_each(|(key, val)| {
-      key.trace_debug(stdio);
-      val.trace_debug(stdio);
+    self.stack[0..self.stack_top].iter().for_each(|value| {
+      value.trace_debug(stdio);
+    });
+
+    self.frames[0..self.frame_count].iter().for_each(|frame| {
+      frame.closure.trace_debug(stdio);
+    });
+
+    self.upvalues.iter().for_each(|(_, upvalue)| {
+      upvalue.trace_debug(stdio);
+    });
+
+    self.imports.iter().for_each(|(module, _)| {
+      module.trace_debug(stdio);
+    });
+
+    self.generators.iter().for_each(|(generator, _, _)| {
+      generator.trace_debug(stdio);
     });
 
     true
   }
 }
 
-impl Manage for Instance {
+impl Manage for Fiber {
   fn alloc_type(&self) -> &str {
-    "instance"
+    "fiber"
   }
 
   fn debug(&self) -> String {
@@ -1120,19 +1544,723 @@ impl Manage for Instance {
   }
 
   fn debug_free(&self) -> String {
-    String::from("Instance: {{ class: {{...}}, fields: {{...}} }}")
+    String::from("Fiber: {{ closure: {{ ... }}, stack: {{ ... }}, frames: {{ ... }} }}")
   }
 
   fn size(&self) -> usize {
-    mem::size_of::<Instance>()
-      + (mem::size_of::<Managed<String>>() + mem::size_of::<Value>()) * self.fields.capacity()
+    mem::size_of::<Self>()
+      + mem::size_of::<Value>() * self.stack.capacity()
+      + mem::size_of::<CallFrame>() * self.frames.capacity()
+      + mem::size_of::<(usize, Managed<Upvalue>)>() * self.upvalues.capacity()
+      + mem::size_of::<(usize, usize, u32)>() * self.handlers.capacity()
+      + mem::size_of::<(Managed<Module>, usize)>() * self.imports.capacity()
+      + mem::size_of::<(Managed<Generator>, usize, bool)>() * self.generators.capacity()
   }
 }
 
+/// A bounded queue fibers pass values through. Fibers sending to a full
+/// channel or receiving from an empty one wait here until they can finish
 #[derive(PartialEq, Clone)]
-pub struct Method {
-  pub receiver: Value,
-  pub method: Value,
+pub struct Channel {
+  /// The number of values buffered before senders have to wait
+  pub capacity: usize,
+
+  /// The values sent but not yet received
+  pub buffer: VecDeque<Value>,
+
+  /// Fibers waiting to send paired with the value they are sending
+  pub senders: VecDeque<(Managed<Fiber>, Value)>,
+
+  /// Fibers waiting to receive
+  pub receivers: VecDeque<Managed<Fiber>>,
+}
+
+impl Channel {
+  /// Create an empty channel buffering up to `capacity` values
+  pub fn new(capacity: usize) -> Self {
+    Channel {
+      capacity,
+      buffer: VecDeque::with_capacity(capacity),
+      senders: VecDeque::new(),
+      receivers: VecDeque::new(),
+    }
+  }
+}
+
+impl fmt::Debug for Channel {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Channel")
+      .field("capacity", &self.capacity)
+      .field("buffer", &format!("[Value; {}]", &self.buffer.len()))
+      .field("senders", &format!("[Fiber; {}]", &self.senders.len()))
+      .field("receivers", &format!("[Fiber; {}]", &self.receivers.len()))
+      .finish()
+  }
+}
+
+impl Trace for Channel {
+  fn trace(&self) -> bool {
+    self.buffer.iter().for_each(|value| {
+      value.trace();
+    });
+
+    self.senders.iter().for_each(|(fiber, value)| {
+      fiber.trace();
+      value.trace();
+    });
+
+    self.receivers.iter().for_each(|fiber| {
+      fiber.trace();
+    });
+
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.buffer.iter().for_each(|value| {
+      value.trace_debug(stdio);
+    });
+
+    self.senders.iter().for_each(|(fiber, value)| {
+      fiber.trace_debug(stdio);
+      value.trace_debug(stdio);
+    });
+
+    self.receivers.iter().for_each(|fiber| {
+      fiber.trace_debug(stdio);
+    });
+
+    true
+  }
+}
+
+impl Manage for Channel {
+  fn alloc_type(&self) -> &str {
+    "channel"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    String::from("Channel: {{ buffer: {{ ... }}, senders: {{ ... }}, receivers: {{ ... }} }}")
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>()
+      + mem::size_of::<Value>() * self.buffer.capacity()
+      + mem::size_of::<(Managed<Fiber>, Value)>() * self.senders.capacity()
+      + mem::size_of::<Managed<Fiber>>() * self.receivers.capacity()
+  }
+}
+
+/// An integer range counting from `start` towards `end` by `step`. The
+/// sign of the step gives the direction, `end` is only included in
+/// inclusive ranges
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Range {
+  /// The first value in the range
+  pub start: i64,
+
+  /// The bound the range counts towards
+  pub end: i64,
+
+  /// The distance between values, never zero
+  pub step: i64,
+
+  /// Is the end bound part of the range
+  pub inclusive: bool,
+}
+
+impl Range {
+  /// Create a range counting up from `start` to `end` by one
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::Range;
+  ///
+  /// let range = Range::new(0, 10, false);
+  /// assert_eq!(range.len(), 10);
+  ///
+  /// let range = Range::new(0, 10, true);
+  /// assert_eq!(range.len(), 11);
+  /// ```
+  pub fn new(start: i64, end: i64, inclusive: bool) -> Self {
+    Range {
+      start,
+      end,
+      step: 1,
+      inclusive,
+    }
+  }
+
+  /// The number of values in this range. A range spanning most of the
+  /// integers can hold more values than fit in an i64
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::Range;
+  ///
+  /// let mut range = Range::new(0, 10, false);
+  /// range.step = 3;
+  /// assert_eq!(range.len(), 4);
+  ///
+  /// let range = Range::new(10, 0, false);
+  /// assert_eq!(range.len(), 0);
+  ///
+  /// let range = Range::new(i64::MIN, i64::MAX, true);
+  /// assert_eq!(range.len(), 1 << 64);
+  /// ```
+  pub fn len(&self) -> u128 {
+    let step = self.step as i128;
+    let mut span = (self.end as i128 - self.start as i128) * step.signum();
+    if self.inclusive {
+      span += 1;
+    }
+
+    if span <= 0 {
+      return 0;
+    }
+
+    let step = step.abs();
+    ((span + step - 1) / step) as u128
+  }
+
+  /// Does this range contain no values
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// The value at `index` in this range if there is one
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::Range;
+  ///
+  /// let range = Range::new(5, 8, false);
+  /// assert_eq!(range.get(0), Some(5));
+  /// assert_eq!(range.get(2), Some(7));
+  /// assert_eq!(range.get(3), None);
+  /// ```
+  pub fn get(&self, index: u128) -> Option<i64> {
+    if index < self.len() {
+      // every value in the range lies between its bounds
+      Some((self.start as i128 + index as i128 * self.step as i128) as i64)
+    } else {
+      None
+    }
+  }
+
+  /// Is `value` one of the values in this range
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::Range;
+  ///
+  /// let mut range = Range::new(0, 10, false);
+  /// range.step = 2;
+  /// assert!(range.contains(4));
+  /// assert!(!range.contains(5));
+  /// assert!(!range.contains(10));
+  /// ```
+  pub fn contains(&self, value: i64) -> bool {
+    let offset = value as i128 - self.start as i128;
+    let step = self.step as i128;
+
+    offset % step == 0 && offset / step >= 0 && ((offset / step) as u128) < self.len()
+  }
+
+  /// The range counting over the same values in the opposite direction
+  ///
+  /// # Examples
+  /// ```
+  /// use spacelox_core::value::Range;
+  ///
+  /// let range = Range::new(0, 3, false).reverse();
+  /// assert_eq!(range.get(0), Some(2));
+  /// assert_eq!(range.get(2), Some(0));
+  /// assert_eq!(range.len(), 3);
+  /// ```
+  pub fn reverse(&self) -> Self {
+    match self.get(self.len().saturating_sub(1)) {
+      None => *self,
+      Some(last) => Range {
+        start: last,
+        end: self.start,
+        step: -self.step,
+        inclusive: true,
+      },
+    }
+  }
+}
+
+impl fmt::Display for Range {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let operator = if self.inclusive { "..=" } else { ".." };
+    match self.step {
+      1 => write!(f, "{}{}{}", self.start, operator, self.end),
+      step => write!(f, "{}{}{} by {}", self.start, operator, self.end, step),
+    }
+  }
+}
+
+impl Trace for Range {
+  fn trace(&self) -> bool {
+    true
+  }
+
+  fn trace_debug(&self, _: &dyn StdIo) -> bool {
+    true
+  }
+}
+
+impl Manage for Range {
+  fn alloc_type(&self) -> &str {
+    "range"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    String::from("Range: {{ start: {{ ... }}, end: {{ ... }}, step: {{ ... }} }}")
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>()
+  }
+}
+
+#[derive(PartialEq, Clone)]
+pub struct Class {
+  pub name: Managed<String>,
+  pub doc: Option<Managed<String>>,
+  pub init: Option<Value>,
+  pub super_class: Option<Managed<Class>>,
+  methods: DynamicMap<Managed<String>, Value>,
+  static_methods: DynamicMap<Managed<String>, Value>,
+  getters: DynamicMap<Managed<String>, Value>,
+  setters: DynamicMap<Managed<String>, Value>,
+  fields: DynamicMap<Managed<String>, Value>,
+}
+
+impl Class {
+  pub fn new(name: Managed<String>) -> Self {
+    Class {
+      name,
+      doc: None,
+      init: None,
+      super_class: None,
+      methods: DynamicMap::new(),
+      static_methods: DynamicMap::new(),
+      getters: DynamicMap::new(),
+      setters: DynamicMap::new(),
+      fields: DynamicMap::new(),
+    }
+  }
+
+  pub fn add_method(&mut self, hooks: &Hooks, name: Managed<String>, method: Value) {
+    if *name == INIT {
+      self.init = Some(method);
+    }
+
+    hooks.resize(self, |class| {
+      class.methods.insert(name, method);
+    });
+  }
+
+  pub fn get_method(&self, name: &Managed<String>) -> Option<&Value> {
+    self.methods.get(name)
+  }
+
+  pub fn add_static_method(&mut self, hooks: &Hooks, name: Managed<String>, method: Value) {
+    hooks.resize(self, |class| {
+      class.static_methods.insert(name, method);
+    });
+  }
+
+  pub fn get_static_method(&self, name: &Managed<String>) -> Option<&Value> {
+    self.static_methods.get(name)
+  }
+
+  pub fn add_getter(&mut self, hooks: &Hooks, name: Managed<String>, getter: Value) {
+    hooks.resize(self, |class| {
+      class.getters.insert(name, getter);
+    });
+  }
+
+  pub fn get_getter(&self, name: &Managed<String>) -> Option<&Value> {
+    self.getters.get(name)
+  }
+
+  pub fn add_setter(&mut self, hooks: &Hooks, name: Managed<String>, setter: Value) {
+    hooks.resize(self, |class| {
+      class.setters.insert(name, setter);
+    });
+  }
+
+  pub fn get_setter(&self, name: &Managed<String>) -> Option<&Value> {
+    self.setters.get(name)
+  }
+
+  /// Set a class level field, fields belong only to the class they are set on
+  pub fn set_field(&mut self, hooks: &Hooks, name: Managed<String>, value: Value) {
+    hooks.resize(self, |class| {
+      class.fields.insert(name, value);
+    });
+  }
+
+  pub fn get_field(&self, name: &Managed<String>) -> Option<&Value> {
+    self.fields.get(name)
+  }
+
+  pub fn inherit(&mut self, hooks: &Hooks, super_class: Managed<Class>) {
+    hooks.resize(self, |class| {
+      super_class.methods.for_each(|(key, value)| {
+        match class.methods.get(key) {
+          None => class.methods.insert(*key, *value),
+          _ => None,
+        };
+      });
+
+      super_class.static_methods.for_each(|(key, value)| {
+        match class.static_methods.get(key) {
+          None => class.static_methods.insert(*key, *value),
+          _ => None,
+        };
+      });
+
+      super_class.getters.for_each(|(key, value)| {
+        match class.getters.get(key) {
+          None => class.getters.insert(*key, *value),
+          _ => None,
+        };
+      });
+
+      super_class.setters.for_each(|(key, value)| {
+        match class.setters.get(key) {
+          None => class.setters.insert(*key, *value),
+          _ => None,
+        };
+      });
+    });
+
+    self.init = self.init.or(super_class.init);
+    self.super_class = Some(super_class);
+  }
+}
+
+impl fmt::Debug for Class {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Class")
+      .field("name", &self.name)
+      .field("methods", &"Methods: { ... }")
+      .field("static_methods", &"Methods: { ... }")
+      .field("getters", &"Getters: { ... }")
+      .field("setters", &"Setters: { ... }")
+      .field("fields", &"Fields: { ... }")
+      .field("init", &self.init)
+      .field("super_class", &self.super_class.map(|class| class.name))
+      .finish()
+  }
+}
+
+impl Trace for Class {
+  fn trace(&self) -> bool {
+    self.name.trace();
+    do_if_some(self.doc, |doc| {
+      doc.trace();
+    });
+    do_if_some(self.init, |init| {
+      init.trace();
+    });
+    do_if_some(self.super_class, |super_class| {
+      super_class.trace();
+    });
+
+    self.methods.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    self.static_methods.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    self.getters.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    self.setters.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    self.fields.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.name.trace_debug(stdio);
+    do_if_some(self.doc, |doc| {
+      doc.trace_debug(stdio);
+    });
+    do_if_some(self.init, |init| {
+      init.trace_debug(stdio);
+    });
+    do_if_some(self.super_class, |super_class| {
+      super_class.trace_debug(stdio);
+    });
+
+    self.methods.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    self.static_methods.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    self.getters.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    self.setters.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    self.fields.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    true
+  }
+}
+
+impl Manage for Class {
+  fn alloc_type(&self) -> &str {
+    "class"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    String::from("Class: {{ init: {{...}}, name: {{...}}, methods: {{...}}}}")
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Class>()
+      + (mem::size_of::<Managed<String>>() + mem::size_of::<Value>())
+        * (self.methods.capacity()
+          + self.static_methods.capacity()
+          + self.getters.capacity()
+          + self.setters.capacity()
+          + self.fields.capacity())
+  }
+}
+
+/// A trait, a named set of methods that can be mixed into a class
+/// along with the names of the methods a class must provide itself
+#[derive(PartialEq, Clone)]
+pub struct Trait {
+  pub name: Managed<String>,
+  methods: DynamicMap<Managed<String>, Value>,
+  required: Vec<Managed<String>>,
+}
+
+impl Trait {
+  pub fn new(name: Managed<String>) -> Self {
+    Trait {
+      name,
+      methods: DynamicMap::new(),
+      required: Vec::new(),
+    }
+  }
+
+  pub fn add_method(&mut self, hooks: &Hooks, name: Managed<String>, method: Value) {
+    hooks.resize(self, |trait_| {
+      trait_.methods.insert(name, method);
+    });
+  }
+
+  pub fn get_method(&self, name: &Managed<String>) -> Option<&Value> {
+    self.methods.get(name)
+  }
+
+  /// Apply a closure to each method provided by this trait
+  pub fn for_each_method<F: FnMut((&Managed<String>, &Value))>(&self, closure: F) {
+    self.methods.for_each(closure);
+  }
+
+  /// Require classes mixing in this trait to define a method of this name
+  pub fn add_required(&mut self, hooks: &Hooks, name: Managed<String>) {
+    hooks.resize(self, |trait_| {
+      trait_.required.push(name);
+    });
+  }
+
+  pub fn required(&self) -> &[Managed<String>] {
+    &self.required
+  }
+}
+
+impl fmt::Debug for Trait {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Trait")
+      .field("name", &self.name)
+      .field("methods", &"Methods: { ... }")
+      .field("required", &self.required)
+      .finish()
+  }
+}
+
+impl Trace for Trait {
+  fn trace(&self) -> bool {
+    self.name.trace();
+
+    self.methods.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    self.required.iter().for_each(|name| {
+      name.trace();
+    });
+
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.name.trace_debug(stdio);
+
+    self.methods.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    self.required.iter().for_each(|name| {
+      name.trace_debug(stdio);
+    });
+
+    true
+  }
+}
+
+impl Manage for Trait {
+  fn alloc_type(&self) -> &str {
+    "trait"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    String::from("Trait: {{ name: {{...}}, methods: {{...}}, required: {{...}}}}")
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Trait>()
+      + (mem::size_of::<Managed<String>>() + mem::size_of::<Value>()) * self.methods.capacity()
+      + mem::size_of::<Managed<String>>() * self.required.capacity()
+  }
+}
+
+#[derive(PartialEq, Clone)]
+pub struct Instance {
+  pub class: Managed<Class>,
+  fields: DynamicMap<Managed<String>, Value>,
+}
+
+impl Instance {
+  pub fn new(class: Managed<Class>) -> Self {
+    Instance {
+      class,
+      fields: DynamicMap::new(),
+    }
+  }
+
+  pub fn set_field(&mut self, hooks: &Hooks, name: Managed<String>, value: Value) {
+    hooks.resize(self, |instance: &mut Instance| {
+      instance.fields.insert(name, value);
+    });
+  }
+
+  pub fn get_field(&self, name: &Managed<String>) -> Option<&Value> {
+    self.fields.get(name)
+  }
+}
+
+impl fmt::Debug for Instance {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Instance")
+      .field("class", &self.class)
+      .field("fields", &self.fields)
+      .finish()
+  }
+}
+
+impl Trace for Instance {
+  fn trace(&self) -> bool {
+    self.class.trace();
+
+    self.fields.for_each(|(key, val)| {
+      key.trace();
+      val.trace();
+    });
+
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.class.trace_debug(stdio);
+
+    self.fields.for_each(|(key, val)| {
+      key.trace_debug(stdio);
+      val.trace_debug(stdio);
+    });
+
+    true
+  }
+}
+
+impl Manage for Instance {
+  fn alloc_type(&self) -> &str {
+    "instance"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    String::from("Instance: {{ class: {{...}}, fields: {{...}} }}")
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Instance>()
+      + (mem::size_of::<Managed<String>>() + mem::size_of::<Value>()) * self.fields.capacity()
+  }
+}
+
+#[derive(PartialEq, Clone)]
+pub struct Method {
+  pub receiver: Value,
+  pub method: Value,
 }
 
 impl Method {
@@ -1186,17 +2314,70 @@ impl Manage for Method {
 mod test {
   use super::*;
   use crate::managed::Allocation;
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::Hasher;
   use std::ptr::NonNull;
 
   fn example_each(string: Managed<String>) -> Vec<Value> {
     vec![
       Value::Bool(true),
       Value::Nil,
-      Value::Number(10.0),
+      Value::Number(10.5),
+      Value::Int(10),
       Value::String(string),
     ]
   }
 
+  fn hash_value(value: Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+  }
+
+  #[test]
+  fn test_numeric_equality() {
+    assert_eq!(Value::Int(3), Value::Number(3.0));
+    assert_eq!(Value::Number(-0.0), Value::Int(0));
+    assert_ne!(Value::Int(3), Value::Number(3.5));
+    assert_ne!(Value::Int(i64::MAX), Value::Number(i64::MAX as f64));
+  }
+
+  #[test]
+  fn test_numeric_hash() {
+    assert_eq!(hash_value(Value::Int(3)), hash_value(Value::Number(3.0)));
+    assert_eq!(
+      hash_value(Value::Number(0.0)),
+      hash_value(Value::Number(-0.0))
+    );
+    assert_ne!(
+      hash_value(Value::Number(1.5)),
+      hash_value(Value::Number(1.9))
+    );
+  }
+
+  #[test]
+  fn test_big_int_equality_and_hash() {
+    let mut small_alloc = Box::new(Allocation::new(BigInt::from(3)));
+    let small_ptr = unsafe { NonNull::new_unchecked(&mut *small_alloc) };
+    let small = Value::BigInt(Managed::from(small_ptr));
+
+    let mut large_alloc = Box::new(Allocation::new(BigInt::from(u64::MAX) * 2));
+    let large_ptr = unsafe { NonNull::new_unchecked(&mut *large_alloc) };
+    let large = Value::BigInt(Managed::from(large_ptr));
+
+    let mut other_alloc = Box::new(Allocation::new(BigInt::from(u64::MAX) * 2));
+    let other_ptr = unsafe { NonNull::new_unchecked(&mut *other_alloc) };
+    let other = Value::BigInt(Managed::from(other_ptr));
+
+    assert_eq!(small, Value::Int(3));
+    assert_eq!(small, Value::Number(3.0));
+    assert_eq!(large, other);
+    assert_ne!(small, large);
+
+    assert_eq!(hash_value(small), hash_value(Value::Int(3)));
+    assert_eq!(hash_value(large), hash_value(other));
+  }
+
   #[test]
   fn test_diff_type_no_equal() {
     // let string = "example";
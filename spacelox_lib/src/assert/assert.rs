@@ -25,6 +25,12 @@ impl NativeAssert {
   }
 }
 
+impl Default for NativeAssert {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl NativeFun for NativeAssert {
   fn meta(&self) -> &NativeMeta {
     &self.meta
@@ -36,9 +42,9 @@ impl NativeFun for NativeAssert {
         if b {
           return NativeResult::Success(Value::Nil);
         }
-        NativeResult::RuntimeError(format!("'assert' expected true received false."))
+        NativeResult::RuntimeError(String::from("'assert' expected true received false."))
       }
-      _ => NativeResult::RuntimeError(format!("'assert' expected a boolean value.")),
+      _ => NativeResult::RuntimeError(String::from("'assert' expected a boolean value.")),
     }
   }
 }
@@ -65,6 +71,12 @@ impl NativeAssertEq {
   }
 }
 
+impl Default for NativeAssertEq {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl NativeFun for NativeAssertEq {
   fn meta(&self) -> &NativeMeta {
     &self.meta
@@ -101,6 +113,12 @@ impl NativeAssertNe {
   }
 }
 
+impl Default for NativeAssertNe {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl NativeFun for NativeAssertNe {
   fn meta(&self) -> &NativeMeta {
     &self.meta
@@ -0,0 +1,216 @@
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeFun, NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::{
+  hooks::Hooks,
+  value::{ArityKind, Class, Value},
+};
+
+pub const BIG_INT_CLASS_NAME: &str = "BigInt";
+const BIG_INT_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
+const BIG_INT_NUM: NativeMeta = NativeMeta::new("num", ArityKind::Fixed(0));
+const BIG_INT_NEW: NativeMeta = NativeMeta::new("BigInt", ArityKind::Fixed(1));
+
+pub fn create_big_int_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(BIG_INT_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(BIG_INT_STR.name)),
+    Value::NativeMethod(hooks.manage(Box::new(BigIntStr::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(BIG_INT_NUM.name)),
+    Value::NativeMethod(hooks.manage(Box::new(BigIntNum::new()))),
+  );
+
+  class
+}
+
+/// The native functions for constructing big ints
+pub fn big_int_funs() -> Vec<Box<dyn NativeFun>> {
+  vec![Box::new(BigIntNew::new())]
+}
+
+#[derive(Clone, Debug)]
+struct BigIntStr {
+  meta: Box<NativeMeta>,
+}
+
+impl BigIntStr {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(BIG_INT_STR),
+    }
+  }
+}
+
+impl NativeMethod for BigIntStr {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(hooks.manage_str(this.to_string())))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct BigIntNum {
+  meta: Box<NativeMeta>,
+}
+
+impl BigIntNum {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(BIG_INT_NUM),
+    }
+  }
+}
+
+impl NativeMethod for BigIntNum {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    match this.to_big_int().to_f64() {
+      Some(num) => NativeResult::Success(Value::Number(num)),
+      None => NativeResult::RuntimeError(format!("Cannot convert {} to a number.", this)),
+    }
+  }
+}
+
+/// Construct a big int from a string, a number or another big int
+#[derive(Clone, Debug)]
+pub struct BigIntNew {
+  meta: Box<NativeMeta>,
+}
+
+impl Default for BigIntNew {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl BigIntNew {
+  pub fn new() -> Self {
+    Self {
+      meta: Box::new(BIG_INT_NEW),
+    }
+  }
+}
+
+impl NativeFun for BigIntNew {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, args: &[Value]) -> NativeResult {
+    let big = match args[0] {
+      Value::String(string) => match string.trim().parse::<BigInt>() {
+        Ok(big) => big,
+        Err(_) => {
+          return NativeResult::RuntimeError(format!(
+            "Cannot parse \"{}\" as a BigInt.",
+            string.as_str()
+          ))
+        }
+      },
+      Value::Int(int) => BigInt::from(int),
+      Value::Number(num) if num.fract() == 0.0 => match BigInt::from_f64(num) {
+        Some(big) => big,
+        None => return NativeResult::RuntimeError(format!("Cannot convert {} to a BigInt.", num)),
+      },
+      Value::BigInt(big) => return NativeResult::Success(Value::BigInt(big)),
+      value => {
+        return NativeResult::RuntimeError(format!("Cannot convert {} to a BigInt.", value));
+      }
+    };
+
+    NativeResult::Success(Value::BigInt(hooks.manage(big)))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::support::{test_native_dependencies, TestContext};
+
+  #[cfg(test)]
+  mod new {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let big_int_new = BigIntNew::new();
+
+      assert_eq!(big_int_new.meta.name, "BigInt");
+      assert_eq!(big_int_new.meta.arity, ArityKind::Fixed(1));
+    }
+
+    #[test]
+    fn call() {
+      let big_int_new = BigIntNew::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let string = Value::String(hooks.manage_str(String::from("-123456789012345678901234")));
+      match big_int_new.call(&hooks, &[string]) {
+        NativeResult::Success(r) => assert_eq!(
+          *r.to_big_int(),
+          "-123456789012345678901234".parse::<BigInt>().unwrap()
+        ),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match big_int_new.call(&hooks, &[Value::Int(10)]) {
+        NativeResult::Success(r) => assert_eq!(*r.to_big_int(), BigInt::from(10)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      let invalid = Value::String(hooks.manage_str(String::from("12ab")));
+      match big_int_new.call(&hooks, &[invalid]) {
+        NativeResult::Success(_) => assert!(false),
+        NativeResult::RuntimeError(_) => assert!(true),
+      }
+
+      match big_int_new.call(&hooks, &[Value::Number(1.5)]) {
+        NativeResult::Success(_) => assert!(false),
+        NativeResult::RuntimeError(_) => assert!(true),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod num {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let big_int_num = BigIntNum::new();
+
+      assert_eq!(big_int_num.meta.name, "num");
+      assert_eq!(big_int_num.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let big_int_num = BigIntNum::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::BigInt(hooks.manage(BigInt::from(42)));
+      match big_int_num.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Number(42.0)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
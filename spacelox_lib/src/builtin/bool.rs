@@ -2,7 +2,7 @@ use spacelox_core::managed::{Managed};
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::{hooks::Hooks, value::{ArityKind, Class, Value}};
 
-pub const BOOL_CLASS_NAME: &'static str = "Bool";
+pub const BOOL_CLASS_NAME: &str = "Bool";
 const BOOL_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
 
 pub fn create_bool_class(hooks: &Hooks) -> Managed<Class> {
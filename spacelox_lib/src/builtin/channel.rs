@@ -0,0 +1,141 @@
+use spacelox_core::hooks::Hooks;
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::value::{ArityKind, Class, Value};
+
+pub const CHANNEL_CLASS_NAME: &str = "Channel";
+
+const CHANNEL_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
+const CHANNEL_SIZE: NativeMeta = NativeMeta::new("size", ArityKind::Fixed(0));
+
+pub fn create_channel_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(CHANNEL_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(CHANNEL_STR.name)),
+    Value::NativeMethod(hooks.manage(Box::new(ChannelStr::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(CHANNEL_SIZE.name)),
+    Value::NativeMethod(hooks.manage(Box::new(ChannelSize::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct ChannelStr {
+  meta: Box<NativeMeta>,
+}
+
+impl ChannelStr {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(CHANNEL_STR),
+    }
+  }
+}
+
+impl NativeMethod for ChannelStr {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(hooks.manage_str(this.to_string())))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct ChannelSize {
+  meta: Box<NativeMeta>,
+}
+
+impl ChannelSize {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(CHANNEL_SIZE),
+    }
+  }
+}
+
+impl NativeMethod for ChannelSize {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::Int(this.to_channel().buffer.len() as i64))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod str {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+    use spacelox_core::value::Channel;
+
+    #[test]
+    fn new() {
+      let channel_str = ChannelStr::new();
+
+      assert_eq!(channel_str.meta.name, "str");
+      assert_eq!(channel_str.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let channel_str = ChannelStr::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Channel(hooks.manage(Channel::new(2)));
+
+      match channel_str.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "<channel>"),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod size {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+    use spacelox_core::value::Channel;
+
+    #[test]
+    fn new() {
+      let channel_size = ChannelSize::new();
+
+      assert_eq!(channel_size.meta.name, "size");
+      assert_eq!(channel_size.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let channel_size = ChannelSize::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let mut channel = hooks.manage(Channel::new(2));
+      channel.buffer.push_back(Value::Nil);
+      let this = Value::Channel(channel);
+
+      match channel_size.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Int(1)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
@@ -0,0 +1,86 @@
+use spacelox_core::hooks::Hooks;
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::value::{ArityKind, Class, Value};
+
+pub const CLASS_CLASS_NAME: &str = "Class";
+
+const CLASS_DOC: NativeMeta = NativeMeta::new("doc", ArityKind::Fixed(0));
+
+pub fn create_class_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(CLASS_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(CLASS_DOC.name)),
+    Value::NativeMethod(hooks.manage(Box::new(ClassDoc::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct ClassDoc {
+  meta: Box<NativeMeta>,
+}
+
+impl ClassDoc {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(CLASS_DOC),
+    }
+  }
+}
+
+impl NativeMethod for ClassDoc {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    match this.to_class().doc {
+      Some(doc) => NativeResult::Success(Value::String(doc)),
+      None => NativeResult::Success(Value::Nil),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod doc {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let class_doc = ClassDoc::new();
+
+      assert_eq!(class_doc.meta.name, "doc");
+      assert_eq!(class_doc.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let class_doc = ClassDoc::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let mut class = hooks.manage(Class::new(hooks.manage_str(String::from("Example"))));
+      match class_doc.call(&hooks, Value::Class(class), &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Nil),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      class.doc = Some(hooks.manage_str(String::from("An example.")));
+      match class_doc.call(&hooks, Value::Class(class), &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "An example."),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
@@ -0,0 +1,93 @@
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::{
+  constants::INIT,
+  hooks::Hooks,
+  value::{ArityKind, Class, Value},
+};
+
+pub const ERROR_CLASS_NAME: &str = "Error";
+pub const ERROR_MESSAGE: &str = "message";
+pub const ERROR_STACK: &str = "stack";
+
+const ERROR_INIT: NativeMeta = NativeMeta::new(INIT, ArityKind::Fixed(1));
+
+pub fn create_error_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(ERROR_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(ERROR_INIT.name)),
+    Value::NativeMethod(hooks.manage(Box::new(ErrorInit::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct ErrorInit {
+  meta: Box<NativeMeta>,
+}
+
+impl ErrorInit {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(ERROR_INIT),
+    }
+  }
+}
+
+impl NativeMethod for ErrorInit {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, args: &[Value]) -> NativeResult {
+    let mut instance = this.to_instance();
+    instance.set_field(hooks, hooks.manage_str(String::from(ERROR_MESSAGE)), args[0]);
+
+    NativeResult::Success(this)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod init {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+    use spacelox_core::value::Instance;
+
+    #[test]
+    fn new() {
+      let error_init = ErrorInit::new();
+
+      assert_eq!(error_init.meta.name, "init");
+      assert_eq!(error_init.meta.arity, ArityKind::Fixed(1));
+    }
+
+    #[test]
+    fn call() {
+      let error_init = ErrorInit::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let class = create_error_class(&hooks);
+      let this = Value::Instance(hooks.manage(Instance::new(class)));
+      let message = Value::String(hooks.manage_str(String::from("oops")));
+
+      let result = error_init.call(&hooks, this, &[message]);
+      match result {
+        NativeResult::Success(r) => {
+          let field = hooks.manage_str(String::from(ERROR_MESSAGE));
+          assert_eq!(r.to_instance().get_field(&field), Some(&message));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
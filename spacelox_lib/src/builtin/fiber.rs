@@ -0,0 +1,154 @@
+use spacelox_core::hooks::Hooks;
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::value::{ArityKind, Class, Value};
+
+pub const FIBER_CLASS_NAME: &str = "Fiber";
+
+const FIBER_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
+const FIBER_IS_DONE: NativeMeta = NativeMeta::new("isDone", ArityKind::Fixed(0));
+
+pub fn create_fiber_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(FIBER_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(FIBER_STR.name)),
+    Value::NativeMethod(hooks.manage(Box::new(FiberStr::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(FIBER_IS_DONE.name)),
+    Value::NativeMethod(hooks.manage(Box::new(FiberIsDone::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct FiberStr {
+  meta: Box<NativeMeta>,
+}
+
+impl FiberStr {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(FIBER_STR),
+    }
+  }
+}
+
+impl NativeMethod for FiberStr {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(hooks.manage_str(this.to_string())))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct FiberIsDone {
+  meta: Box<NativeMeta>,
+}
+
+impl FiberIsDone {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(FIBER_IS_DONE),
+    }
+  }
+}
+
+impl NativeMethod for FiberIsDone {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::Bool(this.to_fiber().is_done()))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::support::{test_native_dependencies, TestContext};
+  use spacelox_core::module::Module;
+  use spacelox_core::value::{Closure, Fiber, FiberState, Fun};
+
+  fn test_fiber(hooks: &Hooks) -> Value {
+    let name = hooks.manage_str(String::from("example"));
+    let module = hooks.manage(Module::new(name, None));
+    let fun = hooks.manage(Fun::new(name));
+    let closure = hooks.manage(Closure::new(fun, module));
+
+    Value::Fiber(hooks.manage(Fiber::new(closure, vec![], vec![])))
+  }
+
+  #[cfg(test)]
+  mod str {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let fiber_str = FiberStr::new();
+
+      assert_eq!(fiber_str.meta.name, "str");
+      assert_eq!(fiber_str.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let fiber_str = FiberStr::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = test_fiber(&hooks);
+
+      match fiber_str.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "<fiber example>"),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod is_done {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let fiber_is_done = FiberIsDone::new();
+
+      assert_eq!(fiber_is_done.meta.name, "isDone");
+      assert_eq!(fiber_is_done.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let fiber_is_done = FiberIsDone::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = test_fiber(&hooks);
+
+      match fiber_is_done.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(false)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      this.to_fiber().state = FiberState::Done;
+
+      match fiber_is_done.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(true)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
@@ -2,9 +2,10 @@ use spacelox_core::managed::{Managed};
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::{hooks::Hooks, value::{ArityKind, Class, Value}};
 
-pub const FUN_CLASS_NAME: &'static str = "Fun";
+pub const FUN_CLASS_NAME: &str = "Fun";
 
 const FUN_NAME: NativeMeta = NativeMeta::new("name", ArityKind::Fixed(0));
+const FUN_DOC: NativeMeta = NativeMeta::new("doc", ArityKind::Fixed(0));
 
 pub fn create_fun_class(hooks: &Hooks) -> Managed<Class> {
   let name = hooks.manage_str(String::from(FUN_CLASS_NAME));
@@ -16,6 +17,12 @@ pub fn create_fun_class(hooks: &Hooks) -> Managed<Class> {
     Value::NativeMethod(hooks.manage(Box::new(FunName::new()))),
   );
 
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(FUN_DOC.name)),
+    Value::NativeMethod(hooks.manage(Box::new(FunDoc::new()))),
+  );
+
   class
 }
 
@@ -41,3 +48,69 @@ impl NativeMethod for FunName {
     NativeResult::Success(Value::String(this.to_fun().name))
   }
 }
+
+#[derive(Clone, Debug)]
+struct FunDoc {
+  meta: Box<NativeMeta>,
+}
+
+impl FunDoc {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(FUN_DOC),
+    }
+  }
+}
+
+impl NativeMethod for FunDoc {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    match this.to_fun().doc {
+      Some(doc) => NativeResult::Success(Value::String(doc)),
+      None => NativeResult::Success(Value::Nil),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod doc {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+    use spacelox_core::value::Fun;
+
+    #[test]
+    fn new() {
+      let fun_doc = FunDoc::new();
+
+      assert_eq!(fun_doc.meta.name, "doc");
+      assert_eq!(fun_doc.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let fun_doc = FunDoc::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let mut fun = hooks.manage(Fun::new(hooks.manage_str(String::from("example"))));
+      match fun_doc.call(&hooks, Value::Fun(fun), &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Nil),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      fun.doc = Some(hooks.manage_str(String::from("An example.")));
+      match fun_doc.call(&hooks, Value::Fun(fun), &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "An example."),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
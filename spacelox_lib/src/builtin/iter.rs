@@ -0,0 +1,240 @@
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::{
+  constants::{CURRENT, ITER, NEXT, STR},
+  hooks::Hooks,
+  value::{ArityKind, Class, Value},
+};
+
+pub const ITER_CLASS_NAME: &str = "Iterator";
+
+const ITER_STR: NativeMeta = NativeMeta::new(STR, ArityKind::Fixed(0));
+const ITER_NEXT: NativeMeta = NativeMeta::new(NEXT, ArityKind::Fixed(0));
+const ITER_CURRENT: NativeMeta = NativeMeta::new(CURRENT, ArityKind::Fixed(0));
+const ITER_ITER: NativeMeta = NativeMeta::new(ITER, ArityKind::Fixed(0));
+
+pub fn create_iter_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(ITER_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(ITER_STR.name)),
+    Value::NativeMethod(hooks.manage(Box::new(IterStr::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(ITER_NEXT.name)),
+    Value::NativeMethod(hooks.manage(Box::new(IterNext::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(ITER_CURRENT.name)),
+    Value::NativeMethod(hooks.manage(Box::new(IterCurrent::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(ITER_ITER.name)),
+    Value::NativeMethod(hooks.manage(Box::new(IterIter::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct IterStr {
+  meta: Box<NativeMeta>,
+}
+
+impl IterStr {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(ITER_STR),
+    }
+  }
+}
+
+impl NativeMethod for IterStr {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(hooks.manage_str(this.to_string())))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct IterNext {
+  meta: Box<NativeMeta>,
+}
+
+impl IterNext {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(ITER_NEXT),
+    }
+  }
+}
+
+impl NativeMethod for IterNext {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::Bool(this.to_iter().next(hooks)))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct IterCurrent {
+  meta: Box<NativeMeta>,
+}
+
+impl IterCurrent {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(ITER_CURRENT),
+    }
+  }
+}
+
+impl NativeMethod for IterCurrent {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(this.to_iter().current())
+  }
+}
+
+#[derive(Clone, Debug)]
+struct IterIter {
+  meta: Box<NativeMeta>,
+}
+
+impl IterIter {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(ITER_ITER),
+    }
+  }
+}
+
+impl NativeMethod for IterIter {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(this)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::builtin::list::ListIterator;
+  use crate::support::{test_native_dependencies, TestContext};
+  use spacelox_core::iterator::SlIter;
+
+  fn list_iter(hooks: &Hooks) -> Value {
+    let list = hooks.manage(vec![Value::Number(1.0), Value::Number(2.0)]);
+    let iter: Box<dyn SlIter> = Box::new(ListIterator::new(list));
+    Value::Iter(hooks.manage(iter))
+  }
+
+  #[cfg(test)]
+  mod next {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let iter_next = IterNext::new();
+
+      assert_eq!(iter_next.meta.name, "next");
+      assert_eq!(iter_next.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let iter_next = IterNext::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = list_iter(&hooks);
+      for expected in &[true, true, false] {
+        match iter_next.call(&hooks, this, &[]) {
+          NativeResult::Success(r) => assert_eq!(r.to_bool(), *expected),
+          NativeResult::RuntimeError(_) => assert!(false),
+        }
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod current {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let iter_current = IterCurrent::new();
+
+      assert_eq!(iter_current.meta.name, "current");
+      assert_eq!(iter_current.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let iter_current = IterCurrent::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = list_iter(&hooks);
+      match iter_current.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert!(r.is_nil()),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      this.to_iter().next(&hooks);
+      match iter_current.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r.to_num(), 1.0),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod iter {
+    use super::*;
+
+    #[test]
+    fn new() {
+      let iter_iter = IterIter::new();
+
+      assert_eq!(iter_iter.meta.name, "iter");
+      assert_eq!(iter_iter.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let iter_iter = IterIter::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = list_iter(&hooks);
+      match iter_iter.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, this),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
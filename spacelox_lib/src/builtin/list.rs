@@ -1,10 +1,13 @@
-use spacelox_core::managed::Managed;
+use spacelox_core::io::StdIo;
+use spacelox_core::iterator::SlIter;
+use spacelox_core::managed::{Managed, Trace};
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::value::{ArityKind, Class, Value};
 use spacelox_core::hooks::Hooks;
+use std::mem;
 
 
-pub const LIST_CLASS_NAME: &'static str = "List";
+pub const LIST_CLASS_NAME: &str = "List";
 
 const LIST_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
 const LIST_SIZE: NativeMeta = NativeMeta::new("size", ArityKind::Fixed(0));
@@ -14,6 +17,7 @@ const LIST_REMOVE: NativeMeta = NativeMeta::new("remove", ArityKind::Fixed(1));
 const LIST_INSERT: NativeMeta = NativeMeta::new("insert", ArityKind::Fixed(2));
 const LIST_CLEAR: NativeMeta = NativeMeta::new("clear", ArityKind::Fixed(0));
 const LIST_HAS: NativeMeta = NativeMeta::new("has", ArityKind::Fixed(1));
+const LIST_ITER: NativeMeta = NativeMeta::new("iter", ArityKind::Fixed(0));
 
 pub fn create_list_class(hooks: &Hooks) -> Managed<Class> {
   let name = hooks.manage_str(String::from(LIST_CLASS_NAME));
@@ -67,6 +71,12 @@ pub fn create_list_class(hooks: &Hooks) -> Managed<Class> {
     Value::NativeMethod(hooks.manage(Box::new(ListHas::new()))),
   );
 
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(LIST_ITER.name)),
+    Value::NativeMethod(hooks.manage(Box::new(ListIter::new()))),
+  );
+
   class
 }
 
@@ -112,7 +122,7 @@ impl NativeMethod for ListSize {
   }
 
   fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
-    NativeResult::Success(Value::Number(this.to_list().len() as f64))
+    NativeResult::Success(Value::Int(this.to_list().len() as i64))
   }
 }
 
@@ -291,6 +301,87 @@ impl NativeMethod for ListHas {
   }
 }
 
+#[derive(Clone, Debug)]
+struct ListIter {
+  meta: Box<NativeMeta>,
+}
+
+impl ListIter {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(LIST_ITER),
+    }
+  }
+}
+
+impl NativeMethod for ListIter {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    let iter: Box<dyn SlIter> = Box::new(ListIterator::new(this.to_list()));
+    NativeResult::Success(Value::Iter(hooks.manage(iter)))
+  }
+}
+
+/// An iterator over the elements of a list
+#[derive(Debug)]
+pub struct ListIterator {
+  list: Managed<Vec<Value>>,
+  current: Value,
+  index: usize,
+}
+
+impl ListIterator {
+  pub fn new(list: Managed<Vec<Value>>) -> Self {
+    Self {
+      list,
+      current: Value::Nil,
+      index: 0,
+    }
+  }
+}
+
+impl SlIter for ListIterator {
+  fn name(&self) -> &str {
+    "List Iterator"
+  }
+
+  fn current(&self) -> Value {
+    self.current
+  }
+
+  fn next(&mut self, _hooks: &Hooks) -> bool {
+    match self.list.get(self.index) {
+      Some(value) => {
+        self.current = *value;
+        self.index += 1;
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>()
+  }
+}
+
+impl Trace for ListIterator {
+  fn trace(&self) -> bool {
+    self.list.trace();
+    self.current.trace();
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.list.trace_debug(stdio);
+    self.current.trace_debug(stdio);
+    true
+  }
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -599,4 +690,42 @@ mod test {
       }
     }
   }
+
+  #[cfg(test)]
+  mod iter {
+    use super::*;
+    use crate::support::{TestContext, test_native_dependencies};
+
+    #[test]
+    fn new() {
+      let list_iter = ListIter::new();
+
+      assert_eq!(list_iter.meta.name, "iter");
+      assert_eq!(list_iter.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let list_iter = ListIter::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let list = vec![Value::Nil, Value::Number(10.0)];
+      let this = hooks.manage(list);
+
+      let result = list_iter.call(&hooks, Value::List(this), &[]);
+      match result {
+        NativeResult::Success(r) => {
+          let mut iter = r.to_iter();
+          assert!(iter.next(&hooks));
+          assert!(iter.current().is_nil());
+          assert!(iter.next(&hooks));
+          assert_eq!(iter.current().to_num(), 10.0);
+          assert!(!iter.next(&hooks));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
 }
@@ -1,13 +1,20 @@
-use spacelox_core::managed::{Managed};
+use spacelox_core::io::StdIo;
+use spacelox_core::iterator::SlIter;
+use spacelox_core::managed::{Managed, Trace};
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::{hooks::Hooks, value::{ArityKind, Class, Value}};
+use std::mem;
 
-pub const MAP_CLASS_NAME: &'static str = "Map";
+pub const MAP_CLASS_NAME: &str = "Map";
 
 const MAP_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
 const MAP_SIZE: NativeMeta = NativeMeta::new("size", ArityKind::Fixed(0));
 const MAP_HAS: NativeMeta = NativeMeta::new("has", ArityKind::Fixed(1));
 const MAP_GET: NativeMeta = NativeMeta::new("get", ArityKind::Fixed(1));
+const MAP_ITER: NativeMeta = NativeMeta::new("iter", ArityKind::Fixed(0));
+const MAP_KEYS: NativeMeta = NativeMeta::new("keys", ArityKind::Fixed(0));
+const MAP_VALUES: NativeMeta = NativeMeta::new("values", ArityKind::Fixed(0));
+const MAP_ENTRIES: NativeMeta = NativeMeta::new("entries", ArityKind::Fixed(0));
 
 pub fn create_map_class(hooks: &Hooks) -> Managed<Class> {
   let name = hooks.manage_str(String::from(MAP_CLASS_NAME));
@@ -37,6 +44,30 @@ pub fn create_map_class(hooks: &Hooks) -> Managed<Class> {
     Value::NativeMethod(hooks.manage(Box::new(MapGet::new()))),
   );
 
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(MAP_ITER.name)),
+    Value::NativeMethod(hooks.manage(Box::new(MapIter::new(MAP_ITER, MapIterKind::Keys)))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(MAP_KEYS.name)),
+    Value::NativeMethod(hooks.manage(Box::new(MapIter::new(MAP_KEYS, MapIterKind::Keys)))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(MAP_VALUES.name)),
+    Value::NativeMethod(hooks.manage(Box::new(MapIter::new(MAP_VALUES, MapIterKind::Values)))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(MAP_ENTRIES.name)),
+    Value::NativeMethod(hooks.manage(Box::new(MapIter::new(MAP_ENTRIES, MapIterKind::Entries)))),
+  );
+
   class
 }
 
@@ -82,7 +113,7 @@ impl NativeMethod for MapSize {
   }
 
   fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
-    NativeResult::Success(Value::Number(this.to_map().len() as f64))
+    NativeResult::Success(Value::Int(this.to_map().len() as i64))
   }
 }
 
@@ -135,6 +166,119 @@ impl NativeMethod for MapGet {
   }
 }
 
+/// Which part of each map entry an iterator produces
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MapIterKind {
+  Keys,
+  Values,
+  Entries,
+}
+
+#[derive(Clone, Debug)]
+struct MapIter {
+  meta: Box<NativeMeta>,
+  kind: MapIterKind,
+}
+
+impl MapIter {
+  fn new(meta: NativeMeta, kind: MapIterKind) -> Self {
+    Self {
+      meta: Box::new(meta),
+      kind,
+    }
+  }
+}
+
+impl NativeMethod for MapIter {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    let entries = this
+      .to_map()
+      .iter()
+      .map(|(key, value)| (*key, *value))
+      .collect();
+
+    let iter: Box<dyn SlIter> = Box::new(MapIterator::new(entries, self.kind));
+    NativeResult::Success(Value::Iter(hooks.manage(iter)))
+  }
+}
+
+/// An iterator over the entries of a map as they were when it was created
+#[derive(Debug)]
+pub struct MapIterator {
+  entries: Vec<(Value, Value)>,
+  kind: MapIterKind,
+  current: Value,
+  index: usize,
+}
+
+impl MapIterator {
+  pub fn new(entries: Vec<(Value, Value)>, kind: MapIterKind) -> Self {
+    Self {
+      entries,
+      kind,
+      current: Value::Nil,
+      index: 0,
+    }
+  }
+}
+
+impl SlIter for MapIterator {
+  fn name(&self) -> &str {
+    match self.kind {
+      MapIterKind::Keys => "Map Keys Iterator",
+      MapIterKind::Values => "Map Values Iterator",
+      MapIterKind::Entries => "Map Entries Iterator",
+    }
+  }
+
+  fn current(&self) -> Value {
+    self.current
+  }
+
+  fn next(&mut self, hooks: &Hooks) -> bool {
+    let (key, value) = match self.entries.get(self.index) {
+      Some(entry) => *entry,
+      None => return false,
+    };
+
+    self.current = match self.kind {
+      MapIterKind::Keys => key,
+      MapIterKind::Values => value,
+      MapIterKind::Entries => Value::List(hooks.manage(vec![key, value])),
+    };
+    self.index += 1;
+    true
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>() + self.entries.capacity() * mem::size_of::<(Value, Value)>()
+  }
+}
+
+impl Trace for MapIterator {
+  fn trace(&self) -> bool {
+    self.entries.iter().for_each(|(key, value)| {
+      key.trace();
+      value.trace();
+    });
+    self.current.trace();
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.entries.iter().for_each(|(key, value)| {
+      key.trace_debug(stdio);
+      value.trace_debug(stdio);
+    });
+    self.current.trace_debug(stdio);
+    true
+  }
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -286,4 +430,64 @@ mod test {
       }
     }
   }
+
+  #[cfg(test)]
+  mod iter {
+    use super::*;
+    use crate::support::{TestContext, test_native_dependencies};
+    use fnv::FnvHashMap;
+
+    #[test]
+    fn new() {
+      let map_iter = MapIter::new(MAP_ENTRIES, MapIterKind::Entries);
+
+      assert_eq!(map_iter.meta.name, "entries");
+      assert_eq!(map_iter.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let mut map = FnvHashMap::default();
+      map.insert(Value::Nil, Value::Bool(false));
+      let this = Value::Map(hooks.manage(map));
+
+      let map_keys = MapIter::new(MAP_KEYS, MapIterKind::Keys);
+      let map_values = MapIter::new(MAP_VALUES, MapIterKind::Values);
+      let map_entries = MapIter::new(MAP_ENTRIES, MapIterKind::Entries);
+
+      match map_keys.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => {
+          let mut iter = r.to_iter();
+          assert!(iter.next(&hooks));
+          assert!(iter.current().is_nil());
+          assert!(!iter.next(&hooks));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match map_values.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => {
+          let mut iter = r.to_iter();
+          assert!(iter.next(&hooks));
+          assert!(!iter.current().to_bool());
+          assert!(!iter.next(&hooks));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match map_entries.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => {
+          let mut iter = r.to_iter();
+          assert!(iter.next(&hooks));
+          assert_eq!(&*iter.current().to_list(), &[Value::Nil, Value::Bool(false)]);
+          assert!(!iter.next(&hooks));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
 }
@@ -1,20 +1,38 @@
+pub mod big_int;
 pub mod bool;
+pub mod channel;
+pub mod class;
+pub mod error;
+pub mod fiber;
 pub mod fun;
+pub mod iter;
 pub mod list;
 pub mod map;
+pub mod module;
 pub mod native;
 pub mod nil;
 pub mod number;
+pub mod range;
 pub mod string;
+pub mod trait_;
 
+use crate::builtin::big_int::create_big_int_class;
 use crate::builtin::bool::create_bool_class;
+use crate::builtin::channel::create_channel_class;
+use crate::builtin::class::create_class_class;
+use crate::builtin::error::create_error_class;
+use crate::builtin::fiber::create_fiber_class;
 use crate::builtin::fun::create_fun_class;
+use crate::builtin::iter::create_iter_class;
 use crate::builtin::list::create_list_class;
 use crate::builtin::map::create_map_class;
+use crate::builtin::module::create_module_class;
 use crate::builtin::native::create_native_class;
 use crate::builtin::nil::create_nil_class;
 use crate::builtin::number::create_number_class;
+use crate::builtin::range::create_range_class;
 use crate::builtin::string::create_string_class;
+use crate::builtin::trait_::create_trait_class;
 use spacelox_core::value::BuiltInClasses;
 use spacelox_core::hooks::Hooks;
 
@@ -23,10 +41,19 @@ pub fn make_builtin_classes(hooks: &Hooks) -> BuiltInClasses {
     bool: create_bool_class(hooks),
     nil: create_nil_class(hooks),
     number: create_number_class(hooks),
+    big_int: create_big_int_class(hooks),
     string: create_string_class(hooks),
     list: create_list_class(hooks),
     map: create_map_class(hooks),
     fun: create_fun_class(hooks),
     native: create_native_class(hooks),
+    error: create_error_class(hooks),
+    iter: create_iter_class(hooks),
+    module: create_module_class(hooks),
+    fiber: create_fiber_class(hooks),
+    channel: create_channel_class(hooks),
+    range: create_range_class(hooks),
+    class: create_class_class(hooks),
+    trait_: create_trait_class(hooks),
   }
 }
@@ -0,0 +1,80 @@
+use spacelox_core::hooks::Hooks;
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::value::{ArityKind, Class, Value};
+
+pub const MODULE_CLASS_NAME: &str = "Module";
+
+const MODULE_NAME: NativeMeta = NativeMeta::new("name", ArityKind::Fixed(0));
+
+pub fn create_module_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(MODULE_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(MODULE_NAME.name)),
+    Value::NativeMethod(hooks.manage(Box::new(ModuleName::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct ModuleName {
+  meta: Box<NativeMeta>,
+}
+
+impl ModuleName {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(MODULE_NAME),
+    }
+  }
+}
+
+impl NativeMethod for ModuleName {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(this.to_module().name))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod name {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+    use spacelox_core::module::Module;
+
+    #[test]
+    fn new() {
+      let module_name = ModuleName::new();
+
+      assert_eq!(module_name.meta.name, "name");
+      assert_eq!(module_name.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let module_name = ModuleName::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let name = hooks.manage_str(String::from("example"));
+      let this = Value::Module(hooks.manage(Module::new(name, None)));
+
+      match module_name.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "example"),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
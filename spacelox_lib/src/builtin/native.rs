@@ -3,7 +3,7 @@ use spacelox_core::managed::Managed;
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::value::{ArityKind, Class, Value};
 
-pub const NATIVE_CLASS_NAME: &'static str = "Native";
+pub const NATIVE_CLASS_NAME: &str = "Native";
 
 const NATIVE_NAME: NativeMeta = NativeMeta::new("name", ArityKind::Fixed(0));
 
@@ -2,7 +2,7 @@ use spacelox_core::managed::{Managed};
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::{hooks::Hooks, value::{ArityKind, Class, Value}};
 
-pub const NIL_CLASS_NAME: &'static str = "Nil";
+pub const NIL_CLASS_NAME: &str = "Nil";
 const NIL_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
 
 pub fn create_nil_class(hooks: &Hooks) -> Managed<Class> {
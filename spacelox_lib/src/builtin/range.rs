@@ -0,0 +1,528 @@
+use spacelox_core::hooks::Hooks;
+use spacelox_core::io::StdIo;
+use spacelox_core::iterator::SlIter;
+use spacelox_core::managed::{Managed, Trace};
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::utils::exact_integer;
+use spacelox_core::value::{ArityKind, Class, Range, Value};
+use std::convert::TryFrom;
+use std::mem;
+
+pub const RANGE_CLASS_NAME: &str = "Range";
+
+const RANGE_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
+const RANGE_SIZE: NativeMeta = NativeMeta::new("size", ArityKind::Fixed(0));
+const RANGE_CONTAINS: NativeMeta = NativeMeta::new("contains", ArityKind::Fixed(1));
+const RANGE_STEP: NativeMeta = NativeMeta::new("step", ArityKind::Fixed(1));
+const RANGE_REVERSE: NativeMeta = NativeMeta::new("reverse", ArityKind::Fixed(0));
+const RANGE_ITER: NativeMeta = NativeMeta::new("iter", ArityKind::Fixed(0));
+
+pub fn create_range_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(RANGE_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(RANGE_STR.name)),
+    Value::NativeMethod(hooks.manage(Box::new(RangeStr::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(RANGE_SIZE.name)),
+    Value::NativeMethod(hooks.manage(Box::new(RangeSize::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(RANGE_CONTAINS.name)),
+    Value::NativeMethod(hooks.manage(Box::new(RangeContains::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(RANGE_STEP.name)),
+    Value::NativeMethod(hooks.manage(Box::new(RangeStep::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(RANGE_REVERSE.name)),
+    Value::NativeMethod(hooks.manage(Box::new(RangeReverse::new()))),
+  );
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(RANGE_ITER.name)),
+    Value::NativeMethod(hooks.manage(Box::new(RangeIter::new()))),
+  );
+
+  class
+}
+
+/// The integer a value holds if it is integral
+fn to_integer(value: Value) -> Option<i64> {
+  match value {
+    Value::Int(int) => Some(int),
+    Value::Number(num) => exact_integer(num),
+    _ => None,
+  }
+}
+
+#[derive(Clone, Debug)]
+struct RangeStr {
+  meta: Box<NativeMeta>,
+}
+
+impl RangeStr {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(RANGE_STR),
+    }
+  }
+}
+
+impl NativeMethod for RangeStr {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(hooks.manage_str(this.to_string())))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct RangeSize {
+  meta: Box<NativeMeta>,
+}
+
+impl RangeSize {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(RANGE_SIZE),
+    }
+  }
+}
+
+impl NativeMethod for RangeSize {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    match i64::try_from(this.to_range().len()) {
+      Ok(size) => NativeResult::Success(Value::Int(size)),
+      Err(_) => NativeResult::RuntimeError(format!(
+        "Range {} has too many values for its size to be an integer.",
+        this
+      )),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+struct RangeContains {
+  meta: Box<NativeMeta>,
+}
+
+impl RangeContains {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(RANGE_CONTAINS),
+    }
+  }
+}
+
+impl NativeMethod for RangeContains {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, args: &[Value]) -> NativeResult {
+    let range = this.to_range();
+    let contains = to_integer(args[0]).is_some_and(|int| range.contains(int));
+    NativeResult::Success(Value::Bool(contains))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct RangeStep {
+  meta: Box<NativeMeta>,
+}
+
+impl RangeStep {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(RANGE_STEP),
+    }
+  }
+}
+
+impl NativeMethod for RangeStep {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, args: &[Value]) -> NativeResult {
+    let step = match to_integer(args[0]) {
+      Some(step) if step > 0 => step,
+      _ => {
+        return NativeResult::RuntimeError(format!(
+          "Range step must be a positive integer, received {}.",
+          args[0]
+        ))
+      }
+    };
+
+    let range = this.to_range();
+    let stepped = Range {
+      step: step * range.step.signum(),
+      ..*range
+    };
+
+    NativeResult::Success(Value::Range(hooks.manage(stepped)))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct RangeReverse {
+  meta: Box<NativeMeta>,
+}
+
+impl RangeReverse {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(RANGE_REVERSE),
+    }
+  }
+}
+
+impl NativeMethod for RangeReverse {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    let reversed = this.to_range().reverse();
+    NativeResult::Success(Value::Range(hooks.manage(reversed)))
+  }
+}
+
+#[derive(Clone, Debug)]
+struct RangeIter {
+  meta: Box<NativeMeta>,
+}
+
+impl RangeIter {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(RANGE_ITER),
+    }
+  }
+}
+
+impl NativeMethod for RangeIter {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    let iter: Box<dyn SlIter> = Box::new(RangeIterator::new(*this.to_range()));
+    NativeResult::Success(Value::Iter(hooks.manage(iter)))
+  }
+}
+
+/// An iterator counting through a range without materializing it
+#[derive(Debug)]
+pub struct RangeIterator {
+  range: Range,
+  current: Value,
+  index: u128,
+}
+
+impl RangeIterator {
+  pub fn new(range: Range) -> Self {
+    Self {
+      range,
+      current: Value::Nil,
+      index: 0,
+    }
+  }
+}
+
+impl SlIter for RangeIterator {
+  fn name(&self) -> &str {
+    "Range Iterator"
+  }
+
+  fn current(&self) -> Value {
+    self.current
+  }
+
+  fn next(&mut self, _hooks: &Hooks) -> bool {
+    match self.range.get(self.index) {
+      Some(int) => {
+        self.current = Value::Int(int);
+        self.index += 1;
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>()
+  }
+}
+
+impl Trace for RangeIterator {
+  fn trace(&self) -> bool {
+    true
+  }
+
+  fn trace_debug(&self, _: &dyn StdIo) -> bool {
+    true
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod str {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let range_str = RangeStr::new();
+
+      assert_eq!(range_str.meta.name, "str");
+      assert_eq!(range_str.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let range_str = RangeStr::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Range(hooks.manage(Range::new(0, 10, false)));
+      match range_str.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "0..10"),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      let this = Value::Range(hooks.manage(Range::new(0, 10, true)));
+      match range_str.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "0..=10"),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod size {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let range_size = RangeSize::new();
+
+      assert_eq!(range_size.meta.name, "size");
+      assert_eq!(range_size.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let range_size = RangeSize::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Range(hooks.manage(Range::new(2, 5, false)));
+      match range_size.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Int(3)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      let this = Value::Range(hooks.manage(Range::new(5, 2, true)));
+      match range_size.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Int(0)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod contains {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let range_contains = RangeContains::new();
+
+      assert_eq!(range_contains.meta.name, "contains");
+      assert_eq!(range_contains.meta.arity, ArityKind::Fixed(1));
+    }
+
+    #[test]
+    fn call() {
+      let range_contains = RangeContains::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Range(hooks.manage(Range::new(0, 10, false)));
+      match range_contains.call(&hooks, this, &[Value::Int(3)]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(true)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match range_contains.call(&hooks, this, &[Value::Number(3.0)]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(true)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match range_contains.call(&hooks, this, &[Value::Number(3.5)]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(false)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match range_contains.call(&hooks, this, &[Value::Int(10)]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(false)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match range_contains.call(&hooks, this, &[Value::Nil]) {
+        NativeResult::Success(r) => assert_eq!(r, Value::Bool(false)),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod step {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let range_step = RangeStep::new();
+
+      assert_eq!(range_step.meta.name, "step");
+      assert_eq!(range_step.meta.arity, ArityKind::Fixed(1));
+    }
+
+    #[test]
+    fn call() {
+      let range_step = RangeStep::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Range(hooks.manage(Range::new(0, 10, false)));
+      match range_step.call(&hooks, this, &[Value::Int(3)]) {
+        NativeResult::Success(r) => {
+          let range = r.to_range();
+          assert_eq!(range.step, 3);
+          assert_eq!(range.len(), 4);
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      let reversed = Value::Range(hooks.manage(Range::new(0, 10, false).reverse()));
+      match range_step.call(&hooks, reversed, &[Value::Int(2)]) {
+        NativeResult::Success(r) => assert_eq!(r.to_range().step, -2),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+
+      match range_step.call(&hooks, this, &[Value::Int(0)]) {
+        NativeResult::Success(_) => assert!(false),
+        NativeResult::RuntimeError(_) => assert!(true),
+      }
+
+      match range_step.call(&hooks, this, &[Value::Number(1.5)]) {
+        NativeResult::Success(_) => assert!(false),
+        NativeResult::RuntimeError(_) => assert!(true),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod reverse {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let range_reverse = RangeReverse::new();
+
+      assert_eq!(range_reverse.meta.name, "reverse");
+      assert_eq!(range_reverse.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let range_reverse = RangeReverse::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Range(hooks.manage(Range::new(0, 3, false)));
+      match range_reverse.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => {
+          let range = r.to_range();
+          assert_eq!(range.start, 2);
+          assert_eq!(range.end, 0);
+          assert_eq!(range.step, -1);
+          assert!(range.inclusive);
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+
+  #[cfg(test)]
+  mod iter {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let range_iter = RangeIter::new();
+
+      assert_eq!(range_iter.meta.name, "iter");
+      assert_eq!(range_iter.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let range_iter = RangeIter::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::Range(hooks.manage(Range::new(1, 3, true)));
+      match range_iter.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => {
+          let mut iter = r.to_iter();
+          assert_eq!(iter.current(), Value::Nil);
+          assert!(iter.next(&hooks));
+          assert_eq!(iter.current(), Value::Int(1));
+          assert!(iter.next(&hooks));
+          assert_eq!(iter.current(), Value::Int(2));
+          assert!(iter.next(&hooks));
+          assert_eq!(iter.current(), Value::Int(3));
+          assert!(!iter.next(&hooks));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
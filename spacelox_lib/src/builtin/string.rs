@@ -1,10 +1,14 @@
 use spacelox_core::hooks::Hooks;
-use spacelox_core::managed::Managed;
+use spacelox_core::io::StdIo;
+use spacelox_core::iterator::SlIter;
+use spacelox_core::managed::{Managed, Trace};
 use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
 use spacelox_core::value::{ArityKind, Class, Value};
+use std::mem;
 
-pub const STRING_CLASS_NAME: &'static str = "String";
+pub const STRING_CLASS_NAME: &str = "String";
 const STRING_STR: NativeMeta = NativeMeta::new("str", ArityKind::Fixed(0));
+const STRING_ITER: NativeMeta = NativeMeta::new("iter", ArityKind::Fixed(0));
 
 pub fn create_string_class(hooks: &Hooks) -> Managed<Class> {
   let name = hooks.manage_str(String::from(STRING_CLASS_NAME));
@@ -16,6 +20,12 @@ pub fn create_string_class(hooks: &Hooks) -> Managed<Class> {
     Value::NativeMethod(hooks.manage(Box::new(StringStr::new()))),
   );
 
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(STRING_ITER.name)),
+    Value::NativeMethod(hooks.manage(Box::new(StringIter::new()))),
+  );
+
   class
 }
 
@@ -41,3 +51,126 @@ impl NativeMethod for StringStr {
     NativeResult::Success(Value::String(this.to_str()))
   }
 }
+
+#[derive(Clone, Debug)]
+struct StringIter {
+  meta: Box<NativeMeta>,
+}
+
+impl StringIter {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(STRING_ITER),
+    }
+  }
+}
+
+impl NativeMethod for StringIter {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    let iter: Box<dyn SlIter> = Box::new(StringIterator::new(this.to_str()));
+    NativeResult::Success(Value::Iter(hooks.manage(iter)))
+  }
+}
+
+/// An iterator over the characters of a string, each as its own string
+#[derive(Debug)]
+pub struct StringIterator {
+  string: Managed<String>,
+  current: Value,
+  index: usize,
+}
+
+impl StringIterator {
+  pub fn new(string: Managed<String>) -> Self {
+    Self {
+      string,
+      current: Value::Nil,
+      index: 0,
+    }
+  }
+}
+
+impl SlIter for StringIterator {
+  fn name(&self) -> &str {
+    "String Iterator"
+  }
+
+  fn current(&self) -> Value {
+    self.current
+  }
+
+  fn next(&mut self, hooks: &Hooks) -> bool {
+    match self.string[self.index..].chars().next() {
+      Some(c) => {
+        self.index += c.len_utf8();
+        self.current = Value::String(hooks.manage_str(c.to_string()));
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>()
+  }
+}
+
+impl Trace for StringIterator {
+  fn trace(&self) -> bool {
+    self.string.trace();
+    self.current.trace();
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.string.trace_debug(stdio);
+    self.current.trace_debug(stdio);
+    true
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod iter {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+
+    #[test]
+    fn new() {
+      let string_iter = StringIter::new();
+
+      assert_eq!(string_iter.meta.name, "iter");
+      assert_eq!(string_iter.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let string_iter = StringIter::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let this = Value::String(hooks.manage_str(String::from("aé")));
+
+      let result = string_iter.call(&hooks, this, &[]);
+      match result {
+        NativeResult::Success(r) => {
+          let mut iter = r.to_iter();
+          assert!(iter.next(&hooks));
+          assert_eq!(&*iter.current().to_str(), "a");
+          assert!(iter.next(&hooks));
+          assert_eq!(&*iter.current().to_str(), "é");
+          assert!(!iter.next(&hooks));
+        }
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
@@ -0,0 +1,80 @@
+use spacelox_core::hooks::Hooks;
+use spacelox_core::managed::Managed;
+use spacelox_core::native::{NativeMeta, NativeMethod, NativeResult};
+use spacelox_core::value::{ArityKind, Class, Value};
+
+pub const TRAIT_CLASS_NAME: &str = "Trait";
+
+const TRAIT_NAME: NativeMeta = NativeMeta::new("name", ArityKind::Fixed(0));
+
+pub fn create_trait_class(hooks: &Hooks) -> Managed<Class> {
+  let name = hooks.manage_str(String::from(TRAIT_CLASS_NAME));
+  let mut class = hooks.manage(Class::new(name));
+
+  class.add_method(
+    hooks,
+    hooks.manage_str(String::from(TRAIT_NAME.name)),
+    Value::NativeMethod(hooks.manage(Box::new(TraitName::new()))),
+  );
+
+  class
+}
+
+#[derive(Clone, Debug)]
+struct TraitName {
+  meta: Box<NativeMeta>,
+}
+
+impl TraitName {
+  fn new() -> Self {
+    Self {
+      meta: Box::new(TRAIT_NAME),
+    }
+  }
+}
+
+impl NativeMethod for TraitName {
+  fn meta(&self) -> &NativeMeta {
+    &self.meta
+  }
+
+  fn call(&self, _hooks: &Hooks, this: Value, _args: &[Value]) -> NativeResult {
+    NativeResult::Success(Value::String(this.to_trait().name))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[cfg(test)]
+  mod name {
+    use super::*;
+    use crate::support::{test_native_dependencies, TestContext};
+    use spacelox_core::value::Trait;
+
+    #[test]
+    fn new() {
+      let trait_name = TraitName::new();
+
+      assert_eq!(trait_name.meta.name, "name");
+      assert_eq!(trait_name.meta.arity, ArityKind::Fixed(0));
+    }
+
+    #[test]
+    fn call() {
+      let trait_name = TraitName::new();
+      let gc = test_native_dependencies();
+      let mut context = TestContext::new(&gc);
+      let hooks = Hooks::new(&mut context);
+
+      let name = hooks.manage_str(String::from("Comparable"));
+      let this = Value::Trait(hooks.manage(Trait::new(name)));
+
+      match trait_name.call(&hooks, this, &[]) {
+        NativeResult::Success(r) => assert_eq!(&*r.to_str(), "Comparable"),
+        NativeResult::RuntimeError(_) => assert!(false),
+      }
+    }
+  }
+}
@@ -1,4 +1,8 @@
 #![deny(clippy::all)]
+#![cfg_attr(
+  test,
+  allow(clippy::assertions_on_constants, clippy::bool_assert_comparison)
+)]
 pub mod assert;
 pub mod builtin;
 pub mod time;
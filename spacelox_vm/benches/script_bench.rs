@@ -13,7 +13,7 @@ fn fixture_path(bench_path: &str) -> Option<PathBuf> {
     .parent()
     .and_then(|path| path.parent())
     .and_then(|path| path.parent())
-    .and_then(|path| Some(path.join("fixture").join("criterion").join(bench_path)))
+    .map(|path| path.join("fixture").join("criterion").join(bench_path))
 }
 
 fn load_source(dir: &str) -> String {
@@ -1,14 +1,14 @@
 use crate::scanner::Scanner;
+use fnv::FnvHashSet;
 use spacelox_core::chunk::{AlignedByteCode, Chunk, UpvalueIndex};
 use spacelox_core::io::{Io, StdIo};
 use spacelox_core::managed::{Manage, Managed, Trace};
 use spacelox_core::token::{Token, TokenKind};
 use spacelox_core::utils::{copy_string, do_if_some};
 use spacelox_core::{
-  constants::{INIT, SCRIPT, SUPER, THIS},
+  constants::{AS, CURRENT, FROM, GET, INIT, ITER, LAMBDA, NEXT, SCRIPT, SET, STR, SUPER, THIS},
   value::{ArityKind, Fun, FunKind, Value}, hooks::Hooks,
 };
-use std::convert::TryInto;
 use std::mem;
 
 #[cfg(feature = "debug")]
@@ -21,10 +21,142 @@ pub struct CompilerResult {
 
   /// The chunk that was compiled
   pub fun: Managed<Fun>,
+
+  /// The names of the script's globals declared const
+  pub const_globals: FnvHashSet<String>,
 }
 
 const UNINITIALIZED: i16 = -1;
 
+/// Names of the hidden locals used by try statements. Neither
+/// can be written as an identifier so they cannot be shadowed.
+/// Once the finally block completes the rethrow slot decides how the
+/// statement is left, nil continues, true rethrows the error slot,
+/// false returns the value in the error slot and an int takes the
+/// loop jump recorded at that index
+const ERROR_SLOT: &str = "@error";
+const RETHROW_SLOT: &str = "@rethrow";
+
+/// Name of the hidden local holding the iterator of a for-in loop
+const ITER_SLOT: &str = "@iter";
+
+/// Name of the hidden local holding the subject of a match statement
+const MATCH_SLOT: &str = "@match";
+
+/// Error reported when assigning to a const variable
+const CONST_ASSIGNMENT: &str = "Cannot assign to a const variable.";
+
+/// Name of the pattern matching any value without binding it
+const WILDCARD: &str = "_";
+
+/// A loop currently being compiled
+#[derive(Debug, Clone)]
+struct Loop {
+  /// optional label of the loop
+  label: Option<String>,
+
+  /// instruction continue statements jump back to
+  start: usize,
+
+  /// scope depth outside of the loop body
+  scope_depth: i16,
+
+  /// number of active exception handlers outside of the loop body
+  handler_count: usize,
+
+  /// break jumps to patch once the loop ends
+  breaks: Vec<usize>,
+}
+
+/// A try statement whose try or catch block is currently being compiled
+#[derive(Debug, Clone)]
+struct Try {
+  /// scope depth of the try statement's hidden locals
+  scope_depth: i16,
+
+  /// slot of the hidden error local, the rethrow local follows it
+  slot: u8,
+
+  /// number of active exception handlers outside of the try statement
+  handler_count: usize,
+
+  /// number of loops enclosing the try statement
+  loop_count: usize,
+
+  /// does a return inside the statement wait on the finally block
+  returns: bool,
+
+  /// loop jumps waiting on the finally block
+  exits: Vec<LoopExit>,
+
+  /// jumps to the finally block to patch once it is reached
+  jumps: Vec<usize>,
+}
+
+/// A break or continue leaving a try statement inside the loop it targets
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LoopExit {
+  /// break out of the loop at this index
+  Break(usize),
+
+  /// continue the loop at this index
+  Continue(usize),
+}
+
+/// A pattern in a match statement case
+#[derive(Debug, Clone)]
+enum Pattern {
+  /// matches anything without binding it
+  Wildcard,
+
+  /// matches anything binding it to a new local
+  Binding(Token),
+
+  /// matches a value equal to a literal
+  Literal(AlignedByteCode),
+
+  /// matches a list by its elements with an optional rest pattern
+  List(Vec<Pattern>, Option<Box<Pattern>>),
+
+  /// matches a map containing each key
+  Map(Vec<(u8, Pattern)>),
+
+  /// matches an instance of a class by its fields
+  Instance(Vec<Token>, Vec<(u8, Pattern)>),
+}
+
+/// A step from a match subject to a nested value
+#[derive(Debug, Clone, Copy)]
+enum PatternStep {
+  /// index into a list with a position
+  Element(u8),
+
+  /// index into a map with a constant key
+  Key(u8),
+
+  /// get a property of an instance
+  Field(u8),
+
+  /// take the remainder of a list
+  Rest(u8),
+}
+
+/// The kind of assignment following an assignable target
+#[derive(Debug, Clone, Copy)]
+enum Assignment {
+  /// plain assignment with '='
+  Set,
+
+  /// assignment combined with a binary operator such as '+='
+  Compound(AlignedByteCode),
+
+  /// prefix '++' or '--' evaluating to the updated value
+  Prefix(AlignedByteCode),
+
+  /// postfix '++' or '--' evaluating to the original value
+  Postfix(AlignedByteCode),
+}
+
 #[derive(Debug, Clone)]
 pub struct Local {
   /// name of the local
@@ -35,6 +167,9 @@ pub struct Local {
 
   /// is this local captured
   is_captured: bool,
+
+  /// is this local const
+  is_const: bool,
 }
 
 /// The spacelox compiler for converting tokens to bytecode
@@ -73,6 +208,27 @@ pub struct Compiler<'a, 's, I: Io + 'static> {
 
   /// upvalues in this function
   upvalues: Vec<UpvalueIndex>,
+
+  /// loops enclosing the current statement
+  loops: Vec<Loop>,
+
+  /// number of exception handlers active at the current statement
+  handler_count: usize,
+
+  /// try statements enclosing the current statement
+  tries: Vec<Try>,
+
+  /// names of the globals declared const in this script
+  const_globals: FnvHashSet<String>,
+
+  /// names exported from this script
+  exports: FnvHashSet<String>,
+
+  /// assignments to globals to check once the script is compiled
+  global_assignments: Vec<Token>,
+
+  /// operator of a prefix increment waiting for its target
+  prefix_increment: Option<AlignedByteCode>,
 }
 
 impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
@@ -84,6 +240,7 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
   /// ```
   /// use spacelox_vm::compiler::{Compiler, Parser};
   /// use spacelox_core::memory::Gc;
+  /// use spacelox_core::hooks::{Hooks, NoContext};
   /// use spacelox_core::io::{NativeIo, NativeStdIo};
   ///
   /// // an expression
@@ -91,8 +248,10 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
   ///
   /// let mut gc = Gc::new(Box::new(NativeStdIo::new()));
   /// let mut parser = Parser::new(NativeStdIo::new(), &source);
+  /// let mut context = NoContext::new(&mut gc);
+  /// let hooks = Hooks::new(&mut context);
   ///
-  /// let compiler = Compiler::new(NativeIo::new(), &mut parser, &mut gc);
+  /// let compiler = Compiler::new(NativeIo::new(), &mut parser, &hooks);
   /// ```
   pub fn new(io: I, parser: &'a mut Parser<'s, I::StdIo>, hooks: &'a Hooks) -> Self {
     let fun = hooks.manage(
@@ -114,10 +273,18 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
           name: Option::None,
           depth: UNINITIALIZED,
           is_captured: false,
+          is_const: false,
         };
-        std::u8::MAX as usize
+        u8::MAX as usize
       ],
-      upvalues: vec![UpvalueIndex::Local(0); std::u8::MAX as usize],
+      upvalues: vec![UpvalueIndex::Local(0); u8::MAX as usize],
+      loops: Vec::new(),
+      handler_count: 0,
+      tries: Vec::new(),
+      const_globals: FnvHashSet::default(),
+      exports: FnvHashSet::default(),
+      global_assignments: Vec::new(),
+      prefix_increment: None,
     };
 
     compiler.locals[0] = first_local(FunKind::Script);
@@ -141,10 +308,18 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
           name: Option::None,
           depth: UNINITIALIZED,
           is_captured: false,
+          is_const: false,
         };
-        std::u8::MAX as usize
+        u8::MAX as usize
       ],
-      upvalues: vec![UpvalueIndex::Local(0); std::u8::MAX as usize],
+      upvalues: vec![UpvalueIndex::Local(0); u8::MAX as usize],
+      loops: Vec::new(),
+      handler_count: 0,
+      tries: Vec::new(),
+      const_globals: FnvHashSet::default(),
+      exports: FnvHashSet::default(),
+      global_assignments: Vec::new(),
+      prefix_increment: None,
     };
 
     child.fun = child.hooks.manage(
@@ -163,6 +338,7 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
   /// ```
   /// use spacelox_vm::compiler::{Compiler, Parser};
   /// use spacelox_core::memory::Gc;
+  /// use spacelox_core::hooks::{Hooks, NoContext};
   /// use spacelox_core::io::{NativeIo, NativeStdIo};
   ///
   /// // an expression
@@ -170,8 +346,10 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
   ///
   /// let mut gc = Gc::new(Box::new(NativeStdIo::new()));
   /// let mut parser = Parser::new(NativeStdIo::new(), &source);
+  /// let mut context = NoContext::new(&mut gc);
+  /// let hooks = Hooks::new(&mut context);
   ///
-  /// let compiler = Compiler::new(NativeIo::new(), &mut parser, &mut gc);
+  /// let compiler = Compiler::new(NativeIo::new(), &mut parser, &hooks);
   /// let result = compiler.compile();
   /// assert_eq!(result.success, true);
   /// ```
@@ -185,6 +363,7 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
       return CompilerResult {
         success: !self.parser.had_error,
         fun: self.fun,
+        const_globals: self.const_globals,
       };
     }
 
@@ -195,14 +374,23 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     self
       .parser
       .consume(TokenKind::Eof, "Expected end of expression.");
+    self.check_global_assignments();
     self.end_compiler();
 
     CompilerResult {
       success: !self.parser.had_error,
       fun: self.fun,
+      const_globals: self.const_globals,
     }
   }
 
+  /// Treat these globals as already declared const, as they are when
+  /// the repl compiles another line of the same script
+  pub fn with_const_globals(mut self, const_globals: FnvHashSet<String>) -> Self {
+    self.const_globals = const_globals;
+    self
+  }
+
   /// The current chunk
   fn current_chunk(&mut self) -> &Chunk {
     self.fun.chunk()
@@ -215,12 +403,27 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
 
   /// Parse a declaration
   fn declaration(&mut self) {
+    let doc = self.parser.take_doc();
+
     if self.parser.match_kind(TokenKind::Class) {
-      self.class_declaration();
-    } else if self.parser.match_kind(TokenKind::Fun) {
-      self.fun_declaration();
+      self.class_declaration(doc);
+    } else if self.parser.match_kind(TokenKind::Trait) {
+      self.trait_declaration();
+    } else if self.parser.check(TokenKind::Fun)
+      && self.parser.peek_kind() != TokenKind::LeftParen
+    {
+      self.parser.advance();
+      self.fun_declaration(doc);
     } else if self.parser.match_kind(TokenKind::Var) {
       self.var_declaration();
+    } else if self.parser.match_kind(TokenKind::Const) {
+      self.const_declaration();
+    } else if self.parser.match_kind(TokenKind::Import) {
+      self.import_declaration();
+    } else if self.is_from_import() {
+      self.selective_import_declaration();
+    } else if self.parser.match_kind(TokenKind::Export) {
+      self.export_declaration(doc);
     } else {
       self.statement();
     }
@@ -235,13 +438,27 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     if self.parser.match_kind(TokenKind::Print) {
       self.print_statement();
     } else if self.parser.match_kind(TokenKind::For) {
-      self.for_statement();
+      self.for_statement(None);
+    } else if self.parser.match_kind(TokenKind::Break) {
+      self.break_statement();
+    } else if self.parser.match_kind(TokenKind::Continue) {
+      self.continue_statement();
     } else if self.parser.match_kind(TokenKind::If) {
       self.if_statement();
+    } else if self.parser.match_kind(TokenKind::Match) {
+      self.match_statement();
     } else if self.parser.match_kind(TokenKind::Return) {
       self.return_statement();
+    } else if self.parser.match_kind(TokenKind::Throw) {
+      self.throw_statement();
+    } else if self.parser.match_kind(TokenKind::Try) {
+      self.try_statement();
     } else if self.parser.match_kind(TokenKind::While) {
-      self.while_statement();
+      self.while_statement(None);
+    } else if self.parser.check(TokenKind::Identifier)
+      && self.parser.peek_kind() == TokenKind::Colon
+    {
+      self.labeled_statement();
     } else if self.parser.match_kind(TokenKind::LeftBrace) {
       self.begin_scope();
       self.block();
@@ -253,7 +470,10 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
 
   /// Parse an expression
   fn expression(&mut self) {
-    self.parse_precedence(Precedence::Assignment)
+    // a nested expression is never the target of an enclosing prefix increment
+    let prefix_increment = self.prefix_increment.take();
+    self.parse_precedence(Precedence::Assignment);
+    self.prefix_increment = prefix_increment;
   }
 
   /// Parse a block statement
@@ -268,7 +488,7 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
   }
 
   /// Parse a function declaration
-  fn class_declaration(&mut self) {
+  fn class_declaration(&mut self, doc: Option<String>) {
     self
       .parser
       .consume(TokenKind::Identifier, "Expect class name.");
@@ -278,12 +498,17 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     self.declare_variable();
 
     self.emit_byte(AlignedByteCode::Class(name_constant));
+    if let Some(doc) = doc {
+      let doc_constant = self.name_constant(&doc);
+      self.emit_byte(AlignedByteCode::Doc(doc_constant));
+    }
     self.define_variable(name_constant);
 
     let mut class_compiler = self.hooks.manage(
       ClassCompiler {
         name: self.parser.previous.clone(),
         has_super_class: false,
+        is_trait: false,
         enclosing: self.current_class,
       },
     );
@@ -313,6 +538,24 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
       class_compiler.has_super_class = true;
     }
 
+    let mut traits: Vec<Token> = Vec::new();
+    if self.parser.match_kind(TokenKind::With) {
+      loop {
+        self
+          .parser
+          .consume(TokenKind::Identifier, "Expect trait name.");
+        traits.push(self.parser.previous.clone());
+
+        if traits.len() > u8::MAX as usize {
+          self.parser.error("Cannot mix in more than 255 traits.");
+        }
+
+        if !self.parser.match_kind(TokenKind::Comma) {
+          break;
+        }
+      }
+    }
+
     self.named_variable(class_name, false);
 
     self
@@ -325,6 +568,17 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     self
       .parser
       .consume(TokenKind::RightBrace, "Expect '}' after class body.");
+
+    // traits are mixed in once the class body is known so the class's
+    // own methods take precedence
+    if !traits.is_empty() {
+      let count = traits.len() as u8;
+      for trait_name in traits {
+        self.named_variable(trait_name, false);
+      }
+      self.emit_byte(AlignedByteCode::Mixin(count));
+    }
+
     self.emit_byte(AlignedByteCode::Pop);
 
     if class_compiler.has_super_class {
@@ -334,64 +588,241 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     self.current_class = class_compiler.enclosing;
   }
 
+  /// Parse a trait declaration
+  fn trait_declaration(&mut self) {
+    self
+      .parser
+      .consume(TokenKind::Identifier, "Expect trait name.");
+
+    let trait_name = self.parser.previous.clone();
+    let name_constant = self.identifer_constant(self.parser.previous.clone());
+    self.declare_variable();
+
+    self.emit_byte(AlignedByteCode::Trait(name_constant));
+    self.define_variable(name_constant);
+
+    let class_compiler = self.hooks.manage(ClassCompiler {
+      name: self.parser.previous.clone(),
+      has_super_class: false,
+      is_trait: true,
+      enclosing: self.current_class,
+    });
+    self.current_class = Some(class_compiler);
+
+    self.named_variable(trait_name, false);
+
+    self
+      .parser
+      .consume(TokenKind::LeftBrace, "Expect '{' before trait body.");
+    while !self.parser.check(TokenKind::RightBrace) && !self.parser.check(TokenKind::Eof) {
+      self.trait_method();
+    }
+
+    self
+      .parser
+      .consume(TokenKind::RightBrace, "Expect '}' after trait body.");
+    self.emit_byte(AlignedByteCode::Pop);
+
+    self.current_class = class_compiler.enclosing;
+  }
+
+  /// Parse a trait method. A method signature ending in a semicolon
+  /// is a method classes mixing in the trait are required to define
+  fn trait_method(&mut self) {
+    let doc = self.parser.take_doc();
+    self
+      .parser
+      .consume(TokenKind::Identifier, "Expect method name.");
+    let constant = self.identifer_constant(self.parser.previous.clone());
+
+    if INIT == self.parser.previous.lexeme {
+      self
+        .parser
+        .error("Cannot define an initializer in a trait.");
+    }
+
+    let name = self
+      .hooks
+      .manage_str(self.parser.previous.lexeme.to_string());
+
+    self
+      .parser
+      .consume(TokenKind::LeftParen, "Expect '(' after method name.");
+
+    // a required method is a signature ending in a semicolon
+    if !self
+      .parser
+      .after_group(1, |kind| kind == TokenKind::Semicolon)
+    {
+      self.function_body(name, FunKind::Method, false, doc);
+      self.emit_byte(AlignedByteCode::Method(constant));
+      return;
+    }
+
+    while !self.parser.check(TokenKind::RightParen) {
+      self.parser.match_kind(TokenKind::Ellipsis);
+      self
+        .parser
+        .consume(TokenKind::Identifier, "Expect parameter name.");
+
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
+      }
+    }
+
+    self
+      .parser
+      .consume(TokenKind::RightParen, "Expect ')' after parameters.");
+    self
+      .parser
+      .consume(TokenKind::Semicolon, "Expect ';' after required method.");
+    self.emit_byte(AlignedByteCode::Require(constant));
+  }
+
   /// Parse a function declaration
-  fn fun_declaration(&mut self) {
+  fn fun_declaration(&mut self, doc: Option<String>) {
     let global = self.parse_variable("Expect variable name.");
 
     self.mark_initialized();
-    self.function(FunKind::Fun);
+    self.function(FunKind::Fun, doc);
     self.define_variable(global);
   }
 
   /// Parse a function declaration and body
-  fn function(&mut self, fun_kind: FunKind) {
+  fn function(&mut self, fun_kind: FunKind, doc: Option<String>) {
     let name = self
       .hooks
       .manage_str(self.parser.previous.lexeme.to_string());
 
-    let mut fun_compiler = Compiler::child(name, fun_kind, &mut *self);
-    fun_compiler.begin_scope();
-
-    fun_compiler
+    self
       .parser
       .consume(TokenKind::LeftParen, "Expect '(' after function name.");
+    self.function_body(name, fun_kind, false, doc);
+  }
 
-    // parse function parameters
-    if !fun_compiler.parser.check(TokenKind::RightParen) {
-      let mut arity: u16 = 0;
+  /// Parse a function's parameters. Default values are compiled in order
+  /// at the start of the function, each pushing its parameter's slot
+  fn parameters(&mut self) {
+    let mut arity: u16 = 0;
+    let mut required: u16 = 0;
+    let mut entry_points: Vec<u32> = Vec::new();
+    let mut has_rest = false;
 
-      loop {
-        arity += 1;
-        if arity == std::u8::MAX as u16 {
-          fun_compiler
-            .parser
-            .error_at_current("Cannot have more than 255 parameters.");
-        }
+    while !self.parser.check(TokenKind::RightParen) {
+      arity += 1;
+      if arity == u8::MAX as u16 {
+        self
+          .parser
+          .error_at_current("Cannot have more than 255 parameters.");
+      }
 
-        let param_constant = fun_compiler.parse_variable("Expect parameter name.");
-        fun_compiler.define_variable(param_constant);
+      if self.parser.match_kind(TokenKind::Ellipsis) {
+        let param_constant = self.parse_variable("Expect rest parameter name.");
+        self.define_variable(param_constant);
+        has_rest = true;
 
-        if !fun_compiler.parser.match_kind(TokenKind::Comma) {
-          break;
+        if self.parser.check(TokenKind::Comma) {
+          self.parser.error_at_current("Rest parameter must be last.");
         }
+        break;
+      }
+
+      let param_constant = self.parse_variable("Expect parameter name.");
+
+      if self.parser.match_kind(TokenKind::Equal) {
+        entry_points.push(self.current_chunk().instructions.len() as u32);
+        self.expression();
+      } else if !entry_points.is_empty() {
+        self
+          .parser
+          .error("Parameter without a default cannot follow one with a default.");
+      } else {
+        required += 1;
+      }
+
+      self.define_variable(param_constant);
+
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
       }
+    }
+
+    let required = required as u8;
+    let defaults = entry_points.len() as u8;
+
+    // when no extra arguments are supplied the rest parameter is an empty list
+    if has_rest {
+      entry_points.push(self.current_chunk().instructions.len() as u32);
+      self.emit_byte(AlignedByteCode::List);
+    }
+
+    self.fun.arity = if has_rest {
+      ArityKind::Variadic(required)
+    } else if defaults > 0 {
+      ArityKind::Default(required, required.saturating_add(defaults))
+    } else {
+      ArityKind::Fixed(required)
+    };
+
+    if !entry_points.is_empty() {
+      entry_points.push(self.current_chunk().instructions.len() as u32);
+      self.fun.entry_points = entry_points;
+    }
+  }
+
+  /// Parse a function's parameters and body after the opening paren
+  fn function_body(
+    &mut self,
+    name: Managed<String>,
+    fun_kind: FunKind,
+    arrow: bool,
+    doc: Option<String>,
+  ) {
+    let mut fun_compiler = Compiler::child(name, fun_kind, &mut *self);
+    if let Some(doc) = doc {
+      fun_compiler.fun.doc = Some(fun_compiler.hooks.manage_str(doc));
+    }
+    fun_compiler.begin_scope();
+
+    fun_compiler.parameters();
 
-      fun_compiler.fun.arity = ArityKind::Fixed(arity as u8);
+    match (&fun_compiler.fun_kind, fun_compiler.fun.arity) {
+      (FunKind::Getter, ArityKind::Fixed(0)) | (FunKind::Setter, ArityKind::Fixed(1)) => (),
+      (FunKind::Getter, _) => fun_compiler
+        .parser
+        .error("A getter cannot have parameters."),
+      (FunKind::Setter, _) => fun_compiler
+        .parser
+        .error("A setter must have exactly one parameter."),
+      _ => (),
     }
 
     fun_compiler
       .parser
       .consume(TokenKind::RightParen, "Expect ')' after parameters.");
 
-    fun_compiler
-      .parser
-      .consume(TokenKind::LeftBrace, "Expect '{' before function body.");
-    fun_compiler.block();
+    if arrow {
+      fun_compiler
+        .parser
+        .consume(TokenKind::Arrow, "Expect '=>' after parameters.");
+
+      // arrow functions take either a block or a single expression
+      if fun_compiler.parser.match_kind(TokenKind::LeftBrace) {
+        fun_compiler.block();
+      } else {
+        fun_compiler.expression();
+        fun_compiler.emit_byte(AlignedByteCode::Return);
+      }
+    } else {
+      fun_compiler
+        .parser
+        .consume(TokenKind::LeftBrace, "Expect '{' before function body.");
+      fun_compiler.block();
+    }
 
     // end compilation of function chunk
     fun_compiler.end_compiler();
     let upvalue_count = fun_compiler.fun.upvalue_count;
-    // let boxed_fun = Box::new(fun_compiler.fun);
 
     let index = self.make_constant(Value::Fun(fun_compiler.fun));
     self.emit_byte(AlignedByteCode::Closure(index));
@@ -403,6 +834,18 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
   }
 
   fn method(&mut self) {
+    let doc = self.parser.take_doc();
+
+    if self.parser.match_kind(TokenKind::Static) {
+      self.static_member(doc);
+      return;
+    }
+
+    if self.is_accessor() {
+      self.accessor(doc);
+      return;
+    }
+
     self
       .parser
       .consume(TokenKind::Identifier, "Expect method name.");
@@ -414,12 +857,78 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
       FunKind::Method
     };
 
-    self.function(fun_kind);
+    self.function(fun_kind, doc);
     self.emit_byte(AlignedByteCode::Method(constant));
   }
 
+  /// Is the current token a contextual `get` or `set` followed by a property name
+  fn is_accessor(&mut self) -> bool {
+    self.parser.check(TokenKind::Identifier)
+      && (self.parser.current.lexeme == GET || self.parser.current.lexeme == SET)
+      && self.parser.peek_kind() == TokenKind::Identifier
+  }
+
+  /// Parse a property getter or setter
+  fn accessor(&mut self, doc: Option<String>) {
+    self.parser.advance();
+    let is_getter = self.parser.previous.lexeme == GET;
+
+    self
+      .parser
+      .consume(TokenKind::Identifier, "Expect property name.");
+    let constant = self.identifer_constant(self.parser.previous.clone());
+
+    if is_getter {
+      self.function(FunKind::Getter, doc);
+      self.emit_byte(AlignedByteCode::Getter(constant));
+    } else {
+      self.function(FunKind::Setter, doc);
+      self.emit_byte(AlignedByteCode::Setter(constant));
+    }
+  }
+
+  /// Parse a static method or class field. Static methods are called with
+  /// the class as `this`, class fields are set on the class in order
+  fn static_member(&mut self, doc: Option<String>) {
+    self
+      .parser
+      .consume(TokenKind::Identifier, "Expect static member name.");
+    let constant = self.identifer_constant(self.parser.previous.clone());
+
+    if self.parser.check(TokenKind::LeftParen) {
+      self.function(FunKind::Method, doc);
+      self.emit_byte(AlignedByteCode::StaticMethod(constant));
+      return;
+    }
+
+    let class_name = match self.current_class {
+      Some(class_compiler) => class_compiler.name.clone(),
+      None => return,
+    };
+
+    self.named_variable(class_name, false);
+    if self.parser.match_kind(TokenKind::Equal) {
+      self.expression();
+    } else {
+      self.emit_byte(AlignedByteCode::Nil);
+    }
+    self.emit_bytes(AlignedByteCode::SetProperty(constant), AlignedByteCode::Pop);
+
+    self
+      .parser
+      .consume(TokenKind::Semicolon, "Expect ';' after class field.");
+  }
+
   /// Parse a variable declaration
   fn var_declaration(&mut self) {
+    if self.parser.check(TokenKind::LeftBracket)
+      || self.parser.check(TokenKind::LeftBrace)
+      || self.parser.check(TokenKind::MapOpen)
+    {
+      self.destructuring_declaration();
+      return;
+    }
+
     let global = self.parse_variable("Expect variable name.");
 
     if self.parser.match_kind(TokenKind::Equal) {
@@ -436,34 +945,252 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     self.define_variable(global);
   }
 
-  /// Parse an expression statement
-  fn expression_statement(&mut self) {
-    self.expression();
-    self
-      .parser
-      .consume(TokenKind::Semicolon, "Expected ';' after expression.");
-    self.emit_byte(AlignedByteCode::Pop)
-  }
-
-  /// Parse for loop
-  fn for_statement(&mut self) {
-    self.begin_scope();
-    self
-      .parser
-      .consume(TokenKind::LeftParen, "Expected '(' after 'for'.");
-    // parse an initializer
-    if self.parser.match_kind(TokenKind::Semicolon) {
-    } else if self.parser.match_kind(TokenKind::Var) {
-      self.var_declaration();
-    } else {
-      self.expression_statement();
+  /// Parse a variable declaration destructuring a list or map into
+  /// several variables
+  fn destructuring_declaration(&mut self) {
+    let mut names: Vec<String> = Vec::new();
+    let pattern = self.pattern(&mut names);
+    if !is_destructuring_pattern(&pattern) {
+      self
+        .parser
+        .error("Can only destructure into names, lists and maps.");
     }
 
-    let mut loop_start = self.current_chunk().instructions.len();
+    let mut bindings: Vec<(Token, Vec<PatternStep>)> = Vec::new();
+    pattern_bindings(&pattern, &mut Vec::new(), &mut bindings);
 
-    // parse loop condition
-    let mut exit_jump: Option<usize> = Option::None;
-    if !self.parser.match_kind(TokenKind::Semicolon) {
+    // reserve each local before the initializer so it can't reference them
+    let first = self.local_count;
+    for (name, _) in &bindings {
+      self.declare_name(name.clone());
+      if self.scope_depth > 0 {
+        self.emit_byte(AlignedByteCode::Nil);
+      }
+    }
+
+    self
+      .parser
+      .consume(TokenKind::Equal, "Expect '=' after destructuring pattern.");
+    self.expression();
+    self.parser.consume(
+      TokenKind::Semicolon,
+      "Expect ';' after variable declaration.",
+    );
+
+    let subject = self.local_count as u8;
+    self.destructure_checks(subject, &pattern, &mut Vec::new());
+
+    for (index, (name, path)) in bindings.into_iter().enumerate() {
+      self.load_pattern_path(subject, &path);
+
+      if self.scope_depth > 0 {
+        self.emit_bytes(
+          AlignedByteCode::SetLocal((first + index) as u8),
+          AlignedByteCode::Pop,
+        );
+      } else {
+        let global = self.identifer_constant(name);
+        self.emit_byte(AlignedByteCode::DefineGlobal(global));
+      }
+    }
+    self.emit_byte(AlignedByteCode::Pop);
+
+    if self.scope_depth > 0 {
+      for local in &mut self.locals[first..self.local_count] {
+        local.depth = self.scope_depth;
+      }
+    }
+  }
+
+  /// Parse a const declaration
+  fn const_declaration(&mut self) {
+    let global = self.parse_variable("Expect constant name.");
+    let name = self.parser.previous.clone();
+
+    self
+      .parser
+      .consume(TokenKind::Equal, "Expect '=' after constant name.");
+    self.expression();
+    self.parser.consume(
+      TokenKind::Semicolon,
+      "Expect ';' after constant declaration.",
+    );
+
+    self.mark_const(name);
+    self.define_variable(global);
+  }
+
+  /// Mark the variable just declared as const
+  fn mark_const(&mut self, name: Token) {
+    if self.scope_depth > 0 {
+      self.locals[self.local_count - 1].is_const = true;
+    } else {
+      self.script_compiler().const_globals.insert(name.lexeme);
+    }
+  }
+
+  /// Parse an import binding the whole module to a name
+  fn import_declaration(&mut self) {
+    let path = self.module_path();
+
+    if !(self.parser.check(TokenKind::Identifier) && self.parser.current.lexeme == AS) {
+      self
+        .parser
+        .error_at_current("Expect 'as' after module path.");
+      return;
+    }
+    self.parser.advance();
+
+    let global = self.parse_variable("Expect module name.");
+    self.emit_byte(AlignedByteCode::Import(path));
+    self
+      .parser
+      .consume(TokenKind::Semicolon, "Expect ';' after import.");
+
+    self.define_variable(global);
+  }
+
+  /// Is the current token the start of a from import
+  fn is_from_import(&mut self) -> bool {
+    self.parser.check(TokenKind::Identifier)
+      && self.parser.current.lexeme == FROM
+      && self.parser.peek_kind() == TokenKind::String
+  }
+
+  /// Parse an import binding individual exports to names
+  fn selective_import_declaration(&mut self) {
+    self.parser.advance();
+    let path = self.module_path();
+    self
+      .parser
+      .consume(TokenKind::Import, "Expect 'import' after module path.");
+
+    loop {
+      let global = self.parse_variable("Expect name to import.");
+      let token = self.parser.previous.clone();
+      let name = self.identifer_constant(token.clone());
+
+      self.emit_byte(AlignedByteCode::Import(path));
+      self.emit_byte(AlignedByteCode::GetProperty(name));
+
+      // an imported name is bound to the export, it cannot be reassigned
+      self.mark_const(token);
+      self.define_variable(global);
+
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
+      }
+    }
+
+    self
+      .parser
+      .consume(TokenKind::Semicolon, "Expect ';' after import.");
+  }
+
+  /// Parse the path of an imported module
+  fn module_path(&mut self) -> u8 {
+    self
+      .parser
+      .consume(TokenKind::String, "Expect module path.");
+    let path = self.hooks.manage_str(copy_string(&self.parser.previous));
+    self.make_constant(Value::String(path))
+  }
+
+  /// Parse a declaration visible to importing modules
+  fn export_declaration(&mut self, doc: Option<String>) {
+    if self.fun_kind != FunKind::Script || self.scope_depth > 0 {
+      self
+        .parser
+        .error("Can only export from the top level of a module.");
+    }
+
+    if !self.parser.match_kind(TokenKind::Class)
+      && !self.parser.match_kind(TokenKind::Trait)
+      && !self.parser.match_kind(TokenKind::Fun)
+      && !self.parser.match_kind(TokenKind::Var)
+      && !self.parser.match_kind(TokenKind::Const)
+    {
+      self
+        .parser
+        .error_at_current("Expect class, trait, function or variable declaration after 'export'.");
+      return;
+    }
+
+    let name = self.parser.current.clone();
+    if !self.exports.insert(name.lexeme.clone()) {
+      self
+        .parser
+        .error_at(name.clone(), "Name already exported from this module.");
+    }
+
+    match self.parser.previous.kind {
+      TokenKind::Class => self.class_declaration(doc),
+      TokenKind::Trait => self.trait_declaration(),
+      TokenKind::Fun => self.fun_declaration(doc),
+      TokenKind::Const => self.const_declaration(),
+      _ => self.var_declaration(),
+    }
+
+    let name = self.identifer_constant(name);
+    self.emit_byte(AlignedByteCode::Export(name));
+  }
+
+  /// Parse an expression statement
+  fn expression_statement(&mut self) {
+    self.expression();
+    self
+      .parser
+      .consume(TokenKind::Semicolon, "Expected ';' after expression.");
+    self.emit_byte(AlignedByteCode::Pop)
+  }
+
+  /// Parse a loop with a label
+  fn labeled_statement(&mut self) {
+    self.parser.advance();
+    let label = self.parser.previous.lexeme.clone();
+    self.parser.advance();
+
+    if self.loops.iter().any(|l| l.label.as_ref() == Some(&label)) {
+      self
+        .parser
+        .error("Label already used by an enclosing loop.");
+    }
+
+    if self.parser.match_kind(TokenKind::For) {
+      self.for_statement(Some(label));
+    } else if self.parser.match_kind(TokenKind::While) {
+      self.while_statement(Some(label));
+    } else {
+      self.parser.error_at_current("Expect loop after label.");
+    }
+  }
+
+  /// Parse for loop
+  fn for_statement(&mut self, label: Option<String>) {
+    self.begin_scope();
+    self
+      .parser
+      .consume(TokenKind::LeftParen, "Expected '(' after 'for'.");
+
+    if self.parser.check(TokenKind::Identifier) && self.parser.peek_kind() == TokenKind::In {
+      self.for_in(label);
+      self.end_scope();
+      return;
+    }
+
+    // parse an initializer
+    if self.parser.match_kind(TokenKind::Semicolon) {
+    } else if self.parser.match_kind(TokenKind::Var) {
+      self.var_declaration();
+    } else {
+      self.expression_statement();
+    }
+
+    let mut loop_start = self.current_chunk().instructions.len();
+
+    // parse loop condition
+    let mut exit_jump: Option<usize> = Option::None;
+    if !self.parser.match_kind(TokenKind::Semicolon) {
       self.expression();
       self
         .parser
@@ -489,7 +1216,7 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
       self.patch_jump(body_jump);
     }
 
-    self.statement();
+    self.loop_body(label, loop_start);
     self.emit_loop(loop_start);
 
     // patch exit jump
@@ -498,11 +1225,81 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
       self.emit_byte(AlignedByteCode::Pop);
     }
 
+    self.patch_breaks();
+    self.end_scope();
+  }
+
+  /// Parse the remainder of a for-in loop. The iterable's `iter` method is
+  /// called once, then `next` before each iteration and `current` for
+  /// a fresh binding of the loop variable. The loop runs as the body of a
+  /// try statement whose finally block closes the iterator, so however the
+  /// loop is left a generator stopped early runs its own finally blocks
+  fn for_in(&mut self, label: Option<String>) {
+    self
+      .parser
+      .consume(TokenKind::Identifier, "Expect loop variable name.");
+    let variable = self.parser.previous.clone();
+    self
+      .parser
+      .consume(TokenKind::In, "Expect 'in' after loop variable.");
+
+    let iter_constant = self.name_constant(ITER);
+    let next_constant = self.name_constant(NEXT);
+    let current_constant = self.name_constant(CURRENT);
+
+    self.expression();
+    self.emit_byte(AlignedByteCode::Invoke((iter_constant, 0)));
+    self.add_hidden_local(ITER_SLOT);
+    let iter_slot = (self.local_count - 1) as u8;
+
+    self
+      .parser
+      .consume(TokenKind::RightParen, "Expect ')' after for clauses.");
+
+    let slot = self.emit_try_slots();
+    let finally_handler = self.emit_jump(AlignedByteCode::PushHandler(0));
+    self.begin_try(slot, 1);
+
+    let loop_start = self.current_chunk().instructions.len();
+    self.emit_byte(AlignedByteCode::GetLocal(iter_slot));
+    self.emit_byte(AlignedByteCode::Invoke((next_constant, 0)));
+    let exit_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
+    self.emit_byte(AlignedByteCode::Pop);
+
+    self.enter_loop(label, loop_start);
+    self.begin_scope();
+    self.emit_byte(AlignedByteCode::GetLocal(iter_slot));
+    self.emit_byte(AlignedByteCode::Invoke((current_constant, 0)));
+    self.add_local(variable);
+    self.mark_initialized();
+
+    self.statement();
+    self.end_scope();
+    self.emit_loop(loop_start);
+
+    self.patch_jump(exit_jump);
+    self.emit_byte(AlignedByteCode::Pop);
+    self.patch_breaks();
+
+    self.emit_byte(AlignedByteCode::PopHandler);
+    self.handler_count -= 1;
+
+    let try_ = self
+      .tries
+      .pop()
+      .expect("Internal spacelox error. for-in loop not tracked");
+    self.patch_jump(finally_handler);
+    try_.jumps.iter().for_each(|jump| self.patch_jump(*jump));
+
+    self.emit_byte(AlignedByteCode::GetLocal(iter_slot));
+    self.emit_bytes(AlignedByteCode::CloseIterator, AlignedByteCode::Pop);
+
+    self.emit_completion(&try_);
     self.end_scope();
   }
 
   /// Parse while statement
-  fn while_statement(&mut self) {
+  fn while_statement(&mut self, label: Option<String>) {
     let loop_start = self.current_chunk().instructions.len();
 
     self
@@ -516,12 +1313,163 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     let exit_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
 
     self.emit_byte(AlignedByteCode::Pop);
-    self.statement();
+    self.loop_body(label, loop_start);
 
     self.emit_loop(loop_start);
 
     self.patch_jump(exit_jump);
-    self.emit_byte(AlignedByteCode::Pop)
+    self.emit_byte(AlignedByteCode::Pop);
+    self.patch_breaks();
+  }
+
+  /// Parse the body of a loop tracking it for break and continue statements
+  fn loop_body(&mut self, label: Option<String>, start: usize) {
+    self.enter_loop(label, start);
+    self.statement();
+  }
+
+  /// Begin tracking a loop for break and continue statements
+  fn enter_loop(&mut self, label: Option<String>, start: usize) {
+    self.loops.push(Loop {
+      label,
+      start,
+      scope_depth: self.scope_depth,
+      handler_count: self.handler_count,
+      breaks: Vec::new(),
+    });
+  }
+
+  /// Patch the breaks of the loop that just ended
+  fn patch_breaks(&mut self) {
+    if let Some(ended) = self.loops.pop() {
+      ended.breaks.iter().for_each(|jump| self.patch_jump(*jump));
+    }
+  }
+
+  /// Parse a break statement
+  fn break_statement(&mut self) {
+    if let Some(index) = self.jump_target("break") {
+      self.emit_loop_exit(LoopExit::Break(index));
+    }
+  }
+
+  /// Parse a continue statement
+  fn continue_statement(&mut self) {
+    if let Some(index) = self.jump_target("continue") {
+      self.emit_loop_exit(LoopExit::Continue(index));
+    }
+  }
+
+  /// Jump out of or back to the start of a loop. From inside a try
+  /// statement in the loop the jump waits on its finally block
+  fn emit_loop_exit(&mut self, exit: LoopExit) {
+    let index = match exit {
+      LoopExit::Break(index) | LoopExit::Continue(index) => index,
+    };
+
+    let (slot, code) = match self.tries.last_mut() {
+      Some(try_) if try_.loop_count > index => {
+        let code = match try_.exits.iter().position(|pending| *pending == exit) {
+          Some(code) => code,
+          None => {
+            try_.exits.push(exit);
+            try_.exits.len() - 1
+          }
+        };
+        (try_.slot, code)
+      }
+      _ => {
+        let target = &self.loops[index];
+        let (scope_depth, handler_count) = (target.scope_depth, target.handler_count);
+        self.emit_exit_scopes(scope_depth, handler_count);
+
+        match exit {
+          LoopExit::Break(_) => {
+            let jump = self.emit_jump(AlignedByteCode::Jump(0));
+            self.loops[index].breaks.push(jump);
+          }
+          LoopExit::Continue(_) => self.emit_loop(self.loops[index].start),
+        }
+        return;
+      }
+    };
+
+    self.emit_constant(Value::Int(code as i64));
+    self.emit_bytes(AlignedByteCode::SetLocal(slot + 1), AlignedByteCode::Pop);
+    self.emit_finally_jump();
+  }
+
+  /// Resolve the loop targeted by a break or continue
+  fn jump_target(&mut self, keyword: &str) -> Option<usize> {
+    let label = if self.parser.match_kind(TokenKind::Identifier) {
+      Some(self.parser.previous.lexeme.clone())
+    } else {
+      None
+    };
+
+    self.parser.consume(
+      TokenKind::Semicolon,
+      &format!("Expect ';' after '{}'.", keyword),
+    );
+
+    let index = match &label {
+      Some(label) => self
+        .loops
+        .iter()
+        .rposition(|l| l.label.as_ref() == Some(label)),
+      None => self.loops.len().checked_sub(1),
+    };
+
+    let index = match index {
+      Some(index) => index,
+      None => {
+        let message = match (&label, self.in_enclosing_loop()) {
+          (Some(label), _) => format!("No enclosing loop with label '{}'.", label),
+          (None, true) => format!(
+            "Cannot use '{}' inside a function nested in a loop.",
+            keyword
+          ),
+          (None, false) => format!("Cannot use '{}' outside of a loop.", keyword),
+        };
+        self.parser.error(&message);
+        return None;
+      }
+    };
+
+    Some(index)
+  }
+
+  /// Emit the instructions to leave any scopes deeper than `scope_depth`
+  /// and any handlers installed after the first `handler_count`
+  fn emit_exit_scopes(&mut self, scope_depth: i16, handler_count: usize) {
+    for _ in handler_count..self.handler_count {
+      self.emit_byte(AlignedByteCode::PopHandler);
+    }
+
+    let mut local_count = self.local_count;
+    while local_count > 0 && self.locals[local_count - 1].depth > scope_depth {
+      if self.locals[local_count - 1].is_captured {
+        self.emit_byte(AlignedByteCode::CloseUpvalue);
+      } else {
+        self.emit_byte(AlignedByteCode::Pop);
+      }
+      local_count -= 1;
+    }
+  }
+
+  /// Is an enclosing function currently compiling a loop
+  fn in_enclosing_loop(&self) -> bool {
+    let mut enclosing = self.enclosing;
+
+    while let Some(parent_ptr) = enclosing {
+      let parent = unsafe { &*parent_ptr };
+      if !parent.loops.is_empty() {
+        return true;
+      }
+      enclosing = parent.enclosing;
+    }
+
+    false
   }
 
   /// Compile a if statement
@@ -568,1332 +1516,3268 @@ impl<'a, 's, I: Io + Clone> Compiler<'a, 's, I> {
     }
 
     if self.parser.match_kind(TokenKind::Semicolon) {
-      self.emit_return();
+      self.emit_return_value();
     } else {
-      if let FunKind::Initializer = self.fun_kind {
-        self
+      match self.fun_kind {
+        FunKind::Initializer => self
           .parser
-          .error("Cannot return a value from an initializer.");
+          .error("Cannot return a value from an initializer."),
+        FunKind::Setter => self.parser.error("Cannot return a value from a setter."),
+        _ => (),
       }
 
       self.expression();
       self
         .parser
         .consume(TokenKind::Semicolon, "Expect ',' after return value.");
-      self.emit_byte(AlignedByteCode::Return);
     }
-  }
 
-  /// Synchronize the compiler to a sentinel token
-  fn synchronize(&mut self, optional_stop: Option<TokenKind>) {
-    self.parser.panic_mode = false;
+    self.emit_return_exit();
+  }
 
-    while self.parser.current.kind != TokenKind::Eof {
-      if self.parser.previous.kind == TokenKind::Semicolon {
+  /// Return the value on top of the stack. Inside a try statement the value
+  /// is held in its error slot until its finally block has run
+  fn emit_return_exit(&mut self) {
+    let slot = match self.tries.last_mut() {
+      Some(try_) => {
+        try_.returns = true;
+        try_.slot
+      }
+      None => {
+        self.emit_byte(AlignedByteCode::Return);
         return;
       }
+    };
 
-      match self.parser.current.kind {
-        TokenKind::Class
-        | TokenKind::Fun
-        | TokenKind::Var
-        | TokenKind::For
-        | TokenKind::If
-        | TokenKind::While
-        | TokenKind::Print
-        | TokenKind::Return => {
-          return;
-        }
-        _ => {}
-      }
-
-      if let Some(stop) = optional_stop {
-        if self.parser.current.kind == stop {
-          return;
-        }
-      }
-
-      self.parser.advance();
-    }
+    self.emit_bytes(AlignedByteCode::SetLocal(slot), AlignedByteCode::Pop);
+    self.emit_byte(AlignedByteCode::False);
+    self.emit_bytes(AlignedByteCode::SetLocal(slot + 1), AlignedByteCode::Pop);
+    self.emit_finally_jump();
   }
 
-  /// End the compilation at eof
-  fn end_compiler(&mut self) {
-    self.emit_return();
-
-    #[cfg(feature = "debug")]
-    self.print_chunk();
+  /// Parse a throw statement
+  fn throw_statement(&mut self) {
+    self.expression();
+    self
+      .parser
+      .consume(TokenKind::Semicolon, "Expect ';' after thrown value.");
+    self.emit_byte(AlignedByteCode::Throw);
   }
 
-  /// Increase the scope depth by 1
-  fn begin_scope(&mut self) {
-    self.scope_depth += 1;
-  }
+  /// Parse a try statement with an optional catch and finally clause
+  fn try_statement(&mut self) {
+    // the outer handler catches anything escaping the try or catch blocks
+    // so the finally block can run before it is rethrown
+    let slot = self.emit_try_slots();
+    let finally_handler = self.emit_jump(AlignedByteCode::PushHandler(0));
+    let catch_handler = self.emit_jump(AlignedByteCode::PushHandler(0));
+    self.begin_try(slot, 2);
 
-  /// Decrease the scope depth by 1
-  fn end_scope(&mut self) {
-    self.scope_depth -= 1;
+    self
+      .parser
+      .consume(TokenKind::LeftBrace, "Expect '{' after 'try'.");
+    self.begin_scope();
+    self.block();
+    self.end_scope();
 
-    while self.local_count > 0 && self.locals[self.local_count - 1].depth > self.scope_depth {
-      if self.locals[self.local_count - 1].is_captured {
-        self.emit_byte(AlignedByteCode::CloseUpvalue)
+    self.emit_byte(AlignedByteCode::PopHandler);
+    let catch_jump = self.emit_jump(AlignedByteCode::Jump(0));
+    self.handler_count -= 1;
+
+    self.patch_jump(catch_handler);
+    let has_catch = self.parser.match_kind(TokenKind::Catch);
+    if has_catch {
+      // a generator asked to return enters here without an error
+      let return_jump = if self.fun.is_generator {
+        Some(self.emit_completion_check(slot, AlignedByteCode::True))
       } else {
+        None
+      };
+
+      self
+        .parser
+        .consume(TokenKind::LeftParen, "Expect '(' after 'catch'.");
+      self
+        .parser
+        .consume(TokenKind::Identifier, "Expect error name.");
+
+      self.begin_scope();
+      self.emit_byte(AlignedByteCode::GetLocal(slot));
+      self.add_local(self.parser.previous.clone());
+      self.mark_initialized();
+
+      self
+        .parser
+        .consume(TokenKind::RightParen, "Expect ')' after error name.");
+      self
+        .parser
+        .consume(TokenKind::LeftBrace, "Expect '{' before catch body.");
+      self.block();
+      self.end_scope();
+
+      // the error was handled so the statement completes normally
+      self.emit_byte(AlignedByteCode::Nil);
+      self.emit_bytes(AlignedByteCode::SetLocal(slot + 1), AlignedByteCode::Pop);
+
+      if let Some(return_jump) = return_jump {
+        let handled_jump = self.emit_jump(AlignedByteCode::Jump(0));
+        self.patch_jump(return_jump);
         self.emit_byte(AlignedByteCode::Pop);
+        self.emit_finally_jump();
+        self.patch_jump(handled_jump);
       }
-      self.local_count -= 1;
+    } else {
+      self.emit_finally_jump();
+    }
+
+    self.patch_jump(catch_jump);
+    self.emit_byte(AlignedByteCode::PopHandler);
+    self.handler_count -= 1;
+
+    let try_ = self
+      .tries
+      .pop()
+      .expect("Internal spacelox error. try statement not tracked");
+    self.patch_jump(finally_handler);
+    try_.jumps.iter().for_each(|jump| self.patch_jump(*jump));
+
+    let has_finally = self.parser.match_kind(TokenKind::Finally);
+    if has_finally {
+      self
+        .parser
+        .consume(TokenKind::LeftBrace, "Expect '{' after 'finally'.");
+      self.begin_scope();
+      self.block();
+      self.end_scope();
     }
+
+    if !has_catch && !has_finally {
+      self
+        .parser
+        .error("Expect 'catch' or 'finally' after try block.");
+    }
+
+    // leave the statement the way the try or catch block was left
+    self.emit_completion(&try_);
+    self.end_scope();
   }
 
-  /// Print the chunk if debug and an error occurred
-  #[cfg(feature = "debug")]
-  fn print_chunk(&self) {
-    disassemble_chunk(&self.io.stdio(), self.fun.chunk(), &self.fun.name)
+  /// Begin the scope of a try statement with its hidden error and rethrow
+  /// locals, returning the slot of the error local. The vm stores the
+  /// error and how the statement is being left in these slots whenever
+  /// one of its handlers is entered
+  fn emit_try_slots(&mut self) -> u8 {
+    self.begin_scope();
+    self.add_hidden_local(ERROR_SLOT);
+    self.add_hidden_local(RETHROW_SLOT);
+    self.emit_bytes(AlignedByteCode::Nil, AlignedByteCode::Nil);
+    (self.local_count - 2) as u8
+  }
+
+  /// Begin tracking a try statement whose `handlers` were just pushed
+  fn begin_try(&mut self, slot: u8, handlers: usize) {
+    self.tries.push(Try {
+      scope_depth: self.scope_depth,
+      slot,
+      handler_count: self.handler_count,
+      loop_count: self.loops.len(),
+      returns: false,
+      exits: Vec::new(),
+      jumps: Vec::new(),
+    });
+    self.handler_count += handlers;
   }
 
-  /// Compiles a binary expression into it's equivalent bytecodes
-  ///
-  /// # Panics
-  /// This method will panic if an invalid binary operator is passed
-  fn binary(&mut self) {
-    // Remember the operator
-    let operator_kind = self.parser.previous.kind;
-    let precedence = get_rule(operator_kind).precedence.higher();
-    self.parse_precedence(precedence);
+  /// Once a finally block completes rethrow, return or take a loop jump
+  /// depending on how the try statement was left
+  fn emit_completion(&mut self, try_: &Try) {
+    let slot = try_.slot;
+    let rethrow_jump = self.emit_completion_check(slot, AlignedByteCode::True);
+    self.emit_bytes(AlignedByteCode::GetLocal(slot), AlignedByteCode::Throw);
+    self.patch_jump(rethrow_jump);
+    self.emit_byte(AlignedByteCode::Pop);
 
-    match operator_kind {
-      TokenKind::BangEqual => self.emit_bytes(AlignedByteCode::Equal, AlignedByteCode::Not),
-      TokenKind::EqualEqual => self.emit_byte(AlignedByteCode::Equal),
-      TokenKind::Greater => self.emit_byte(AlignedByteCode::Greater),
-      TokenKind::GreaterEqual => self.emit_bytes(AlignedByteCode::Less, AlignedByteCode::Not),
-      TokenKind::Less => self.emit_byte(AlignedByteCode::Less),
-      TokenKind::LessEqual => self.emit_bytes(AlignedByteCode::Greater, AlignedByteCode::Not),
-      TokenKind::Plus => self.emit_byte(AlignedByteCode::Add),
-      TokenKind::Minus => self.emit_byte(AlignedByteCode::Subtract),
-      TokenKind::Star => self.emit_byte(AlignedByteCode::Multiply),
-      TokenKind::Slash => self.emit_byte(AlignedByteCode::Divide),
-      _ => panic!("Invalid operator"),
+    if try_.returns || self.fun.is_generator {
+      let return_jump = self.emit_completion_check(slot, AlignedByteCode::False);
+      self.emit_byte(AlignedByteCode::GetLocal(slot));
+      self.emit_return_exit();
+      self.patch_jump(return_jump);
+      self.emit_byte(AlignedByteCode::Pop);
     }
-  }
 
-  /// Compile a call invocation
-  fn call(&mut self) {
-    let arg_count = self.call_arguments();
-    self.emit_byte(AlignedByteCode::Call(arg_count));
+    for (code, exit) in try_.exits.iter().enumerate() {
+      let code = self.make_constant(Value::Int(code as i64));
+      let exit_jump = self.emit_completion_check(slot, AlignedByteCode::Constant(code));
+      self.emit_loop_exit(*exit);
+      self.patch_jump(exit_jump);
+      self.emit_byte(AlignedByteCode::Pop);
+    }
   }
 
-  /// Compile a call invocation
-  fn list(&mut self) {
-    self.emit_byte(AlignedByteCode::List);
-    let arg_count = self.list_arguments();
+  /// Jump from inside the innermost try statement to its finally block,
+  /// leaving the scopes and handlers in between
+  fn emit_finally_jump(&mut self) {
+    let (scope_depth, handler_count) = match self.tries.last() {
+      Some(try_) => (try_.scope_depth, try_.handler_count),
+      None => return,
+    };
 
-    if arg_count > 0 {
-      self.emit_byte(AlignedByteCode::ListInit(arg_count));
+    self.emit_exit_scopes(scope_depth, handler_count);
+    let jump = self.emit_jump(AlignedByteCode::Jump(0));
+    if let Some(try_) = self.tries.last_mut() {
+      try_.jumps.push(jump);
     }
   }
 
-  /// Compile a index
-  fn index(&mut self, can_assign: bool) {
+  /// Check whether a try statement's rethrow slot holds `completion`,
+  /// returning the jump taken when it does not. The comparison is left
+  /// on the stack for the jump's landing to pop
+  fn emit_completion_check(&mut self, slot: u8, completion: AlignedByteCode) -> usize {
+    self.emit_bytes(AlignedByteCode::GetLocal(slot + 1), completion);
+    self.emit_byte(AlignedByteCode::Equal);
+    let jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
+    self.emit_byte(AlignedByteCode::Pop);
+    jump
+  }
+
+  /// Parse a match statement
+  fn match_statement(&mut self) {
+    self
+      .parser
+      .consume(TokenKind::LeftParen, "Expect '(' after 'match'.");
+    self.begin_scope();
     self.expression();
+    self.add_hidden_local(MATCH_SLOT);
+    let subject = (self.local_count - 1) as u8;
+
     self
       .parser
-      .consume(TokenKind::RightBracket, "Expected ']' after index");
+      .consume(TokenKind::RightParen, "Expect ')' after match value.");
+    self
+      .parser
+      .consume(TokenKind::LeftBrace, "Expect '{' before match cases.");
 
-    if can_assign && self.parser.match_kind(TokenKind::Equal) {
-      self.expression();
-      self.emit_byte(AlignedByteCode::SetIndex);
-    } else {
-      self.emit_byte(AlignedByteCode::GetIndex);
-    }
-  }
+    let mut end_jumps: Vec<usize> = Vec::new();
+    while !self.parser.check(TokenKind::RightBrace) && !self.parser.check(TokenKind::Eof) {
+      self
+        .parser
+        .consume(TokenKind::Case, "Expect 'case' before pattern.");
 
-  /// Compile a map literal
-  fn map(&mut self) {
-    self.emit_byte(AlignedByteCode::Map);
-    let mut entries: usize = 0;
+      let mut names: Vec<String> = Vec::new();
+      let pattern = self.pattern(&mut names);
+
+      // check the subject has the shape of the pattern
+      let mut fail_jumps: Vec<usize> = Vec::new();
+      self.pattern_checks(subject, &pattern, &mut Vec::new(), &mut fail_jumps);
+
+      // bind each name in the pattern as a local
+      let mut bindings: Vec<(Token, Vec<PatternStep>)> = Vec::new();
+      pattern_bindings(&pattern, &mut Vec::new(), &mut bindings);
+
+      self.begin_scope();
+      for (name, path) in bindings {
+        self.load_pattern_path(subject, &path);
+        self.add_local(name);
+        self.mark_initialized();
+      }
+      let binding_count = names.len();
+
+      let guard = if self.parser.match_kind(TokenKind::If) {
+        self.guard();
+        let guard_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
+        self.emit_byte(AlignedByteCode::Pop);
+
+        let captured: Vec<bool> = self.locals[self.local_count - binding_count..self.local_count]
+          .iter()
+          .map(|local| local.is_captured)
+          .collect();
+        Some((guard_jump, captured))
+      } else {
+        None
+      };
 
-    while !self.parser.check(TokenKind::RightBrace) {
-      self.expression();
       self
         .parser
-        .consume(TokenKind::Colon, "Expected ':' after map key");
-      self.expression();
+        .consume(TokenKind::Arrow, "Expect '=>' after pattern.");
+      self.statement();
+      self.end_scope();
+      end_jumps.push(self.emit_jump(AlignedByteCode::Jump(0)));
 
-      if entries == std::u16::MAX as usize {
-        self.parser.error(&format!(
-          "Cannot have more than {} key value pairs in map literal",
-          entries
-        ));
-      }
-      entries += 1;
+      // unwind the bindings if the guard failed
+      let next_jump = guard.map(|(guard_jump, captured)| {
+        self.patch_jump(guard_jump);
+        self.emit_byte(AlignedByteCode::Pop);
+        for is_captured in captured.iter().rev() {
+          if *is_captured {
+            self.emit_byte(AlignedByteCode::CloseUpvalue);
+          } else {
+            self.emit_byte(AlignedByteCode::Pop);
+          }
+        }
+        self.emit_jump(AlignedByteCode::Jump(0))
+      });
 
-      if !self.parser.match_kind(TokenKind::Comma) {
-        break;
+      // discard the failed check
+      if !fail_jumps.is_empty() {
+        for fail_jump in fail_jumps {
+          self.patch_jump(fail_jump);
+        }
+        self.emit_byte(AlignedByteCode::Pop);
+      }
+      if let Some(next_jump) = next_jump {
+        self.patch_jump(next_jump);
       }
+
+      self.parser.match_kind(TokenKind::Comma);
     }
 
     self
       .parser
-      .consume(TokenKind::RightBrace, "Expected '}' after map");
+      .consume(TokenKind::RightBrace, "Expect '}' after match cases.");
 
-    if entries > 0 {
-      self.emit_byte(AlignedByteCode::MapInit(entries as u16))
+    for end_jump in end_jumps {
+      self.patch_jump(end_jump);
     }
+    self.end_scope();
   }
 
-  /// Compile an dot operator
-  fn dot(&mut self, can_assign: bool) {
-    self
-      .parser
-      .consume(TokenKind::Identifier, "Expect property name after '.'.");
-    let name = self.identifer_constant(self.parser.previous.clone());
-
-    if can_assign && self.parser.match_kind(TokenKind::Equal) {
+  /// Parse the guard of a match case. A guard wrapped in parens up to its
+  /// arrow is a grouping, not the parameters of an arrow function
+  fn guard(&mut self) {
+    if self.parser.check(TokenKind::LeftParen)
+      && self.parser.after_group(0, |kind| kind == TokenKind::Arrow)
+    {
+      self.parser.advance();
       self.expression();
-      self.emit_byte(AlignedByteCode::SetProperty(name));
-    } else if self.parser.match_kind(TokenKind::LeftParen) {
-      let arg_count = self.call_arguments();
-      self.emit_byte(AlignedByteCode::Invoke((name, arg_count)));
-    } else {
-      self.emit_byte(AlignedByteCode::GetProperty(name));
+      self
+        .parser
+        .consume(TokenKind::RightParen, "Expected ')' after expression");
+      return;
     }
+
+    self.expression();
   }
 
-  /// Compile a unary expression into it's equivalent bytecode
-  ///
-  /// # Panics
-  /// This method will panic if an invalid unary operator is attempted to compile
-  fn unary(&mut self) {
-    let operator_kind = self.parser.previous.kind;
+  /// Parse a pattern of a match case
+  fn pattern(&mut self, names: &mut Vec<String>) -> Pattern {
+    if self.parser.match_kind(TokenKind::Identifier) {
+      let name = self.parser.previous.clone();
 
-    // Compile the operand
-    self.parse_precedence(Precedence::Unary);
+      if self.parser.check(TokenKind::LeftParen) || self.parser.check(TokenKind::Dot) {
+        return self.instance_pattern(name, names);
+      }
 
-    // Emit the operator instruction
-    match operator_kind {
-      TokenKind::Minus => self.emit_byte(AlignedByteCode::Negate),
-      TokenKind::Bang => self.emit_byte(AlignedByteCode::Not),
-      _ => panic!(),
+      if name.lexeme == WILDCARD {
+        return Pattern::Wildcard;
+      }
+
+      self.pattern_binding(&name, names);
+      return Pattern::Binding(name);
     }
-  }
 
-  /// Compile a grouping expression
-  fn grouping(&mut self) {
-    self.expression();
+    if self.parser.match_kind(TokenKind::LeftBracket) {
+      return self.list_pattern(names);
+    }
 
-    self
-      .parser
-      .consume(TokenKind::RightParen, "Expected ')' after expression")
-  }
+    if self.parser.match_kind(TokenKind::LeftBrace) || self.parser.match_kind(TokenKind::MapOpen) {
+      return self.map_pattern(names);
+    }
 
-  /// Compile a number literal
-  fn number(&mut self) {
-    let value = Value::Number(
-      self
-        .parser
-        .previous
-        .lexeme
-        .parse::<f64>()
-        .expect("Unable to parse float"),
-    );
-    self.emit_constant(value);
+    match self.literal_pattern() {
+      Some(literal) => Pattern::Literal(literal),
+      None => {
+        self.parser.error_at_current("Expect pattern.");
+        Pattern::Wildcard
+      }
+    }
   }
 
-  /// Compile a variable statement
-  fn variable(&mut self, can_assign: bool) {
-    self.named_variable(self.parser.previous.clone(), can_assign);
+  /// Parse a literal pattern
+  fn literal_pattern(&mut self) -> Option<AlignedByteCode> {
+    if self.parser.match_kind(TokenKind::True) {
+      Some(AlignedByteCode::True)
+    } else if self.parser.match_kind(TokenKind::False) {
+      Some(AlignedByteCode::False)
+    } else if self.parser.match_kind(TokenKind::Nil) {
+      Some(AlignedByteCode::Nil)
+    } else {
+      self.constant_pattern().map(AlignedByteCode::Constant)
+    }
   }
 
-  /// Compile a string literal
-  fn string(&mut self) {
-    let string = self
-      .hooks
-      .manage_str(copy_string(&self.parser.previous));
-    let value = Value::String(string);
-    self.emit_constant(value)
+  /// Parse a string or number constant in a pattern
+  fn constant_pattern(&mut self) -> Option<u8> {
+    if self.parser.match_kind(TokenKind::String) {
+      let string = self.hooks.manage_str(copy_string(&self.parser.previous));
+      return Some(self.make_constant(Value::String(string)));
+    }
+
+    let negate = self.parser.match_kind(TokenKind::Minus);
+    if self.parser.match_kind(TokenKind::Number) || self.parser.match_kind(TokenKind::Integer) {
+      let number = match (self.number_value(), negate) {
+        (Value::Number(num), true) => Value::Number(-num),
+        (Value::Int(int), true) => Value::Int(-int),
+        (number, _) => number,
+      };
+      return Some(self.make_constant(number));
+    }
+
+    if negate {
+      self.parser.error_at_current("Expect number after '-'.");
+    }
+    None
   }
 
-  /// retrieve a named variable from either local or global scope
-  fn named_variable(&mut self, name: Token, can_assign: bool) {
-    let index = self.resolve_local(&name);
+  /// Parse a list pattern
+  fn list_pattern(&mut self, names: &mut Vec<String>) -> Pattern {
+    let mut elements: Vec<Pattern> = Vec::new();
+    let mut rest: Option<Box<Pattern>> = None;
 
-    let (get_byte, set_byte) = match index {
-      Some(local) => (
-        AlignedByteCode::GetLocal(local),
-        AlignedByteCode::SetLocal(local),
-      ),
-      None => match self.resolve_upvalue(&name) {
-        Some(upvalue) => (
-          AlignedByteCode::GetUpvalue(upvalue),
-          AlignedByteCode::SetUpvalue(upvalue),
-        ),
-        None => {
-          let global_index = self.identifer_constant(name);
-          (
-            AlignedByteCode::GetGlobal(global_index),
-            AlignedByteCode::SetGlobal(global_index),
-          )
+    while !self.parser.check(TokenKind::RightBracket) {
+      if self.parser.match_kind(TokenKind::Ellipsis) {
+        self
+          .parser
+          .consume(TokenKind::Identifier, "Expect name after '...'.");
+        let name = self.parser.previous.clone();
+
+        if name.lexeme == WILDCARD {
+          rest = Some(Box::new(Pattern::Wildcard));
+        } else {
+          self.pattern_binding(&name, names);
+          rest = Some(Box::new(Pattern::Binding(name)));
         }
-      },
-    };
-
-    if can_assign && self.parser.match_kind(TokenKind::Equal) {
-      self.expression();
-      self.emit_byte(set_byte);
-    } else {
-      self.emit_byte(get_byte);
-    }
-  }
-
-  /// Compile a literal
-  fn literal(&mut self) {
-    match self.parser.previous.kind {
-      TokenKind::True => self.emit_byte(AlignedByteCode::True),
-      TokenKind::False => self.emit_byte(AlignedByteCode::False),
-      TokenKind::Nil => self.emit_byte(AlignedByteCode::Nil),
-      _ => panic!(format!(
-        "Unexpected token kind {:?}",
-        self.parser.previous.kind
-      )),
-    }
-  }
-
-  /// Compile an expression an a provided precedences
-  fn parse_precedence(&mut self, precedence: Precedence) {
-    self.parser.advance();
+        break;
+      }
 
-    let can_assign = precedence <= Precedence::Assignment;
-    let prefix_fn = get_rule(self.parser.previous.kind).prefix.clone();
+      if elements.len() == u8::MAX as usize {
+        self
+          .parser
+          .error("Cannot have more than 255 elements in list pattern.");
+      }
+      elements.push(self.pattern(names));
 
-    match prefix_fn {
-      Some(prefix) => self.execute_action(prefix, can_assign),
-      None => {
-        self.parser.error("Expected expression.");
-        return;
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
       }
     }
 
-    while precedence <= get_rule(self.parser.current.kind).precedence {
-      self.parser.advance();
-      let infix_fn = get_rule(self.parser.previous.kind).infix.clone();
+    self
+      .parser
+      .consume(TokenKind::RightBracket, "Expect ']' after list pattern.");
+    Pattern::List(elements, rest)
+  }
 
-      self.execute_action(infix_fn.expect("Failure"), can_assign);
-    }
+  /// Parse a map pattern
+  fn map_pattern(&mut self, names: &mut Vec<String>) -> Pattern {
+    let mut entries: Vec<(u8, Pattern)> = Vec::new();
 
-    if can_assign && self.parser.match_kind(TokenKind::Equal) {
-      self.parser.error("Invalid assignment target.")
-    }
-  }
+    while !self.parser.check(TokenKind::RightBrace) {
+      let key = match self.constant_pattern() {
+        Some(key) => key,
+        None => {
+          self
+            .parser
+            .error_at_current("Expect string or number key in map pattern.");
+          return Pattern::Map(entries);
+        }
+      };
 
-  /// Define a variable
-  fn define_variable(&mut self, global: u8) {
-    if self.scope_depth > 0 {
-      self.mark_initialized();
-      return;
-    }
+      self
+        .parser
+        .consume(TokenKind::Colon, "Expect ':' after map pattern key.");
+      entries.push((key, self.pattern(names)));
 
-    self.emit_byte(AlignedByteCode::DefineGlobal(global));
-  }
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
+      }
+    }
 
-  /// Parse a list of argument to a function
-  fn call_arguments(&mut self) -> u8 {
-    let arg_count = self.consume_arguments(TokenKind::RightParen, std::u8::MAX as usize);
     self
       .parser
-      .consume(TokenKind::RightParen, "Expect ')' after arguments");
-    arg_count as u8
+      .consume(TokenKind::RightBrace, "Expect '}' after map pattern.");
+    Pattern::Map(entries)
   }
 
-  /// Parse a list of argument defining a list
-  fn list_arguments(&mut self) -> u16 {
-    let arg_count = self.consume_arguments(TokenKind::RightBracket, std::u16::MAX as usize);
+  /// Parse a class instance pattern
+  fn instance_pattern(&mut self, name: Token, names: &mut Vec<String>) -> Pattern {
+    let mut class = vec![name];
+    while self.parser.match_kind(TokenKind::Dot) {
+      self
+        .parser
+        .consume(TokenKind::Identifier, "Expect class name after '.'.");
+      class.push(self.parser.previous.clone());
+    }
+
     self
       .parser
-      .consume(TokenKind::RightBracket, "Expect ']' after arguments");
-    arg_count as u16
-  }
-
-  /// Consume a comma separated set of arguments for calls and lists
-  fn consume_arguments(&mut self, stop_token: TokenKind, max: usize) -> usize {
-    let mut arg_count: usize = 0;
+      .consume(TokenKind::LeftParen, "Expect '(' after class name.");
 
-    while !self.parser.check(stop_token) {
-      self.expression();
+    let mut fields: Vec<(u8, Pattern)> = Vec::new();
+    while !self.parser.check(TokenKind::RightParen) {
+      self
+        .parser
+        .consume(TokenKind::Identifier, "Expect field name.");
+      let field = self.parser.previous.clone();
+      let constant = self.identifer_constant(field.clone());
 
-      if arg_count == max {
-        self
-          .parser
-          .error(&format!("Cannot have more than {} arguments", max));
-        return arg_count;
+      if self.parser.match_kind(TokenKind::Colon) {
+        fields.push((constant, self.pattern(names)));
+      } else {
+        self.pattern_binding(&field, names);
+        fields.push((constant, Pattern::Binding(field)));
       }
-      arg_count += 1;
 
       if !self.parser.match_kind(TokenKind::Comma) {
         break;
       }
     }
 
-    arg_count
-  }
-
-  /// Emit instruction for a short circuited and
-  fn and(&mut self) {
-    let end_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
-
-    self.emit_byte(AlignedByteCode::Pop);
-    self.parse_precedence(Precedence::And);
-
-    self.patch_jump(end_jump);
-  }
-
-  /// Emit instruction for a short circuited and
-  fn or(&mut self) {
-    let else_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
-    let end_jump = self.emit_jump(AlignedByteCode::Jump(0));
-
-    self.patch_jump(else_jump);
-    self.emit_byte(AlignedByteCode::Pop);
-
-    self.parse_precedence(Precedence::Or);
-    self.patch_jump(end_jump);
+    self
+      .parser
+      .consume(TokenKind::RightParen, "Expect ')' after instance pattern.");
+    Pattern::Instance(class, fields)
   }
 
-  /// Parse a class's this identifier
-  fn this(&mut self) {
-    if self.current_class.is_none() {
-      self.parser.error("Cannot use 'this' outside of class.");
-      return;
+  /// Record a name bound by a pattern
+  fn pattern_binding(&mut self, name: &Token, names: &mut Vec<String>) {
+    if names.contains(&name.lexeme) {
+      self
+        .parser
+        .error("Variable with this name already bound in this pattern.");
     }
+    names.push(name.lexeme.clone());
+  }
+
+  /// Emit the checks a pattern requires jumping to a fail jump when any
+  /// are false
+  fn pattern_checks(
+    &mut self,
+    subject: u8,
+    pattern: &Pattern,
+    path: &mut Vec<PatternStep>,
+    fail_jumps: &mut Vec<usize>,
+  ) {
+    match pattern {
+      Pattern::Wildcard | Pattern::Binding(_) => {}
+      Pattern::Literal(literal) => {
+        self.load_pattern_path(subject, path);
+        self.emit_bytes(*literal, AlignedByteCode::Equal);
+        self.pattern_check(fail_jumps);
+      }
+      Pattern::List(elements, rest) => {
+        self.load_pattern_path(subject, path);
+        match rest {
+          Some(_) => self.emit_byte(AlignedByteCode::MatchListRest(elements.len() as u8)),
+          None => self.emit_byte(AlignedByteCode::MatchList(elements.len() as u8)),
+        }
+        self.pattern_check(fail_jumps);
 
-    self.variable(false);
-  }
-
-  fn super_(&mut self) {
-    match self.current_class {
-      None => self.parser.error("Cannot use 'super' outside of a class."),
-      Some(class) => {
-        if !class.has_super_class {
-          self
-            .parser
-            .error("Cannot use 'super' in a class with no superclass.");
+        for (index, element) in elements.iter().enumerate() {
+          path.push(PatternStep::Element(index as u8));
+          self.pattern_checks(subject, element, path, fail_jumps);
+          path.pop();
         }
       }
-    }
-
-    self
-      .parser
-      .consume(TokenKind::Dot, "Expect '.' after 'super'.");
-    self
-      .parser
-      .consume(TokenKind::Identifier, "Expect superclass method name.");
-    let name = self.identifer_constant(self.parser.previous.clone());
-
-    self.named_variable(
-      Token {
-        lexeme: THIS.to_string(),
-        kind: TokenKind::This,
-        line: self.parser.previous.line,
-      },
-      false,
-    );
+      Pattern::Map(entries) => {
+        self.load_pattern_path(subject, path);
+        self.emit_byte(AlignedByteCode::MatchMap);
+        self.pattern_check(fail_jumps);
+
+        for (key, entry) in entries {
+          self.load_pattern_path(subject, path);
+          self.emit_byte(AlignedByteCode::MatchKey(*key));
+          self.pattern_check(fail_jumps);
+
+          path.push(PatternStep::Key(*key));
+          self.pattern_checks(subject, entry, path, fail_jumps);
+          path.pop();
+        }
+      }
+      Pattern::Instance(class, fields) => {
+        self.load_pattern_path(subject, path);
+        self.named_variable(class[0].clone(), false);
+        for name in &class[1..] {
+          let constant = self.identifer_constant(name.clone());
+          self.emit_byte(AlignedByteCode::GetProperty(constant));
+        }
+        self.emit_byte(AlignedByteCode::MatchClass);
+        self.pattern_check(fail_jumps);
 
-    if self.parser.match_kind(TokenKind::LeftParen) {
-      let arg_count = self.call_arguments();
-      self.named_variable(
-        Token {
-          lexeme: SUPER.to_string(),
-          kind: TokenKind::Super,
-          line: self.parser.previous.line,
-        },
-        false,
-      );
-      self.emit_byte(AlignedByteCode::SuperInvoke((name, arg_count)));
-    } else {
-      self.named_variable(
-        Token {
-          lexeme: "super".to_string(),
-          kind: TokenKind::Super,
-          line: self.parser.previous.line,
-        },
-        false,
-      );
+        for (field, entry) in fields {
+          self.load_pattern_path(subject, path);
+          self.emit_byte(AlignedByteCode::MatchField(*field));
+          self.pattern_check(fail_jumps);
 
-      self.emit_byte(AlignedByteCode::GetSuper(name));
+          path.push(PatternStep::Field(*field));
+          self.pattern_checks(subject, entry, path, fail_jumps);
+          path.pop();
+        }
+      }
     }
   }
 
-  /// Parse a variable from the provided token return it's new constant
-  /// identifer if an identifer was identified
-  fn parse_variable(&mut self, error_message: &str) -> u8 {
-    self.parser.consume(TokenKind::Identifier, error_message);
-    self.declare_variable();
-    if self.scope_depth > 0 {
-      return 0;
-    }
-    self.identifer_constant(self.parser.previous.clone())
-  }
+  /// Emit the checks that a value has the shape of a destructuring
+  /// pattern, raising a runtime error if not
+  fn destructure_checks(&mut self, subject: u8, pattern: &Pattern, path: &mut Vec<PatternStep>) {
+    match pattern {
+      Pattern::List(elements, rest) => {
+        self.load_pattern_path(subject, path);
+        match rest {
+          Some(_) => self.emit_byte(AlignedByteCode::DestructureListRest(elements.len() as u8)),
+          None => self.emit_byte(AlignedByteCode::DestructureList(elements.len() as u8)),
+        }
 
-  /// Mark a variable initialized
-  fn mark_initialized(&mut self) {
-    if self.scope_depth > 0 {
-      self.locals[self.local_count - 1].depth = self.scope_depth;
+        for (index, element) in elements.iter().enumerate() {
+          path.push(PatternStep::Element(index as u8));
+          self.destructure_checks(subject, element, path);
+          path.pop();
+        }
+      }
+      Pattern::Map(entries) => {
+        self.load_pattern_path(subject, path);
+        self.emit_byte(AlignedByteCode::DestructureMap);
+
+        for (key, entry) in entries {
+          path.push(PatternStep::Key(*key));
+          self.destructure_checks(subject, entry, path);
+          path.pop();
+        }
+      }
+      _ => {}
     }
   }
 
-  /// Generate a constant from the provided identifier token
-  fn identifer_constant(&mut self, name: Token) -> u8 {
-    let identifer = self.hooks.manage_str(name.lexeme);
-    self.make_constant(Value::String(identifer))
+  /// Jump to the fail jump if the check on the stack is false
+  fn pattern_check(&mut self, fail_jumps: &mut Vec<usize>) {
+    fail_jumps.push(self.emit_jump(AlignedByteCode::JumpIfFalse(0)));
+    self.emit_byte(AlignedByteCode::Pop);
   }
 
-  fn add_local(&mut self, name: Token) {
-    if self.local_count == std::u8::MAX as usize {
-      self.parser.error("Too many local variables in function.");
-      return;
-    }
+  /// Load the value at a path into the match subject
+  fn load_pattern_path(&mut self, subject: u8, path: &[PatternStep]) {
+    self.emit_byte(AlignedByteCode::GetLocal(subject));
 
-    let local = &mut self.locals[self.local_count];
-    self.local_count += 1;
-
-    local.name = Some(name.lexeme);
-    local.depth = -1;
+    for step in path {
+      match step {
+        PatternStep::Element(index) => {
+          let constant = self.make_constant(Value::Int(*index as i64));
+          self.emit_bytes(
+            AlignedByteCode::Constant(constant),
+            AlignedByteCode::GetIndex,
+          )
+        }
+        PatternStep::Key(constant) => self.emit_bytes(
+          AlignedByteCode::Constant(*constant),
+          AlignedByteCode::GetIndex,
+        ),
+        PatternStep::Field(constant) => self.emit_byte(AlignedByteCode::GetProperty(*constant)),
+        PatternStep::Rest(start) => self.emit_byte(AlignedByteCode::ListRest(*start)),
+      }
+    }
   }
 
-  ///  declare a variable
-  fn declare_variable(&mut self) {
-    // if global exit
-    if self.scope_depth == 0 {
-      return;
-    }
+  /// Synchronize the compiler to a sentinel token
+  fn synchronize(&mut self, optional_stop: Option<TokenKind>) {
+    self.parser.panic_mode = false;
 
-    let name = self.parser.previous.clone();
-    for i in (0..self.local_count).rev() {
-      let local = &self.locals[i];
+    while self.parser.current.kind != TokenKind::Eof {
+      if self.parser.previous.kind == TokenKind::Semicolon {
+        return;
+      }
 
-      // If we in a lower scope break
-      if local.depth != UNINITIALIZED && local.depth < self.scope_depth {
-        break;
+      match self.parser.current.kind {
+        TokenKind::Class
+        | TokenKind::Trait
+        | TokenKind::Fun
+        | TokenKind::Var
+        | TokenKind::Const
+        | TokenKind::For
+        | TokenKind::If
+        | TokenKind::While
+        | TokenKind::Print
+        | TokenKind::Return
+        | TokenKind::Throw
+        | TokenKind::Try
+        | TokenKind::Import
+        | TokenKind::Export => {
+          return;
+        }
+        _ => {}
       }
 
-      // check that the same variable wasn't declared twice in the same scope
-      if let Some(local_name) = &local.name {
-        if &name.lexeme == local_name {
-          self
-            .parser
-            .error("Variable with this name already declared in this scope.");
+      if let Some(stop) = optional_stop {
+        if self.parser.current.kind == stop {
+          return;
         }
       }
-    }
 
-    self.add_local(name);
+      self.parser.advance();
+    }
   }
 
-  /// resolve a token to a local if it exists
-  fn resolve_local(&mut self, name: &Token) -> Option<u8> {
-    for i in (0..self.local_count).rev() {
-      let local = &self.locals[i];
+  /// End the compilation at eof
+  fn end_compiler(&mut self) {
+    self.emit_return();
 
-      if let Some(local_name) = &local.name {
-        if &name.lexeme == local_name {
-          // handle the case were `var a = a;`
-          if local.depth == UNINITIALIZED {
-            self
-              .parser
-              .error("Cannot read local variable in its own initializer.")
-          }
+    #[cfg(feature = "debug")]
+    self.print_chunk();
+  }
 
-          return Some(i as u8);
-        }
+  /// Increase the scope depth by 1
+  fn begin_scope(&mut self) {
+    self.scope_depth += 1;
+  }
+
+  /// Decrease the scope depth by 1
+  fn end_scope(&mut self) {
+    self.scope_depth -= 1;
+
+    while self.local_count > 0 && self.locals[self.local_count - 1].depth > self.scope_depth {
+      if self.locals[self.local_count - 1].is_captured {
+        self.emit_byte(AlignedByteCode::CloseUpvalue)
+      } else {
+        self.emit_byte(AlignedByteCode::Pop);
       }
+      self.local_count -= 1;
     }
+  }
 
-    // not found
-    Option::None
+  /// Print the chunk if debug and an error occurred
+  #[cfg(feature = "debug")]
+  fn print_chunk(&self) {
+    disassemble_chunk(&self.io.stdio(), self.fun.chunk(), &self.fun.name)
   }
 
-  /// resolve a token to an upvalue in an enclosing scope if it exists
-  fn resolve_upvalue(&mut self, name: &Token) -> Option<u8> {
-    match self.enclosing {
-      Some(parent_ptr) => {
-        let parent = unsafe { &mut *parent_ptr };
+  /// Compiles a binary expression into it's equivalent bytecodes
+  ///
+  /// # Panics
+  /// This method will panic if an invalid binary operator is passed
+  fn binary(&mut self) {
+    // Remember the operator
+    let operator_kind = self.parser.previous.kind;
 
-        match parent.resolve_local(name) {
-          Some(local) => {
-            parent.locals[local as usize].is_captured = true;
-            Some(self.add_upvalue(UpvalueIndex::Local(local)) as u8)
-          }
-          None => parent
-            .resolve_upvalue(name)
-            .map(|upvalue| self.add_upvalue(UpvalueIndex::Upvalue(upvalue)) as u8),
-        }
-      }
-      None => None,
+    // exponents are right associative and may have a unary operand
+    let precedence = match operator_kind {
+      TokenKind::StarStar => Precedence::Unary,
+      _ => get_rule(operator_kind).precedence.higher(),
+    };
+    self.parse_precedence(precedence);
+
+    match operator_kind {
+      TokenKind::BangEqual => self.emit_bytes(AlignedByteCode::Equal, AlignedByteCode::Not),
+      TokenKind::EqualEqual => self.emit_byte(AlignedByteCode::Equal),
+      TokenKind::Greater => self.emit_byte(AlignedByteCode::Greater),
+      TokenKind::GreaterEqual => self.emit_bytes(AlignedByteCode::Less, AlignedByteCode::Not),
+      TokenKind::Less => self.emit_byte(AlignedByteCode::Less),
+      TokenKind::LessEqual => self.emit_bytes(AlignedByteCode::Greater, AlignedByteCode::Not),
+      TokenKind::DotDot => self.emit_byte(AlignedByteCode::Range),
+      TokenKind::DotDotEqual => self.emit_byte(AlignedByteCode::RangeInclusive),
+      TokenKind::Plus => self.emit_byte(AlignedByteCode::Add),
+      TokenKind::Minus => self.emit_byte(AlignedByteCode::Subtract),
+      TokenKind::Star => self.emit_byte(AlignedByteCode::Multiply),
+      TokenKind::Slash => self.emit_byte(AlignedByteCode::Divide),
+      TokenKind::Percent => self.emit_byte(AlignedByteCode::Modulo),
+      TokenKind::StarStar => self.emit_byte(AlignedByteCode::Power),
+      TokenKind::TildeSlash => self.emit_byte(AlignedByteCode::IntegerDivide),
+      TokenKind::Ampersand => self.emit_byte(AlignedByteCode::BitAnd),
+      TokenKind::Pipe => self.emit_byte(AlignedByteCode::BitOr),
+      TokenKind::Caret => self.emit_byte(AlignedByteCode::BitXor),
+      TokenKind::LessLess => self.emit_byte(AlignedByteCode::ShiftLeft),
+      TokenKind::GreaterGreater => self.emit_byte(AlignedByteCode::ShiftRight),
+      _ => panic!("Invalid operator"),
     }
   }
 
-  /// add an upvalue
-  fn add_upvalue(&mut self, upvalue: UpvalueIndex) -> usize {
-    let upvalue_count = self.fun.upvalue_count;
-
-    // check for existing upvalues
-    if let Some(i) = self.upvalues[0..upvalue_count]
-      .iter()
-      .position(|existing_upvalue| match (&existing_upvalue, &upvalue) {
-        (UpvalueIndex::Local(existing), UpvalueIndex::Local(new)) => *existing == *new,
-        _ => false,
-      })
-    {
-      return i;
+  /// Compile a call invocation
+  fn call(&mut self) {
+    match self.call_arguments() {
+      Some(arg_count) => self.emit_byte(AlignedByteCode::Call(arg_count)),
+      None => self.emit_byte(AlignedByteCode::CallSpread),
     }
+  }
 
-    // prevent overflow
-    if upvalue_count == std::u8::MAX as usize {
-      self.parser.error("Too many closure variable in function.");
-      return 0;
+  /// Compile a call invocation
+  fn list(&mut self, can_assign: bool) {
+    // a list followed by an equal is the targets of a destructuring assignment
+    if can_assign && self.parser.after_group(1, |kind| kind == TokenKind::Equal) {
+      self.destructuring_assignment();
+      return;
     }
 
-    self.upvalues[upvalue_count] = upvalue;
-    self.fun.upvalue_count += 1;
+    self.emit_byte(AlignedByteCode::List);
+    let arg_count = self.list_arguments();
 
-    upvalue_count
+    if arg_count > 0 {
+      self.emit_byte(AlignedByteCode::ListInit(arg_count));
+    }
   }
 
-  /// Execute a provided action
-  fn execute_action(&mut self, action: Act, can_assign: bool) {
-    match action {
-      Act::And => self.and(),
-      Act::Binary => self.binary(),
-      Act::Call => self.call(),
-      Act::List => self.list(),
-      Act::Map => self.map(),
-      Act::Index => self.index(can_assign),
-      Act::Dot => self.dot(can_assign),
-      Act::Grouping => self.grouping(),
-      Act::Literal => self.literal(),
-      Act::Number => self.number(),
-      Act::Or => self.or(),
-      Act::String => self.string(),
-      Act::Super => self.super_(),
-      Act::This => self.this(),
-      Act::Unary => self.unary(),
-      Act::Variable => self.variable(can_assign),
+  /// Compile a index
+  fn index(&mut self, can_assign: bool) {
+    if self.parser.check(TokenKind::Colon) {
+      self.emit_byte(AlignedByteCode::Nil);
+    } else {
+      self.expression();
     }
-  }
 
-  /// Emit byte code for a return
-  fn emit_return(&mut self) {
-    match self.fun_kind {
-      FunKind::Initializer => self.emit_byte(AlignedByteCode::GetLocal(0)),
-      _ => self.emit_byte(AlignedByteCode::Nil),
+    if self.parser.match_kind(TokenKind::Colon) {
+      self.slice();
+      return;
     }
 
-    self.emit_byte(AlignedByteCode::Return);
+    self
+      .parser
+      .consume(TokenKind::RightBracket, "Expected ']' after index");
+
+    match self.assignment(can_assign) {
+      Some(assignment) => self.emit_assignment(
+        assignment,
+        AlignedByteCode::GetIndex,
+        AlignedByteCode::SetIndex,
+        2,
+      ),
+      None => self.emit_byte(AlignedByteCode::GetIndex),
+    }
   }
 
-  /// Add a constant to the current chunk
-  fn make_constant(&mut self, value: Value) -> u8 {
-    let index = self.fun.add_constant(&self.hooks, value);
-    if index > std::u8::MAX as usize {
-      self.parser.error("Too many constants in one chunk.");
-      return 0;
+  /// Compile the remainder of a slice after the start bound
+  fn slice(&mut self) {
+    self.slice_bound();
+    if self.parser.match_kind(TokenKind::Colon) {
+      self.slice_bound();
+    } else {
+      self.emit_byte(AlignedByteCode::Nil);
     }
 
-    index as u8
+    self
+      .parser
+      .consume(TokenKind::RightBracket, "Expected ']' after slice");
+    self.emit_byte(AlignedByteCode::Slice);
   }
 
-  /// Emit byte code for a constant
-  fn emit_constant(&mut self, value: Value) {
-    let index = self.make_constant(value);
-    self.emit_byte(AlignedByteCode::Constant(index));
+  /// Compile an optional slice bound, defaulting to nil
+  fn slice_bound(&mut self) {
+    if self.parser.check(TokenKind::Colon) || self.parser.check(TokenKind::RightBracket) {
+      self.emit_byte(AlignedByteCode::Nil);
+    } else {
+      self.expression();
+    }
   }
 
-  /// Patch a jump instruction
-  fn patch_jump(&mut self, offset: usize) {
-    let jump_landing = self.calc_jump(offset);
-    let buffer = jump_landing.to_ne_bytes();
-    self.fun.replace_instruction(offset, buffer[0]);
-    self.fun.replace_instruction(offset + 1, buffer[1]);
-  }
+  /// Compile an assignment destructuring a list into existing variables
+  fn destructuring_assignment(&mut self) {
+    let mut targets: Vec<Token> = Vec::new();
+    let mut rest: Option<Token> = None;
 
-  /// Calculate the jump once it's landing has been found
-  fn calc_jump(&mut self, offset: usize) -> u16 {
-    let jump = self.current_chunk().instructions.len() - offset - 2;
+    while !self.parser.check(TokenKind::RightBracket) {
+      if self.parser.match_kind(TokenKind::Ellipsis) {
+        self
+          .parser
+          .consume(TokenKind::Identifier, "Expect name after '...'.");
+        rest = Some(self.parser.previous.clone());
+        break;
+      }
 
-    if jump > std::u16::MAX.try_into().unwrap() {
-      self.parser.error("Too much code to jump over.");
+      if targets.len() == u8::MAX as usize {
+        self
+          .parser
+          .error("Cannot have more than 255 targets in destructuring assignment.");
+      }
+      self
+        .parser
+        .consume(TokenKind::Identifier, "Expect variable name.");
+      targets.push(self.parser.previous.clone());
+
+      if !self.parser.check(TokenKind::Comma) && !self.parser.check(TokenKind::RightBracket) {
+        self
+          .parser
+          .error_at_current("Can only destructure into variable names.");
+      }
+
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
+      }
     }
 
-    jump as u16
-  }
+    self
+      .parser
+      .consume(TokenKind::RightBracket, "Expect ']' after targets.");
+    self
+      .parser
+      .consume(TokenKind::Equal, "Expect '=' after targets.");
+    self.expression();
 
-  /// Emit a loop instruction
-  fn emit_loop(&mut self, loop_start: usize) {
-    let offset = self.current_chunk().instructions.len() - loop_start + 3;
-    if offset > std::u16::MAX.try_into().unwrap() {
-      self.parser.error("Loop body too large.");
+    // the assigned value remains on the stack as the result
+    self.emit_byte(AlignedByteCode::Dup);
+    match rest {
+      Some(_) => self.emit_byte(AlignedByteCode::DestructureListRest(targets.len() as u8)),
+      None => self.emit_byte(AlignedByteCode::DestructureList(targets.len() as u8)),
     }
 
-    self.emit_byte(AlignedByteCode::Loop(offset as u16));
-  }
+    for (index, target) in targets.iter().enumerate() {
+      if target.lexeme == WILDCARD {
+        continue;
+      }
 
-  /// Emit two provided instruction
-  fn emit_bytes(&mut self, op_code1: AlignedByteCode, op_code2: AlignedByteCode) {
-    let line = self.parser.previous.line;
-    self.write_instruction(op_code1, line);
-    self.write_instruction(op_code2, line);
-  }
+      let constant = self.make_constant(Value::Int(index as i64));
+      self.emit_bytes(AlignedByteCode::Dup, AlignedByteCode::Constant(constant));
+      self.emit_byte(AlignedByteCode::GetIndex);
+      self.set_variable(target.clone());
+      self.emit_byte(AlignedByteCode::Pop);
+    }
 
-  /// Emit a provided instruction
-  fn emit_byte(&mut self, op_code: AlignedByteCode) {
-    let line = self.parser.previous.line;
-    self.write_instruction(op_code, line);
+    if let Some(rest) = rest {
+      if rest.lexeme != WILDCARD {
+        self.emit_bytes(
+          AlignedByteCode::Dup,
+          AlignedByteCode::ListRest(targets.len() as u8),
+        );
+        self.set_variable(rest);
+        self.emit_byte(AlignedByteCode::Pop);
+      }
+    }
   }
 
-  /// Emit a jump instruction
-  fn emit_jump(&mut self, jump: AlignedByteCode) -> usize {
-    self.emit_byte(jump);
+  /// Compile a map literal
+  fn map(&mut self) {
+    self.emit_byte(AlignedByteCode::Map);
+    let mut entries: usize = 0;
 
-    self.current_chunk().instructions.len() - 2
-  }
-}
+    while !self.parser.check(TokenKind::RightBrace) {
+      if self.parser.match_kind(TokenKind::Ellipsis) {
+        // flush the entries so far before merging in the spread map
+        if entries > 0 {
+          self.emit_byte(AlignedByteCode::MapInit(entries as u16));
+          entries = 0;
+        }
 
-/// Get the first local for a given function kind
-fn first_local(fun_kind: FunKind) -> Local {
-  match fun_kind {
-    FunKind::Fun => Local {
-      name: Option::None,
-      depth: 0,
-      is_captured: false,
-    },
-    _ => Local {
-      name: Some("this".to_string()),
-      depth: 0,
-      is_captured: false,
-    },
-  }
-}
+        self.expression();
+        self.emit_byte(AlignedByteCode::MapSpread);
 
-/// The rules for infix and prefix operators
-const RULES_TABLE: [ParseRule; 44] = [
-  ParseRule::new(Some(Act::Grouping), Some(Act::Call), Precedence::Call),
-  // TOKEN_LEFT_PAREN
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_RIGHT_PAREN
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_LEFT_BRACE
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_RIGHT_BRACE
-  ParseRule::new(Some(Act::List), Some(Act::Index), Precedence::Call),
-  // TOKEN_LEFT_BRACKET
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_RIGHT_BRACKET
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_COMMA
-  ParseRule::new(None, Some(Act::Dot), Precedence::Call),
-  // TOKEN_DOT
-  ParseRule::new(Some(Act::Unary), Some(Act::Binary), Precedence::Term),
-  // TOKEN_MINUS
-  ParseRule::new(None, Some(Act::Binary), Precedence::Term),
-  // TOKEN_PLUS
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_COLON
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_SEMICOLON
-  ParseRule::new(None, Some(Act::Binary), Precedence::Factor),
-  // TOKEN_SLASH
-  ParseRule::new(None, Some(Act::Binary), Precedence::Factor),
-  // TOKEN_STAR
-  ParseRule::new(Some(Act::Unary), None, Precedence::None),
-  // TOKEN_BANG
-  ParseRule::new(None, Some(Act::Binary), Precedence::Equality),
-  // TOKEN_BANG_EQUAL
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_EQUAL
-  ParseRule::new(None, Some(Act::Binary), Precedence::Equality),
-  // TOKEN_EQUAL_EQUAL
-  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
-  // TOKEN_GREATER
-  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
-  // TOKEN_GREATER_EQUAL
-  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
-  // TOKEN_LESS
-  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
-  // TOKEN_LESS_EQUAL
-  ParseRule::new(Some(Act::Variable), None, Precedence::None),
-  // TOKEN_IDENTIFIER
-  ParseRule::new(Some(Act::String), None, Precedence::None),
-  // TOKEN_STRING
-  ParseRule::new(Some(Act::Number), None, Precedence::None),
-  // TOKEN_NUMBER
-  ParseRule::new(Some(Act::Map), None, Precedence::Call),
-  // TOKEN_MAP_OPEN
-  ParseRule::new(None, Some(Act::And), Precedence::And),
-  // TOKEN_AND
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_CLASS
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_ELSE
-  ParseRule::new(Some(Act::Literal), None, Precedence::None),
-  // TOKEN_FALSE
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_FOR
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_FUN
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_IF
-  ParseRule::new(Some(Act::Literal), None, Precedence::None),
-  // TOKEN_NIL
-  ParseRule::new(None, Some(Act::Or), Precedence::Or),
-  // TOKEN_OR
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_PRINT
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_RETURN
-  ParseRule::new(Some(Act::Super), None, Precedence::None),
-  // TOKEN_SUPER
-  ParseRule::new(Some(Act::This), None, Precedence::None),
-  // TOKEN_THIS
-  ParseRule::new(Some(Act::Literal), None, Precedence::None),
-  // TOKEN_TRUE
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_VAR
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_WHILE
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_ERROR
-  ParseRule::new(None, None, Precedence::None),
-  // TOKEN_EOF
-];
-
-/// Get a rule from the rules table
-const fn get_rule(kind: TokenKind) -> &'static ParseRule {
-  &RULES_TABLE[kind as usize]
-}
+        if !self.parser.match_kind(TokenKind::Comma) {
+          break;
+        }
+        continue;
+      }
 
-#[derive(Debug, Clone)]
-pub struct ClassCompiler {
-  enclosing: Option<Managed<ClassCompiler>>,
-  has_super_class: bool,
-  name: Token,
-}
+      self.expression();
+      self
+        .parser
+        .consume(TokenKind::Colon, "Expected ':' after map key");
+      self.expression();
 
-impl Trace for ClassCompiler {
-  fn trace(&self) -> bool {
-    do_if_some(self.enclosing, |enclosing| {
-      enclosing.trace();
-    });
-    true
-  }
+      if entries == u16::MAX as usize {
+        self.parser.error(&format!(
+          "Cannot have more than {} key value pairs in map literal",
+          entries
+        ));
+      }
+      entries += 1;
 
-  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
-    do_if_some(self.enclosing, |enclosing| {
-      enclosing.trace_debug(stdio);
-    });
-    true
-  }
-}
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
+      }
+    }
 
-impl Manage for ClassCompiler {
-  fn alloc_type(&self) -> &str {
-    "class compiler"
-  }
+    self
+      .parser
+      .consume(TokenKind::RightBrace, "Expected '}' after map");
 
-  fn debug(&self) -> String {
-    format!("{:?}", self)
+    if entries > 0 {
+      self.emit_byte(AlignedByteCode::MapInit(entries as u16))
+    }
   }
 
-  fn debug_free(&self) -> String {
-    format!(
-      "ClassCompiler: {{ has_super_class: {:?}, name: {:?}, enclosing: {{...}} }}",
-      self.has_super_class, self.name
-    )
-  }
+  /// Compile an dot operator
+  fn dot(&mut self, can_assign: bool) {
+    // 'return' and 'yield' are allowed as property names for generators and fibers
+    if !self.parser.match_kind(TokenKind::Return) && !self.parser.match_kind(TokenKind::Yield) {
+      self
+        .parser
+        .consume(TokenKind::Identifier, "Expect property name after '.'.");
+    }
+    let name = self.identifer_constant(self.parser.previous.clone());
 
-  fn size(&self) -> usize {
-    mem::size_of::<Self>()
+    if let Some(assignment) = self.assignment(can_assign) {
+      self.emit_assignment(
+        assignment,
+        AlignedByteCode::GetProperty(name),
+        AlignedByteCode::SetProperty(name),
+        1,
+      );
+    } else if self.parser.match_kind(TokenKind::LeftParen) {
+      match self.call_arguments() {
+        Some(arg_count) => self.emit_byte(AlignedByteCode::Invoke((name, arg_count))),
+        None => self.emit_byte(AlignedByteCode::InvokeSpread(name)),
+      }
+    } else {
+      self.emit_byte(AlignedByteCode::GetProperty(name));
+    }
   }
-}
-
-/// The space lox parser. This struct is responsible for
-/// advancing the scanner and checking for specific conditions
-pub struct Parser<'a, S: StdIo> {
-  /// The current token
-  current: Token,
-
-  /// The previous token
-  previous: Token,
 
-  /// Has the parser encountered an error
-  had_error: bool,
+  /// Compile a unary expression into it's equivalent bytecode
+  ///
+  /// # Panics
+  /// This method will panic if an invalid unary operator is attempted to compile
+  fn unary(&mut self) {
+    let operator_kind = self.parser.previous.kind;
 
-  /// Is the parser in panic mode
-  panic_mode: bool,
+    // Compile the operand
+    let prefix_increment = self.prefix_increment.take();
+    self.parse_precedence(Precedence::Unary);
+    self.prefix_increment = prefix_increment;
 
-  /// Help reference to the backing scanner
-  scanner: Scanner<'a>,
+    // Emit the operator instruction
+    match operator_kind {
+      TokenKind::Minus => self.emit_byte(AlignedByteCode::Negate),
+      TokenKind::Bang => self.emit_byte(AlignedByteCode::Not),
+      TokenKind::Tilde => self.emit_byte(AlignedByteCode::BitNot),
+      _ => panic!(),
+    }
+  }
 
-  /// The environments standard io access
-  stdio: S,
-}
+  /// Compile a prefix increment or decrement. The operator is left
+  /// pending until the final target of the operand claims it
+  fn increment(&mut self) {
+    let operator = self.parser.previous.clone();
+    let op = match operator.kind {
+      TokenKind::PlusPlus => AlignedByteCode::Add,
+      TokenKind::MinusMinus => AlignedByteCode::Subtract,
+      _ => panic!("Invalid increment operator"),
+    };
 
-impl<'a, S: StdIo> Parser<'a, S> {
-  /// Create a new instance of the parser from a source str
-  pub fn new(stdio: S, source: &'a str) -> Self {
-    Self {
-      scanner: Scanner::new(source),
-      stdio,
-      had_error: false,
-      panic_mode: false,
-      previous: Token {
-        lexeme: "error".to_string(),
-        line: 0,
-        kind: TokenKind::Error,
-      },
-      current: Token {
-        lexeme: "error".to_string(),
-        line: 0,
-        kind: TokenKind::Error,
-      },
+    let enclosing = self.prefix_increment.replace(op);
+    self.parse_precedence(Precedence::Unary);
+    let unclaimed = self.prefix_increment.take().is_some();
+    self.prefix_increment = enclosing;
+
+    if unclaimed {
+      match operator.kind {
+        // without a target '--' is still a double negation
+        TokenKind::MinusMinus => {
+          self.emit_bytes(AlignedByteCode::Negate, AlignedByteCode::Negate)
+        }
+        _ => self.parser.error_at(operator, "Invalid increment target."),
+      }
     }
   }
 
-  /// Does the provided token kind match if so advance the
-  /// token index
-  pub fn match_kind(&mut self, kind: TokenKind) -> bool {
-    if !self.check(kind) {
-      return false;
+  /// Determine if an assignment follows the target just compiled,
+  /// consuming its operator
+  fn assignment(&mut self, can_assign: bool) -> Option<Assignment> {
+    // a pending prefix increment belongs to the last target in a chain
+    if self.prefix_increment.is_some()
+      && get_rule(self.parser.current.kind).precedence < Precedence::Call
+    {
+      return self.prefix_increment.take().map(Assignment::Prefix);
     }
-    self.advance();
-    true
-  }
 
-  /// Does the provided token kind match the current kind
-  pub fn check(&self, kind: TokenKind) -> bool {
-    self.current.kind == kind
-  }
+    let assignment = match self.parser.current.kind {
+      TokenKind::Equal if can_assign => Assignment::Set,
+      TokenKind::PlusEqual if can_assign => Assignment::Compound(AlignedByteCode::Add),
+      TokenKind::MinusEqual if can_assign => Assignment::Compound(AlignedByteCode::Subtract),
+      TokenKind::StarEqual if can_assign => Assignment::Compound(AlignedByteCode::Multiply),
+      TokenKind::SlashEqual if can_assign => Assignment::Compound(AlignedByteCode::Divide),
+      TokenKind::PercentEqual if can_assign => Assignment::Compound(AlignedByteCode::Modulo),
+      TokenKind::PlusPlus => Assignment::Postfix(AlignedByteCode::Add),
+      TokenKind::MinusMinus => Assignment::Postfix(AlignedByteCode::Subtract),
+      _ => return None,
+    };
 
-  /// Advance the parser a token forward
-  pub fn advance(&mut self) {
-    self.previous = self.current.clone();
-    loop {
-      self.current = self.scanner.scan_token();
-      if self.current.kind != TokenKind::Error {
-        break;
+    self.parser.advance();
+    Some(assignment)
+  }
+
+  /// Emit an assignment to a target. The operands of the target, an
+  /// instance or a collection and its index, are already on the stack
+  /// and are duplicated so the target is only evaluated once
+  fn emit_assignment(
+    &mut self,
+    assignment: Assignment,
+    get_byte: AlignedByteCode,
+    set_byte: AlignedByteCode,
+    operands: u8,
+  ) {
+    match assignment {
+      Assignment::Set => self.expression(),
+      Assignment::Compound(op) => {
+        self.emit_duplicate(operands);
+        self.emit_byte(get_byte);
+        self.expression();
+        self.emit_byte(op);
+      }
+      Assignment::Prefix(op) => {
+        self.emit_duplicate(operands);
+        self.emit_byte(get_byte);
+        self.emit_constant(Value::Int(1));
+        self.emit_byte(op);
       }
+      Assignment::Postfix(op) => {
+        self.emit_duplicate(operands);
+        self.emit_byte(get_byte);
+
+        // keep the original value below the operands
+        self.emit_byte(AlignedByteCode::Dup);
+        if operands > 0 {
+          self.emit_byte(AlignedByteCode::Rotate(operands + 1));
+        }
 
-      self.error_at_current(&self.current.lexeme.to_string())
+        self.emit_constant(Value::Int(1));
+        self.emit_byte(op);
+        self.emit_bytes(set_byte, AlignedByteCode::Pop);
+        return;
+      }
     }
+
+    self.emit_byte(set_byte);
   }
 
-  /// Consume a token and advance the current token index
-  fn consume(&mut self, kind: TokenKind, message: &str) {
-    if self.current.kind == kind {
-      self.advance();
-      return;
+  /// Duplicate the operands of an assignment target
+  fn emit_duplicate(&mut self, operands: u8) {
+    match operands {
+      0 => (),
+      1 => self.emit_byte(AlignedByteCode::Dup),
+      2 => self.emit_byte(AlignedByteCode::DupTwo),
+      _ => panic!("Invalid assignment operands"),
     }
-
-    self.error_at_current(message)
   }
 
-  /// Indicate an error occurred at he current index
-  fn error_at_current(&mut self, message: &str) {
-    let token = self.current.clone();
-    self.error_at(token, message);
-  }
+  /// Compile an anonymous function expression
+  fn lambda(&mut self) {
+    let name = self.hooks.manage_str(LAMBDA.to_string());
 
-  /// Indicate an error occurred at the previous index
-  pub fn error(&mut self, message: &str) {
-    let token = self.previous.clone();
-    self.error_at(token, message);
+    self
+      .parser
+      .consume(TokenKind::LeftParen, "Expect '(' after 'fun'.");
+    self.function_body(name, FunKind::Fun, false, None);
   }
 
-  /// Print an error to the console for a user to address
-  fn error_at(&mut self, token: Token, message: &str) {
-    if self.panic_mode {
+  /// Compile a grouping expression
+  fn grouping(&mut self) {
+    if self.parser.is_arrow_parameters() {
+      let name = self.hooks.manage_str(LAMBDA.to_string());
+      self.function_body(name, FunKind::Fun, true, None);
       return;
     }
 
-    self.panic_mode = true;
-    self.stdio.eprint(&format!("[line {}] Error", token.line));
-
-    match token.kind {
-      TokenKind::Eof => self.stdio.eprint(" at end"),
-      TokenKind::Error => (),
-      _ => self.stdio.eprint(&format!(" at {}", token.lexeme)),
-    }
+    self.expression();
 
-    self.stdio.eprintln(&format!(": {}", message));
-    self.had_error = true;
+    self
+      .parser
+      .consume(TokenKind::RightParen, "Expected ')' after expression")
   }
-}
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
-enum Precedence {
-  None,
-  Assignment,
-  Or,
-  And,
-  Equality,
-  Comparison,
-  Term,
-  Factor,
-  Unary,
-  Call,
-  Primary,
-}
+  /// Compile a number literal
+  fn number(&mut self) {
+    let value = self.number_value();
+    self.emit_constant(value);
+  }
 
-impl Precedence {
-  pub fn higher(&self) -> Precedence {
-    match self {
-      Precedence::None => Precedence::Assignment,
-      Precedence::Assignment => Precedence::Or,
-      Precedence::Or => Precedence::And,
-      Precedence::And => Precedence::Equality,
-      Precedence::Equality => Precedence::Comparison,
-      Precedence::Comparison => Precedence::Term,
-      Precedence::Term => Precedence::Factor,
-      Precedence::Factor => Precedence::Unary,
-      Precedence::Unary => Precedence::Call,
-      Precedence::Call => Precedence::Primary,
-      Precedence::Primary => panic!("Primary is highest precedence"),
+  /// Parse the value of the previous number or integer token
+  fn number_value(&mut self) -> Value {
+    let lexeme = &self.parser.previous.lexeme;
+    match self.parser.previous.kind {
+      TokenKind::Integer => match lexeme.parse::<i64>() {
+        Ok(int) => Value::Int(int),
+        Err(_) => {
+          self.parser.error("Integer literal is too large.");
+          Value::Int(0)
+        }
+      },
+      _ => Value::Number(lexeme.parse::<f64>().expect("Unable to parse float")),
     }
   }
-}
 
-struct ParseRule {
-  prefix: Option<Act>,
-  infix: Option<Act>,
-  precedence: Precedence,
-}
+  /// Compile a variable statement
+  fn variable(&mut self, can_assign: bool) {
+    self.named_variable(self.parser.previous.clone(), can_assign);
+  }
 
-impl ParseRule {
-  const fn new(prefix: Option<Act>, infix: Option<Act>, precedence: Precedence) -> Self {
-    Self {
-      prefix,
-      infix,
-      precedence,
-    }
+  /// Compile a string literal
+  fn string(&mut self) {
+    let string = self
+      .hooks
+      .manage_str(copy_string(&self.parser.previous));
+    let value = Value::String(string);
+    self.emit_constant(value)
   }
-}
 
-#[derive(Clone)]
-enum Act {
-  And,
-  Binary,
-  Call,
-  Index,
-  List,
-  Map,
-  Dot,
-  Grouping,
-  Literal,
-  Number,
-  Or,
-  String,
-  Super,
-  This,
-  Unary,
-  Variable,
-}
+  /// Compile an interpolated string into concatenations of each
+  /// string segment and the `str` of each interpolated expression
+  fn interpolation(&mut self) {
+    let str_constant = self.name_constant(STR);
+    let mut has_prefix = self.string_segment();
 
-#[cfg(test)]
-mod test {
-  use super::*;
-  use crate::debug::disassemble_chunk;
-  use spacelox_core::chunk::decode_u16;
-  use spacelox_core::memory::Gc;
-  use spacelox_core::{hooks::NoContext, io::{NativeIo, NativeStdIo}};
+    loop {
+      self.expression();
+      self.emit_byte(AlignedByteCode::Invoke((str_constant, 0)));
+      if has_prefix {
+        self.emit_byte(AlignedByteCode::Add);
+      }
+      has_prefix = true;
 
-  enum ByteCodeTest {
-    Code(AlignedByteCode),
-    Fun((u8, Vec<ByteCodeTest>)),
+      if self.parser.match_kind(TokenKind::Interpolation) {
+        if self.string_segment() {
+          self.emit_byte(AlignedByteCode::Add);
+        }
+        continue;
+      }
+
+      self
+        .parser
+        .consume(TokenKind::String, "Expect '}' after interpolation.");
+      if self.parser.previous.kind == TokenKind::String && self.string_segment() {
+        self.emit_byte(AlignedByteCode::Add);
+      }
+      break;
+    }
   }
 
-  fn test_compile<'a>(src: String, gc: &mut Gc) -> Managed<Fun> {
-    let io = NativeIo::new();
-    let mut parser = Parser::new(io.stdio(), &src);
+  /// Emit the previous string segment if not empty
+  fn string_segment(&mut self) -> bool {
+    let segment = copy_string(&self.parser.previous);
+    if segment.is_empty() {
+      return false;
+    }
 
-    let mut context = NoContext::new(gc);
-    let hooks = &Hooks::new(&mut context);
+    let string = self.hooks.manage_str(segment);
+    self.emit_constant(Value::String(string));
+    true
+  }
 
-    let compiler = Compiler::new(io, &mut parser, &hooks);
-    let result = compiler.compile();
-    assert_eq!(result.success, true);
+  /// retrieve a named variable from either local or global scope
+  fn named_variable(&mut self, name: Token, can_assign: bool) {
+    let (get_byte, set_byte, is_const) = self.resolve_variable(&name);
+
+    match self.assignment(can_assign) {
+      Some(assignment) => {
+        if is_const {
+          self.parser.error_at(name, CONST_ASSIGNMENT);
+        } else if let AlignedByteCode::SetGlobal(_) = set_byte {
+          self.script_compiler().global_assignments.push(name);
+        }
 
-    result.fun
+        self.emit_assignment(assignment, get_byte, set_byte, 0);
+      }
+      None => self.emit_byte(get_byte),
+    }
   }
 
-  fn decode_byte_code(fun: Managed<Fun>) -> Vec<AlignedByteCode> {
-    let bytes = &fun.chunk().instructions;
-    let mut decoded = Vec::new();
-    let mut offset = 0;
+  /// Set a named variable to the value on top of the stack
+  fn set_variable(&mut self, name: Token) {
+    let (_, set_byte, is_const) = self.resolve_variable(&name);
 
-    while offset < bytes.len() {
-      let (byte_code, new_offset) = AlignedByteCode::decode(&bytes, offset);
+    if is_const {
+      self.parser.error_at(name, CONST_ASSIGNMENT);
+    } else if let AlignedByteCode::SetGlobal(_) = set_byte {
+      self.script_compiler().global_assignments.push(name);
+    }
 
-      match byte_code {
-        AlignedByteCode::Closure(closure) => {
-          decoded.push(byte_code);
-          offset = decode_byte_code_closure(fun, &mut decoded, new_offset, closure)
-        }
-        _ => {
-          decoded.push(byte_code);
-          offset = new_offset;
+    self.emit_byte(set_byte);
+  }
+
+  /// Resolve the instructions to get and set a named variable and whether
+  /// it is const
+  fn resolve_variable(&mut self, name: &Token) -> (AlignedByteCode, AlignedByteCode, bool) {
+    match self.resolve_local(name) {
+      Some(local) => (
+        AlignedByteCode::GetLocal(local),
+        AlignedByteCode::SetLocal(local),
+        self.locals[local as usize].is_const,
+      ),
+      None => match self.resolve_upvalue(name) {
+        Some(upvalue) => (
+          AlignedByteCode::GetUpvalue(upvalue),
+          AlignedByteCode::SetUpvalue(upvalue),
+          self.is_const_upvalue(name),
+        ),
+        None => {
+          let global_index = self.identifer_constant(name.clone());
+          (
+            AlignedByteCode::GetGlobal(global_index),
+            AlignedByteCode::SetGlobal(global_index),
+            false,
+          )
         }
-      }
+      },
     }
+  }
 
-    decoded
+  /// Compile a literal
+  fn literal(&mut self) {
+    match self.parser.previous.kind {
+      TokenKind::True => self.emit_byte(AlignedByteCode::True),
+      TokenKind::False => self.emit_byte(AlignedByteCode::False),
+      TokenKind::Nil => self.emit_byte(AlignedByteCode::Nil),
+      _ => panic!("Unexpected token kind {:?}", self.parser.previous.kind),
+    }
   }
 
-  fn decode_byte_code_closure(
-    fun: Managed<Fun>,
-    decoded: &mut Vec<AlignedByteCode>,
-    offset: usize,
-    slot: u8,
-  ) -> usize {
-    let inner_fun = fun.chunk().constants[slot as usize].to_fun();
-    let mut current_offset = offset;
+  /// Compile an expression an a provided precedences
+  fn parse_precedence(&mut self, precedence: Precedence) {
+    self.parser.advance();
 
-    let instructions = &fun.chunk().instructions;
-    for _ in 0..inner_fun.upvalue_count {
-      let scalar = decode_u16(&instructions[offset..offset + 2]);
+    let can_assign = precedence <= Precedence::Assignment;
+    let prefix_fn = get_rule(self.parser.previous.kind).prefix.clone();
 
-      let upvalue_index: UpvalueIndex = unsafe { mem::transmute(scalar) };
-      decoded.push(AlignedByteCode::UpvalueIndex(upvalue_index));
-      current_offset = current_offset + 2;
+    match prefix_fn {
+      Some(prefix) => self.execute_action(prefix, can_assign),
+      None => {
+        self.parser.error("Expected expression.");
+        return;
+      }
     }
 
-    current_offset
+    while precedence <= get_rule(self.parser.current.kind).precedence {
+      self.parser.advance();
+      let infix_fn = get_rule(self.parser.previous.kind).infix.clone();
+
+      self.execute_action(infix_fn.expect("Failure"), can_assign);
+    }
+
+    if can_assign && is_assignment_operator(self.parser.current.kind) {
+      self.parser.advance();
+      self.parser.error("Invalid assignment target.")
+    }
   }
 
-  fn assert_simple_bytecode(fun: Managed<Fun>, code: &[AlignedByteCode]) {
-    disassemble_chunk(&NativeStdIo::new(), &fun.chunk(), "test");
-    let decoded_byte_code = decode_byte_code(fun);
-    assert_eq!(decoded_byte_code.len(), code.len());
+  /// Define a variable
+  fn define_variable(&mut self, global: u8) {
+    if self.scope_depth > 0 {
+      self.mark_initialized();
+      return;
+    }
 
-    decoded_byte_code
-      .iter()
-      .zip(code.iter())
-      .for_each(|(actual, expect)| assert_eq!(actual, expect));
+    self.emit_byte(AlignedByteCode::DefineGlobal(global));
   }
 
-  fn assert_fun_bytecode(fun: Managed<Fun>, code: &[ByteCodeTest]) {
-    disassemble_chunk(&NativeStdIo::new(), &fun.chunk(), &*fun.name);
-    let decoded_byte_code = decode_byte_code(fun);
-    assert_eq!(decoded_byte_code.len(), code.len(), "for fun {}", fun.name);
+  /// Parse a list of argument to a function. If the arguments contain
+  /// a spread they are collected into a list and None is returned
+  fn call_arguments(&mut self) -> Option<u8> {
+    let arg_count = self.consume_arguments(TokenKind::RightParen, u8::MAX as usize, false);
+    self
+      .parser
+      .consume(TokenKind::RightParen, "Expect ')' after arguments");
+    arg_count.map(|arg_count| arg_count as u8)
+  }
 
-    for i in 0..code.len() {
-      match decoded_byte_code[i] {
-        AlignedByteCode::Closure(index) => {
-          let fun = fun.chunk().constants[index as usize].to_fun();
+  /// Parse a list of argument defining a list. Any arguments preceding a
+  /// spread are already in the list so only the remaining count is returned
+  fn list_arguments(&mut self) -> u16 {
+    let arg_count = self.consume_arguments(TokenKind::RightBracket, u16::MAX as usize, true);
+    self
+      .parser
+      .consume(TokenKind::RightBracket, "Expect ']' after arguments");
 
-          match &code[i] {
-            ByteCodeTest::Fun((expected, inner)) => {
-              assert_eq!(*expected, index);
-              assert_fun_bytecode(fun, &inner);
-            }
-            _ => assert!(false),
+    match arg_count {
+      Some(arg_count) => arg_count as u16,
+      None => 0,
+    }
+  }
+
+  /// Consume a comma separated set of arguments for calls and lists. Once
+  /// a spread is found the arguments are collected into a list, created
+  /// below them unless `in_list` indicates one already exists
+  fn consume_arguments(
+    &mut self,
+    stop_token: TokenKind,
+    max: usize,
+    in_list: bool,
+  ) -> Option<usize> {
+    let mut arg_count: usize = 0;
+    let mut spread = false;
+
+    while !self.parser.check(stop_token) {
+      if self.parser.match_kind(TokenKind::Ellipsis) {
+        if !spread && !in_list {
+          self.emit_byte(AlignedByteCode::List);
+          if arg_count > 0 {
+            self.emit_byte(AlignedByteCode::Rotate(arg_count as u8));
           }
         }
-        _ => match &code[i] {
-          ByteCodeTest::Code(byte_code) => {
-            assert_eq!(&decoded_byte_code[i], byte_code);
-          }
-          _ => assert!(false),
-        },
-      }
-    }
+        spread = true;
+
+        // flush the arguments so far before extending with the spread list
+        if arg_count > 0 {
+          self.emit_byte(AlignedByteCode::ListInit(arg_count as u16));
+          arg_count = 0;
+        }
+
+        self.expression();
+        if in_list {
+          self.emit_byte(AlignedByteCode::ListSpread);
+        } else {
+          self.emit_byte(AlignedByteCode::ArgumentSpread);
+        }
+      } else {
+        self.expression();
+
+        if arg_count == max {
+          self
+            .parser
+            .error(&format!("Cannot have more than {} arguments", max));
+          return Some(arg_count);
+        }
+        arg_count += 1;
+      }
+
+      if !self.parser.match_kind(TokenKind::Comma) {
+        break;
+      }
+    }
+
+    if spread {
+      if arg_count > 0 {
+        self.emit_byte(AlignedByteCode::ListInit(arg_count as u16));
+      }
+      None
+    } else {
+      Some(arg_count)
+    }
+  }
+
+  /// Emit instruction for a short circuited and
+  fn and(&mut self) {
+    let end_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
+
+    self.emit_byte(AlignedByteCode::Pop);
+    self.parse_precedence(Precedence::And);
+
+    self.patch_jump(end_jump);
+  }
+
+  /// Emit instruction for a short circuited and
+  fn or(&mut self) {
+    let else_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
+    let end_jump = self.emit_jump(AlignedByteCode::Jump(0));
+
+    self.patch_jump(else_jump);
+    self.emit_byte(AlignedByteCode::Pop);
+
+    self.parse_precedence(Precedence::Or);
+    self.patch_jump(end_jump);
+  }
+
+  /// Emit instructions for a conditional expression
+  fn conditional(&mut self) {
+    let else_jump = self.emit_jump(AlignedByteCode::JumpIfFalse(0));
+
+    self.emit_byte(AlignedByteCode::Pop);
+    self.parse_precedence(Precedence::Conditional);
+    self.parser.consume(
+      TokenKind::Colon,
+      "Expect ':' after then branch of conditional expression.",
+    );
+    let end_jump = self.emit_jump(AlignedByteCode::Jump(0));
+
+    self.patch_jump(else_jump);
+    self.emit_byte(AlignedByteCode::Pop);
+
+    self.parse_precedence(Precedence::Conditional);
+    self.patch_jump(end_jump);
+  }
+
+  /// Emit instructions for a nil coalescing expression
+  fn coalesce(&mut self) {
+    let else_jump = self.emit_jump(AlignedByteCode::JumpIfNil(0));
+    let end_jump = self.emit_jump(AlignedByteCode::Jump(0));
+
+    self.patch_jump(else_jump);
+    self.emit_byte(AlignedByteCode::Pop);
+
+    self.parse_precedence(Precedence::Coalesce);
+    self.patch_jump(end_jump);
+  }
+
+  /// Compile an optional dot operator skipping the rest of the
+  /// chain if the receiver is nil
+  fn optional_dot(&mut self) {
+    let nil_jump = self.emit_jump(AlignedByteCode::JumpIfNil(0));
+    self.dot(false);
+
+    while Precedence::Call <= get_rule(self.parser.current.kind).precedence {
+      self.parser.advance();
+      let infix_fn = get_rule(self.parser.previous.kind).infix.clone();
+
+      self.execute_action(infix_fn.expect("Failure"), false);
+    }
+
+    self.patch_jump(nil_jump);
+  }
+
+  /// Parse a yield expression. A function containing a yield is compiled
+  /// as a generator, the expression's value is whatever the generator is
+  /// resumed with
+  fn yield_(&mut self) {
+    match self.fun_kind {
+      FunKind::Script => self.parser.error("Cannot yield from top-level code."),
+      FunKind::Initializer => self.parser.error("Cannot yield from an initializer."),
+      FunKind::Setter => self.parser.error("Cannot yield from a setter."),
+      _ => (),
+    }
+    self.fun.is_generator = true;
+
+    match self.parser.current.kind {
+      TokenKind::Semicolon
+      | TokenKind::RightParen
+      | TokenKind::RightBracket
+      | TokenKind::RightBrace
+      | TokenKind::Comma
+      | TokenKind::Colon => self.emit_byte(AlignedByteCode::Nil),
+      _ => self.expression(),
+    }
+
+    self.emit_byte(AlignedByteCode::Yield);
+  }
+
+  /// Parse a class's this identifier
+  fn this(&mut self) {
+    if self.current_class.is_none() {
+      self.parser.error("Cannot use 'this' outside of class.");
+      return;
+    }
+
+    self.variable(false);
+  }
+
+  fn super_(&mut self) {
+    match self.current_class {
+      None => self.parser.error("Cannot use 'super' outside of a class."),
+      Some(class) => {
+        if class.is_trait {
+          self.parser.error("Cannot use 'super' in a trait.");
+        } else if !class.has_super_class {
+          self
+            .parser
+            .error("Cannot use 'super' in a class with no superclass.");
+        }
+      }
+    }
+
+    self
+      .parser
+      .consume(TokenKind::Dot, "Expect '.' after 'super'.");
+    self
+      .parser
+      .consume(TokenKind::Identifier, "Expect superclass method name.");
+    let name = self.identifer_constant(self.parser.previous.clone());
+
+    self.named_variable(
+      Token {
+        lexeme: THIS.to_string(),
+        kind: TokenKind::This,
+        line: self.parser.previous.line,
+      },
+      false,
+    );
+
+    if self.parser.match_kind(TokenKind::LeftParen) {
+      let arg_count = self.call_arguments();
+      self.named_variable(
+        Token {
+          lexeme: SUPER.to_string(),
+          kind: TokenKind::Super,
+          line: self.parser.previous.line,
+        },
+        false,
+      );
+      match arg_count {
+        Some(arg_count) => self.emit_byte(AlignedByteCode::SuperInvoke((name, arg_count))),
+        None => self.emit_byte(AlignedByteCode::SuperInvokeSpread(name)),
+      }
+    } else {
+      self.named_variable(
+        Token {
+          lexeme: "super".to_string(),
+          kind: TokenKind::Super,
+          line: self.parser.previous.line,
+        },
+        false,
+      );
+
+      self.emit_byte(AlignedByteCode::GetSuper(name));
+    }
+  }
+
+  /// Parse a variable from the provided token return it's new constant
+  /// identifer if an identifer was identified
+  fn parse_variable(&mut self, error_message: &str) -> u8 {
+    self.parser.consume(TokenKind::Identifier, error_message);
+    self.declare_variable();
+    if self.scope_depth > 0 {
+      return 0;
+    }
+    self.identifer_constant(self.parser.previous.clone())
+  }
+
+  /// Mark a variable initialized
+  fn mark_initialized(&mut self) {
+    if self.scope_depth > 0 {
+      self.locals[self.local_count - 1].depth = self.scope_depth;
+    }
+  }
+
+  /// Generate a constant from the provided identifier token
+  fn identifer_constant(&mut self, name: Token) -> u8 {
+    let identifer = self.hooks.manage_str(name.lexeme);
+    self.make_constant(Value::String(identifer))
+  }
+
+  /// Generate a constant for a name known to the compiler
+  fn name_constant(&mut self, name: &str) -> u8 {
+    let identifer = self.hooks.manage_str(name.to_string());
+    self.make_constant(Value::String(identifer))
+  }
+
+  fn add_local(&mut self, name: Token) {
+    if self.local_count == u8::MAX as usize {
+      self.parser.error("Too many local variables in function.");
+      return;
+    }
+
+    let local = &mut self.locals[self.local_count];
+    self.local_count += 1;
+
+    local.name = Some(name.lexeme);
+    local.depth = -1;
+  }
+
+  /// Add a local only visible to the compiler
+  fn add_hidden_local(&mut self, name: &str) {
+    self.add_local(Token {
+      kind: TokenKind::Identifier,
+      lexeme: name.to_string(),
+      line: self.parser.previous.line,
+    });
+    self.mark_initialized();
+  }
+
+  ///  declare a variable
+  fn declare_variable(&mut self) {
+    self.declare_name(self.parser.previous.clone());
+  }
+
+  /// declare a variable with the provided name
+  fn declare_name(&mut self, name: Token) {
+    // if global exit
+    if self.scope_depth == 0 {
+      if self.script_compiler().const_globals.contains(&name.lexeme) {
+        self.parser.error("Cannot redeclare a const variable.");
+      }
+      return;
+    }
+
+    for i in (0..self.local_count).rev() {
+      let local = &self.locals[i];
+
+      // If we in a lower scope break
+      if local.depth != UNINITIALIZED && local.depth < self.scope_depth {
+        break;
+      }
+
+      // check that the same variable wasn't declared twice in the same scope
+      if let Some(local_name) = &local.name {
+        if &name.lexeme == local_name {
+          self
+            .parser
+            .error("Variable with this name already declared in this scope.");
+        }
+      }
+    }
+
+    self.add_local(name);
+  }
+
+  /// resolve a token to a local if it exists
+  fn resolve_local(&mut self, name: &Token) -> Option<u8> {
+    for i in (0..self.local_count).rev() {
+      let local = &self.locals[i];
+
+      if let Some(local_name) = &local.name {
+        if &name.lexeme == local_name {
+          // handle the case were `var a = a;`
+          if local.depth == UNINITIALIZED {
+            self
+              .parser
+              .error("Cannot read local variable in its own initializer.")
+          }
+
+          return Some(i as u8);
+        }
+      }
+    }
+
+    // not found
+    Option::None
+  }
+
+  /// resolve a token to an upvalue in an enclosing scope if it exists
+  fn resolve_upvalue(&mut self, name: &Token) -> Option<u8> {
+    match self.enclosing {
+      Some(parent_ptr) => {
+        let parent = unsafe { &mut *parent_ptr };
+
+        match parent.resolve_local(name) {
+          Some(local) => {
+            parent.locals[local as usize].is_captured = true;
+            Some(self.add_upvalue(UpvalueIndex::Local(local)) as u8)
+          }
+          None => parent
+            .resolve_upvalue(name)
+            .map(|upvalue| self.add_upvalue(UpvalueIndex::Upvalue(upvalue)) as u8),
+        }
+      }
+      None => None,
+    }
+  }
+
+  /// Is the local captured by an upvalue of this name const
+  fn is_const_upvalue(&self, name: &Token) -> bool {
+    match self.enclosing {
+      Some(parent_ptr) => {
+        let parent = unsafe { &*parent_ptr };
+
+        let local = parent.locals[0..parent.local_count]
+          .iter()
+          .rev()
+          .find(|local| local.name.as_ref() == Some(&name.lexeme));
+
+        match local {
+          Some(local) => local.is_const,
+          None => parent.is_const_upvalue(name),
+        }
+      }
+      None => false,
+    }
+  }
+
+  /// The compiler of the top level script
+  fn script_compiler(&mut self) -> &mut Self {
+    match self.enclosing {
+      Some(parent_ptr) => unsafe { &mut *parent_ptr }.script_compiler(),
+      None => self,
+    }
+  }
+
+  /// Report any assignment to a global that was declared const
+  fn check_global_assignments(&mut self) {
+    for name in mem::take(&mut self.global_assignments) {
+      if self.const_globals.contains(&name.lexeme) {
+        self.parser.error_at(name, CONST_ASSIGNMENT);
+      }
+    }
+  }
+
+  /// add an upvalue
+  fn add_upvalue(&mut self, upvalue: UpvalueIndex) -> usize {
+    let upvalue_count = self.fun.upvalue_count;
+
+    // check for existing upvalues
+    if let Some(i) = self.upvalues[0..upvalue_count]
+      .iter()
+      .position(|existing_upvalue| match (&existing_upvalue, &upvalue) {
+        (UpvalueIndex::Local(existing), UpvalueIndex::Local(new)) => *existing == *new,
+        _ => false,
+      })
+    {
+      return i;
+    }
+
+    // prevent overflow
+    if upvalue_count == u8::MAX as usize {
+      self.parser.error("Too many closure variable in function.");
+      return 0;
+    }
+
+    self.upvalues[upvalue_count] = upvalue;
+    self.fun.upvalue_count += 1;
+
+    upvalue_count
+  }
+
+  /// Execute a provided action
+  fn execute_action(&mut self, action: Act, can_assign: bool) {
+    match action {
+      Act::And => self.and(),
+      Act::Binary => self.binary(),
+      Act::Call => self.call(),
+      Act::Coalesce => self.coalesce(),
+      Act::Conditional => self.conditional(),
+      Act::List => self.list(can_assign),
+      Act::Map => self.map(),
+      Act::Increment => self.increment(),
+      Act::Index => self.index(can_assign),
+      Act::Dot => self.dot(can_assign),
+      Act::Grouping => self.grouping(),
+      Act::Interpolation => self.interpolation(),
+      Act::Lambda => self.lambda(),
+      Act::Literal => self.literal(),
+      Act::Number => self.number(),
+      Act::OptionalDot => self.optional_dot(),
+      Act::Or => self.or(),
+      Act::String => self.string(),
+      Act::Super => self.super_(),
+      Act::This => self.this(),
+      Act::Unary => self.unary(),
+      Act::Variable => self.variable(can_assign),
+      Act::Yield => self.yield_(),
+    }
+  }
+
+  /// Emit byte code for a return
+  fn emit_return(&mut self) {
+    self.emit_return_value();
+    self.emit_byte(AlignedByteCode::Return);
+  }
+
+  /// Emit the value returned when no value is given
+  fn emit_return_value(&mut self) {
+    match self.fun_kind {
+      FunKind::Initializer => self.emit_byte(AlignedByteCode::GetLocal(0)),
+      FunKind::Setter => self.emit_byte(AlignedByteCode::GetLocal(1)),
+      _ => self.emit_byte(AlignedByteCode::Nil),
+    }
+  }
+
+  /// Add a constant to the current chunk
+  fn make_constant(&mut self, value: Value) -> u8 {
+    let index = self.fun.add_constant(self.hooks, value);
+    if index > u8::MAX as usize {
+      self.parser.error("Too many constants in one chunk.");
+      return 0;
+    }
+
+    index as u8
+  }
+
+  /// Emit byte code for a constant
+  fn emit_constant(&mut self, value: Value) {
+    let index = self.make_constant(value);
+    self.emit_byte(AlignedByteCode::Constant(index));
+  }
+
+  /// Patch a jump instruction
+  fn patch_jump(&mut self, offset: usize) {
+    let jump_landing = self.calc_jump(offset);
+    let buffer = jump_landing.to_ne_bytes();
+    self.fun.replace_instruction(offset, buffer[0]);
+    self.fun.replace_instruction(offset + 1, buffer[1]);
+  }
+
+  /// Calculate the jump once it's landing has been found
+  fn calc_jump(&mut self, offset: usize) -> u16 {
+    let jump = self.current_chunk().instructions.len() - offset - 2;
+
+    if jump > u16::MAX.into() {
+      self.parser.error("Too much code to jump over.");
+    }
+
+    jump as u16
+  }
+
+  /// Emit a loop instruction
+  fn emit_loop(&mut self, loop_start: usize) {
+    let offset = self.current_chunk().instructions.len() - loop_start + 3;
+    if offset > u16::MAX.into() {
+      self.parser.error("Loop body too large.");
+    }
+
+    self.emit_byte(AlignedByteCode::Loop(offset as u16));
+  }
+
+  /// Emit two provided instruction
+  fn emit_bytes(&mut self, op_code1: AlignedByteCode, op_code2: AlignedByteCode) {
+    let line = self.parser.previous.line;
+    self.write_instruction(op_code1, line);
+    self.write_instruction(op_code2, line);
+  }
+
+  /// Emit a provided instruction
+  fn emit_byte(&mut self, op_code: AlignedByteCode) {
+    let line = self.parser.previous.line;
+    self.write_instruction(op_code, line);
+  }
+
+  /// Emit a jump instruction
+  fn emit_jump(&mut self, jump: AlignedByteCode) -> usize {
+    self.emit_byte(jump);
+
+    self.current_chunk().instructions.len() - 2
+  }
+}
+
+/// Get the first local for a given function kind
+/// Collect the names a pattern binds with the path to each
+/// Can the pattern destructure a value, containing only names, lists and
+/// maps
+fn is_destructuring_pattern(pattern: &Pattern) -> bool {
+  match pattern {
+    Pattern::Wildcard | Pattern::Binding(_) => true,
+    Pattern::List(elements, _) => elements.iter().all(is_destructuring_pattern),
+    Pattern::Map(entries) => entries
+      .iter()
+      .all(|(_, entry)| is_destructuring_pattern(entry)),
+    Pattern::Literal(_) | Pattern::Instance(_, _) => false,
+  }
+}
+
+fn pattern_bindings(
+  pattern: &Pattern,
+  path: &mut Vec<PatternStep>,
+  bindings: &mut Vec<(Token, Vec<PatternStep>)>,
+) {
+  match pattern {
+    Pattern::Wildcard | Pattern::Literal(_) => {}
+    Pattern::Binding(name) => bindings.push((name.clone(), path.clone())),
+    Pattern::List(elements, rest) => {
+      for (index, element) in elements.iter().enumerate() {
+        path.push(PatternStep::Element(index as u8));
+        pattern_bindings(element, path, bindings);
+        path.pop();
+      }
+
+      if let Some(rest) = rest {
+        path.push(PatternStep::Rest(elements.len() as u8));
+        pattern_bindings(rest, path, bindings);
+        path.pop();
+      }
+    }
+    Pattern::Map(entries) => {
+      for (key, entry) in entries {
+        path.push(PatternStep::Key(*key));
+        pattern_bindings(entry, path, bindings);
+        path.pop();
+      }
+    }
+    Pattern::Instance(_, fields) => {
+      for (field, entry) in fields {
+        path.push(PatternStep::Field(*field));
+        pattern_bindings(entry, path, bindings);
+        path.pop();
+      }
+    }
+  }
+}
+
+fn first_local(fun_kind: FunKind) -> Local {
+  match fun_kind {
+    FunKind::Fun => Local {
+      name: Option::None,
+      depth: 0,
+      is_captured: false,
+      is_const: false,
+    },
+    _ => Local {
+      name: Some("this".to_string()),
+      depth: 0,
+      is_captured: false,
+      is_const: false,
+    },
+  }
+}
+
+/// The rules for infix and prefix operators
+const RULES_TABLE: [ParseRule; 86] = [
+  ParseRule::new(Some(Act::Grouping), Some(Act::Call), Precedence::Call),
+  // TOKEN_LEFT_PAREN
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_RIGHT_PAREN
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_LEFT_BRACE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_RIGHT_BRACE
+  ParseRule::new(Some(Act::List), Some(Act::Index), Precedence::Call),
+  // TOKEN_LEFT_BRACKET
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_RIGHT_BRACKET
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_COMMA
+  ParseRule::new(None, Some(Act::Dot), Precedence::Call),
+  // TOKEN_DOT
+  ParseRule::new(None, Some(Act::Binary), Precedence::Range),
+  // TOKEN_DOT_DOT
+  ParseRule::new(None, Some(Act::Binary), Precedence::Range),
+  // TOKEN_DOT_DOT_EQUAL
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_ELLIPSIS
+  ParseRule::new(Some(Act::Unary), Some(Act::Binary), Precedence::Term),
+  // TOKEN_MINUS
+  ParseRule::new(None, Some(Act::Binary), Precedence::Term),
+  // TOKEN_PLUS
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_COLON
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_SEMICOLON
+  ParseRule::new(None, Some(Act::Binary), Precedence::Factor),
+  // TOKEN_SLASH
+  ParseRule::new(None, Some(Act::Binary), Precedence::Factor),
+  // TOKEN_STAR
+  ParseRule::new(None, Some(Act::Binary), Precedence::Factor),
+  // TOKEN_PERCENT
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_PLUS_EQUAL
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_MINUS_EQUAL
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_STAR_EQUAL
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_SLASH_EQUAL
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_PERCENT_EQUAL
+  ParseRule::new(Some(Act::Increment), None, Precedence::None),
+  // TOKEN_PLUS_PLUS
+  ParseRule::new(Some(Act::Increment), None, Precedence::None),
+  // TOKEN_MINUS_MINUS
+  ParseRule::new(None, Some(Act::Binary), Precedence::Exponent),
+  // TOKEN_STAR_STAR
+  ParseRule::new(None, Some(Act::Binary), Precedence::Factor),
+  // TOKEN_TILDE_SLASH
+  ParseRule::new(None, Some(Act::Binary), Precedence::BitAnd),
+  // TOKEN_AMPERSAND
+  ParseRule::new(None, Some(Act::Binary), Precedence::BitOr),
+  // TOKEN_PIPE
+  ParseRule::new(None, Some(Act::Binary), Precedence::BitXor),
+  // TOKEN_CARET
+  ParseRule::new(Some(Act::Unary), None, Precedence::None),
+  // TOKEN_TILDE
+  ParseRule::new(None, Some(Act::Binary), Precedence::Shift),
+  // TOKEN_LESS_LESS
+  ParseRule::new(None, Some(Act::Binary), Precedence::Shift),
+  // TOKEN_GREATER_GREATER
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_ARROW
+  ParseRule::new(None, Some(Act::Conditional), Precedence::Conditional),
+  // TOKEN_QUESTION
+  ParseRule::new(None, Some(Act::OptionalDot), Precedence::Call),
+  // TOKEN_QUESTION_DOT
+  ParseRule::new(None, Some(Act::Coalesce), Precedence::Coalesce),
+  // TOKEN_QUESTION_QUESTION
+  ParseRule::new(Some(Act::Unary), None, Precedence::None),
+  // TOKEN_BANG
+  ParseRule::new(None, Some(Act::Binary), Precedence::Equality),
+  // TOKEN_BANG_EQUAL
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_EQUAL
+  ParseRule::new(None, Some(Act::Binary), Precedence::Equality),
+  // TOKEN_EQUAL_EQUAL
+  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
+  // TOKEN_GREATER
+  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
+  // TOKEN_GREATER_EQUAL
+  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
+  // TOKEN_LESS
+  ParseRule::new(None, Some(Act::Binary), Precedence::Comparison),
+  // TOKEN_LESS_EQUAL
+  ParseRule::new(Some(Act::Variable), None, Precedence::None),
+  // TOKEN_IDENTIFIER
+  ParseRule::new(Some(Act::String), None, Precedence::None),
+  // TOKEN_STRING
+  ParseRule::new(Some(Act::Interpolation), None, Precedence::None),
+  // TOKEN_INTERPOLATION
+  ParseRule::new(Some(Act::Number), None, Precedence::None),
+  // TOKEN_NUMBER
+  ParseRule::new(Some(Act::Number), None, Precedence::None),
+  // TOKEN_INTEGER
+  ParseRule::new(Some(Act::Map), None, Precedence::Call),
+  // TOKEN_MAP_OPEN
+  ParseRule::new(None, Some(Act::And), Precedence::And),
+  // TOKEN_AND
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_BREAK
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_CASE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_CATCH
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_CLASS
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_CONST
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_CONTINUE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_ELSE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_EXPORT
+  ParseRule::new(Some(Act::Literal), None, Precedence::None),
+  // TOKEN_FALSE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_FINALLY
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_FOR
+  ParseRule::new(Some(Act::Lambda), None, Precedence::None),
+  // TOKEN_FUN
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_IF
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_IMPORT
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_IN
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_MATCH
+  ParseRule::new(Some(Act::Literal), None, Precedence::None),
+  // TOKEN_NIL
+  ParseRule::new(None, Some(Act::Or), Precedence::Or),
+  // TOKEN_OR
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_PRINT
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_RETURN
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_STATIC
+  ParseRule::new(Some(Act::Super), None, Precedence::None),
+  // TOKEN_SUPER
+  ParseRule::new(Some(Act::This), None, Precedence::None),
+  // TOKEN_THIS
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_THROW
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_TRAIT
+  ParseRule::new(Some(Act::Literal), None, Precedence::None),
+  // TOKEN_TRUE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_TRY
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_VAR
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_WHILE
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_WITH
+  ParseRule::new(Some(Act::Yield), None, Precedence::None),
+  // TOKEN_YIELD
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_DOC_COMMENT
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_ERROR
+  ParseRule::new(None, None, Precedence::None),
+  // TOKEN_EOF
+];
+
+/// Get a rule from the rules table
+const fn get_rule(kind: TokenKind) -> &'static ParseRule {
+  &RULES_TABLE[kind as usize]
+}
+
+/// Is the token an assignment operator
+fn is_assignment_operator(kind: TokenKind) -> bool {
+  matches!(
+    kind,
+    TokenKind::Equal
+      | TokenKind::PlusEqual
+      | TokenKind::MinusEqual
+      | TokenKind::StarEqual
+      | TokenKind::SlashEqual
+      | TokenKind::PercentEqual
+  )
+}
+
+#[derive(Debug, Clone)]
+pub struct ClassCompiler {
+  enclosing: Option<Managed<ClassCompiler>>,
+  has_super_class: bool,
+  is_trait: bool,
+  name: Token,
+}
+
+impl Trace for ClassCompiler {
+  fn trace(&self) -> bool {
+    do_if_some(self.enclosing, |enclosing| {
+      enclosing.trace();
+    });
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    do_if_some(self.enclosing, |enclosing| {
+      enclosing.trace_debug(stdio);
+    });
+    true
+  }
+}
+
+impl Manage for ClassCompiler {
+  fn alloc_type(&self) -> &str {
+    "class compiler"
+  }
+
+  fn debug(&self) -> String {
+    format!("{:?}", self)
+  }
+
+  fn debug_free(&self) -> String {
+    format!(
+      "ClassCompiler: {{ has_super_class: {:?}, name: {:?}, enclosing: {{...}} }}",
+      self.has_super_class, self.name
+    )
+  }
+
+  fn size(&self) -> usize {
+    mem::size_of::<Self>()
+  }
+}
+
+/// The space lox parser. This struct is responsible for
+/// advancing the scanner and checking for specific conditions
+pub struct Parser<'a, S: StdIo> {
+  /// The current token
+  current: Token,
+
+  /// The previous token
+  previous: Token,
+
+  /// Has the parser encountered an error
+  had_error: bool,
+
+  /// Is the parser in panic mode
+  panic_mode: bool,
+
+  /// The doc comment written before the current token
+  doc: Option<String>,
+
+  /// The token after current and its doc comment if it has been peeked
+  next: Option<(Token, Option<String>)>,
+
+  /// Help reference to the backing scanner
+  scanner: Scanner<'a>,
+
+  /// The environments standard io access
+  stdio: S,
+}
+
+impl<'a, S: StdIo> Parser<'a, S> {
+  /// Create a new instance of the parser from a source str
+  pub fn new(stdio: S, source: &'a str) -> Self {
+    Self {
+      scanner: Scanner::new(source),
+      doc: None,
+      next: None,
+      stdio,
+      had_error: false,
+      panic_mode: false,
+      previous: Token {
+        lexeme: "error".to_string(),
+        line: 0,
+        kind: TokenKind::Error,
+      },
+      current: Token {
+        lexeme: "error".to_string(),
+        line: 0,
+        kind: TokenKind::Error,
+      },
+    }
+  }
+
+  /// Does the provided token kind match if so advance the
+  /// token index
+  pub fn match_kind(&mut self, kind: TokenKind) -> bool {
+    if !self.check(kind) {
+      return false;
+    }
+    self.advance();
+    true
+  }
+
+  /// Does the provided token kind match the current kind
+  pub fn check(&self, kind: TokenKind) -> bool {
+    self.current.kind == kind
+  }
+
+  /// Advance the parser a token forward
+  pub fn advance(&mut self) {
+    self.previous = self.current.clone();
+    loop {
+      let (current, doc) = match self.next.take() {
+        Some(next) => next,
+        None => scan_token(&mut self.scanner),
+      };
+      self.current = current;
+      self.doc = doc;
+      if self.current.kind != TokenKind::Error {
+        break;
+      }
+
+      self.error_at_current(&self.current.lexeme.to_string())
+    }
+  }
+
+  /// Peek the kind of the token following the current token
+  pub fn peek_kind(&mut self) -> TokenKind {
+    let scanner = &mut self.scanner;
+    self.next.get_or_insert_with(|| scan_token(scanner)).0.kind
+  }
+
+  /// Take the doc comment written before the current token
+  pub fn take_doc(&mut self) -> Option<String> {
+    self.doc.take()
+  }
+
+  /// Check if the tokens following an opening paren are the parameters
+  /// of an arrow function. The first two tokens decide it unless they
+  /// could begin either an assignment or a default parameter
+  fn is_arrow_parameters(&mut self) -> bool {
+    match self.current.kind {
+      TokenKind::RightParen => self.peek_kind() == TokenKind::Arrow,
+      TokenKind::Ellipsis => true,
+      TokenKind::Identifier => match self.peek_kind() {
+        TokenKind::Comma => true,
+        TokenKind::RightParen => {
+          let mut scanner = self.scanner.clone();
+          scan_kind(&mut scanner) == TokenKind::Arrow
+        }
+        TokenKind::Equal => self.after_group(1, |kind| kind == TokenKind::Arrow),
+        _ => false,
+      },
+      _ => false,
+    }
+  }
+
+  /// Scan ahead from the current token, `depth` groups deep, to the bracket
+  /// closing the outermost of them and check the kind of the token after it
+  /// with `predicate`. Brackets of every kind nest and reaching the end of
+  /// the source is never a match
+  fn after_group(&self, mut depth: usize, predicate: impl FnOnce(TokenKind) -> bool) -> bool {
+    let mut scanner = self.scanner.clone();
+    let mut pending = vec![self.current.kind];
+    if let Some((next, _)) = &self.next {
+      pending.push(next.kind);
+    }
+    pending.reverse();
+
+    let mut next_kind = || pending.pop().unwrap_or_else(|| scan_kind(&mut scanner));
+
+    loop {
+      match next_kind() {
+        TokenKind::LeftParen
+        | TokenKind::LeftBracket
+        | TokenKind::LeftBrace
+        | TokenKind::MapOpen => depth += 1,
+        TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace => {
+          depth = depth.saturating_sub(1);
+          if depth == 0 {
+            return predicate(next_kind());
+          }
+        }
+        TokenKind::Eof => return false,
+        _ => (),
+      }
+    }
+  }
+
+  /// Consume a token and advance the current token index
+  fn consume(&mut self, kind: TokenKind, message: &str) {
+    if self.current.kind == kind {
+      self.advance();
+      return;
+    }
+
+    self.error_at_current(message)
+  }
+
+  /// Indicate an error occurred at he current index
+  fn error_at_current(&mut self, message: &str) {
+    let token = self.current.clone();
+    self.error_at(token, message);
+  }
+
+  /// Indicate an error occurred at the previous index
+  pub fn error(&mut self, message: &str) {
+    let token = self.previous.clone();
+    self.error_at(token, message);
+  }
+
+  /// Print an error to the console for a user to address
+  fn error_at(&mut self, token: Token, message: &str) {
+    if self.panic_mode {
+      return;
+    }
+
+    self.panic_mode = true;
+    self.stdio.eprint(&format!("[line {}] Error", token.line));
+
+    match token.kind {
+      TokenKind::Eof => self.stdio.eprint(" at end"),
+      TokenKind::Error => (),
+      _ => self.stdio.eprint(&format!(" at {}", token.lexeme)),
+    }
+
+    self.stdio.eprintln(&format!(": {}", message));
+    self.had_error = true;
+  }
+}
+
+/// Scan the next token along with the doc comment written before it
+fn scan_token(scanner: &mut Scanner) -> (Token, Option<String>) {
+  let mut doc: Option<String> = None;
+
+  loop {
+    let token = scanner.scan_token();
+    if token.kind != TokenKind::DocComment {
+      return (token, doc);
+    }
+
+    // drop the slashes and the space usually following them
+    let line = token.lexeme["///".len()..].trim_end();
+    let line = line.strip_prefix(' ').unwrap_or(line);
+    match &mut doc {
+      Some(doc) => {
+        doc.push('\n');
+        doc.push_str(line);
+      }
+      None => doc = Some(line.to_string()),
+    }
+  }
+}
+
+/// Scan the kind of the next token that is not a doc comment
+fn scan_kind(scanner: &mut Scanner) -> TokenKind {
+  scan_token(scanner).0.kind
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum Precedence {
+  None,
+  Assignment,
+  Conditional,
+  Coalesce,
+  Or,
+  And,
+  Equality,
+  Comparison,
+  Range,
+  BitOr,
+  BitXor,
+  BitAnd,
+  Shift,
+  Term,
+  Factor,
+  Unary,
+  Exponent,
+  Call,
+  Primary,
+}
+
+impl Precedence {
+  pub fn higher(&self) -> Precedence {
+    match self {
+      Precedence::None => Precedence::Assignment,
+      Precedence::Assignment => Precedence::Conditional,
+      Precedence::Conditional => Precedence::Coalesce,
+      Precedence::Coalesce => Precedence::Or,
+      Precedence::Or => Precedence::And,
+      Precedence::And => Precedence::Equality,
+      Precedence::Equality => Precedence::Comparison,
+      Precedence::Comparison => Precedence::Range,
+      Precedence::Range => Precedence::BitOr,
+      Precedence::BitOr => Precedence::BitXor,
+      Precedence::BitXor => Precedence::BitAnd,
+      Precedence::BitAnd => Precedence::Shift,
+      Precedence::Shift => Precedence::Term,
+      Precedence::Term => Precedence::Factor,
+      Precedence::Factor => Precedence::Unary,
+      Precedence::Unary => Precedence::Exponent,
+      Precedence::Exponent => Precedence::Call,
+      Precedence::Call => Precedence::Primary,
+      Precedence::Primary => panic!("Primary is highest precedence"),
+    }
+  }
+}
+
+struct ParseRule {
+  prefix: Option<Act>,
+  infix: Option<Act>,
+  precedence: Precedence,
+}
+
+impl ParseRule {
+  const fn new(prefix: Option<Act>, infix: Option<Act>, precedence: Precedence) -> Self {
+    Self {
+      prefix,
+      infix,
+      precedence,
+    }
+  }
+}
+
+#[derive(Clone)]
+enum Act {
+  And,
+  Binary,
+  Call,
+  Coalesce,
+  Conditional,
+  Increment,
+  Index,
+  List,
+  Map,
+  Dot,
+  Grouping,
+  Literal,
+  Number,
+  Interpolation,
+  Lambda,
+  OptionalDot,
+  Or,
+  String,
+  Super,
+  This,
+  Unary,
+  Variable,
+  Yield,
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::debug::disassemble_chunk;
+  use spacelox_core::chunk::decode_u16;
+  use spacelox_core::memory::Gc;
+  use spacelox_core::{hooks::NoContext, io::{NativeIo, NativeStdIo}};
+
+  enum ByteCodeTest {
+    Code(AlignedByteCode),
+    Fun((u8, Vec<ByteCodeTest>)),
+  }
+
+  fn test_compile<'a>(src: String, gc: &mut Gc) -> Managed<Fun> {
+    let io = NativeIo::new();
+    let mut parser = Parser::new(io.stdio(), &src);
+
+    let mut context = NoContext::new(gc);
+    let hooks = &Hooks::new(&mut context);
+
+    let compiler = Compiler::new(io, &mut parser, hooks);
+    let result = compiler.compile();
+    assert_eq!(result.success, true);
+
+    result.fun
+  }
+
+  fn decode_byte_code(fun: Managed<Fun>) -> Vec<AlignedByteCode> {
+    let bytes = &fun.chunk().instructions;
+    let mut decoded = Vec::new();
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+      let (byte_code, new_offset) = AlignedByteCode::decode(bytes, offset);
+
+      match byte_code {
+        AlignedByteCode::Closure(closure) => {
+          decoded.push(byte_code);
+          offset = decode_byte_code_closure(fun, &mut decoded, new_offset, closure)
+        }
+        _ => {
+          decoded.push(byte_code);
+          offset = new_offset;
+        }
+      }
+    }
+
+    decoded
+  }
+
+  fn decode_byte_code_closure(
+    fun: Managed<Fun>,
+    decoded: &mut Vec<AlignedByteCode>,
+    offset: usize,
+    slot: u8,
+  ) -> usize {
+    let inner_fun = fun.chunk().constants[slot as usize].to_fun();
+    let mut current_offset = offset;
+
+    let instructions = &fun.chunk().instructions;
+    for _ in 0..inner_fun.upvalue_count {
+      let scalar = decode_u16(&instructions[offset..offset + 2]);
+
+      let upvalue_index: UpvalueIndex = unsafe { mem::transmute(scalar) };
+      decoded.push(AlignedByteCode::UpvalueIndex(upvalue_index));
+      current_offset += 2;
+    }
+
+    current_offset
+  }
+
+  fn assert_simple_bytecode(fun: Managed<Fun>, code: &[AlignedByteCode]) {
+    disassemble_chunk(&NativeStdIo::new(), fun.chunk(), "test");
+    let decoded_byte_code = decode_byte_code(fun);
+    assert_eq!(decoded_byte_code.len(), code.len());
+
+    decoded_byte_code
+      .iter()
+      .zip(code.iter())
+      .for_each(|(actual, expect)| assert_eq!(actual, expect));
+  }
+
+  fn assert_fun_bytecode(fun: Managed<Fun>, code: &[ByteCodeTest]) {
+    disassemble_chunk(&NativeStdIo::new(), fun.chunk(), &fun.name);
+    let decoded_byte_code = decode_byte_code(fun);
+    assert_eq!(decoded_byte_code.len(), code.len(), "for fun {}", fun.name);
+
+    for i in 0..code.len() {
+      match decoded_byte_code[i] {
+        AlignedByteCode::Closure(index) => {
+          let fun = fun.chunk().constants[index as usize].to_fun();
+
+          match &code[i] {
+            ByteCodeTest::Fun((expected, inner)) => {
+              assert_eq!(*expected, index);
+              assert_fun_bytecode(fun, inner);
+            }
+            _ => assert!(false),
+          }
+        }
+        _ => match &code[i] {
+          ByteCodeTest::Code(byte_code) => {
+            assert_eq!(&decoded_byte_code[i], byte_code);
+          }
+          _ => assert!(false),
+        },
+      }
+    }
+  }
+
+  #[test]
+  fn op_print() {
+    let example = "print 10;".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::Constant(0),
+        AlignedByteCode::Print,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn list_index_set() {
+    let example = "
+      var a = [clock, clock, clock];
+      a[1] = 5;
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,
+        AlignedByteCode::GetGlobal(1),
+        AlignedByteCode::GetGlobal(2),
+        AlignedByteCode::GetGlobal(3),
+        AlignedByteCode::ListInit(3),
+        AlignedByteCode::DefineGlobal(0),
+        AlignedByteCode::GetGlobal(4),
+        AlignedByteCode::Constant(5),
+        AlignedByteCode::Constant(6),
+        AlignedByteCode::SetIndex,
+        AlignedByteCode::Pop,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn list_index_get() {
+    let example = "
+      var a = [\"john\", \"joe\", \"jim\"];
+      print a[1];
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,
+        AlignedByteCode::Constant(1),
+        AlignedByteCode::Constant(2),
+        AlignedByteCode::Constant(3),
+        AlignedByteCode::ListInit(3),
+        AlignedByteCode::DefineGlobal(0),
+        AlignedByteCode::GetGlobal(4),
+        AlignedByteCode::Constant(5),
+        AlignedByteCode::GetIndex,
+        AlignedByteCode::Print,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn list_slice() {
+    let example = "
+      var a = [1, 2, 3];
+      print a[1:];
+      print a[::-1];
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,
+        AlignedByteCode::Constant(1),
+        AlignedByteCode::Constant(2),
+        AlignedByteCode::Constant(3),
+        AlignedByteCode::ListInit(3),
+        AlignedByteCode::DefineGlobal(0),
+        AlignedByteCode::GetGlobal(4),
+        AlignedByteCode::Constant(5),
+        AlignedByteCode::Nil,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Slice,
+        AlignedByteCode::Print,
+        AlignedByteCode::GetGlobal(6),
+        AlignedByteCode::Nil,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Constant(7),
+        AlignedByteCode::Negate,
+        AlignedByteCode::Slice,
+        AlignedByteCode::Print,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn list_initializer() {
+    let example = "
+      var a = [1, 2, nil, false, \"cat\"];
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,
+        AlignedByteCode::Constant(1),
+        AlignedByteCode::Constant(2),
+        AlignedByteCode::Nil,
+        AlignedByteCode::False,
+        AlignedByteCode::Constant(3),
+        AlignedByteCode::ListInit(5),
+        AlignedByteCode::DefineGlobal(0),
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn list_empty() {
+    let example = "
+      var a = [];
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,
+        AlignedByteCode::DefineGlobal(0),
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn open_upvalue() {
+    let example = "
+    fun example() {
+      var x = 0;
+      fun middle() {
+        fun inner() {
+          return x;
+        }
+
+        return inner();
+      }
+
+      return middle();
+    }
+    example();
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          // example
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
+            ByteCodeTest::Fun((
+              // middle
+              1,
+              vec![
+                ByteCodeTest::Fun((
+                  // inner
+                  0,
+                  vec![
+                    ByteCodeTest::Code(AlignedByteCode::GetUpvalue(0)),
+                    ByteCodeTest::Code(AlignedByteCode::Return),
+                    ByteCodeTest::Code(AlignedByteCode::Nil),
+                    ByteCodeTest::Code(AlignedByteCode::Return),
+                  ],
+                )),
+                ByteCodeTest::Code(AlignedByteCode::UpvalueIndex(UpvalueIndex::Upvalue(0))),
+                ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+                ByteCodeTest::Code(AlignedByteCode::Call(0)),
+                ByteCodeTest::Code(AlignedByteCode::Return),
+                ByteCodeTest::Code(AlignedByteCode::Nil),
+                ByteCodeTest::Code(AlignedByteCode::Return),
+              ],
+            )),
+            ByteCodeTest::Code(AlignedByteCode::UpvalueIndex(UpvalueIndex::Local(1))),
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(2)),
+            ByteCodeTest::Code(AlignedByteCode::Call(0)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
+        ByteCodeTest::Code(AlignedByteCode::Call(0)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn close_upvalue() {
+    let example = "
+    fun example() {
+      var a = 10;
+      fun inner() {
+        return a;
+      }
+      return inner;
+    }
+    var inner = example();
+    inner();
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
+            ByteCodeTest::Fun((
+              1,
+              vec![
+                ByteCodeTest::Code(AlignedByteCode::GetUpvalue(0)),
+                ByteCodeTest::Code(AlignedByteCode::Return),
+                ByteCodeTest::Code(AlignedByteCode::Nil),
+                ByteCodeTest::Code(AlignedByteCode::Return),
+              ],
+            )),
+            ByteCodeTest::Code(AlignedByteCode::UpvalueIndex(UpvalueIndex::Local(1))),
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(2)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(3)),
+        ByteCodeTest::Code(AlignedByteCode::Call(0)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(2)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(4)),
+        ByteCodeTest::Code(AlignedByteCode::Call(0)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn empty_fun() {
+    let example = "fun example() {} example();".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
+        ByteCodeTest::Code(AlignedByteCode::Call(0)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn param_fun() {
+    let example = "
+    fun example(a) {
+      return a;
+    }
+    var a = 1;
+    example(a);
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::Constant(3)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(2)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(4)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(5)),
+        ByteCodeTest::Code(AlignedByteCode::Call(1)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn lambda_fun() {
+    let example = "var example = fun (a) { return a; };".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn arrow_fun() {
+    let example = "var example = (a) => a;".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn default_and_rest_parameters() {
+    let example = "fun example(a, b = 2, ...c) { return b; }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
+            ByteCodeTest::Code(AlignedByteCode::List),
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(2)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
+  }
+
+  #[test]
+  fn empty_fun_basic() {
+    let example = "fun example() { var a = 10; return a; } example();".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+    assert_fun_bytecode(
+      fun,
+      &vec![
+        ByteCodeTest::Fun((
+          1,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
+        ByteCodeTest::Code(AlignedByteCode::Call(0)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
+      ],
+    );
   }
 
   #[test]
-  fn op_print() {
-    let example = "print 10;".to_string();
+  fn for_loop() {
+    let example = "for (var x = 0; x < 10; x = x + 1) { print(x); }".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
+
     assert_simple_bytecode(
       fun,
       &vec![
-        AlignedByteCode::Constant(0),
-        AlignedByteCode::Print,
-        AlignedByteCode::Nil,
-        AlignedByteCode::Return,
+        AlignedByteCode::Constant(0),     // 0
+        AlignedByteCode::GetLocal(1),     // 2
+        AlignedByteCode::Constant(1),     // 4
+        AlignedByteCode::Less,            // 6
+        AlignedByteCode::JumpIfFalse(21), // 7
+        AlignedByteCode::Pop,             // 10
+        AlignedByteCode::Jump(11),        // 11
+        AlignedByteCode::GetLocal(1),     // 14
+        AlignedByteCode::Constant(2),     // 16
+        AlignedByteCode::Add,             // 18
+        AlignedByteCode::SetLocal(1),     // 19
+        AlignedByteCode::Pop,             // 21
+        AlignedByteCode::Loop(23),        // 22
+        AlignedByteCode::GetLocal(1),     // 25
+        AlignedByteCode::Print,           // 27
+        AlignedByteCode::Loop(17),        // 28
+        AlignedByteCode::Pop,             // 31
+        AlignedByteCode::Pop,             // 32
+        AlignedByteCode::Nil,             // 33
+        AlignedByteCode::Return,          // 34
       ],
     );
   }
 
   #[test]
-  fn list_index_set() {
-    let example = "
-      var a = [clock, clock, clock];
-      a[1] = 5;
-    "
-    .to_string();
+  fn class_static_members() {
+    let example = "class Foo { static bar = 1; static baz() {} }".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
 
-    assert_simple_bytecode(
+    assert_fun_bytecode(
       fun,
       &vec![
-        AlignedByteCode::List,
-        AlignedByteCode::GetGlobal(1),
-        AlignedByteCode::GetGlobal(2),
-        AlignedByteCode::GetGlobal(3),
-        AlignedByteCode::ListInit(3),
-        AlignedByteCode::DefineGlobal(0),
-        AlignedByteCode::GetGlobal(4),
-        AlignedByteCode::Constant(5),
-        AlignedByteCode::Constant(6),
-        AlignedByteCode::SetIndex,
-        AlignedByteCode::Pop,
-        AlignedByteCode::Nil,
-        AlignedByteCode::Return,
+        ByteCodeTest::Code(AlignedByteCode::Class(0)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(1)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(3)),
+        ByteCodeTest::Code(AlignedByteCode::Constant(4)),
+        ByteCodeTest::Code(AlignedByteCode::SetProperty(2)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Fun((
+          6,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::StaticMethod(5)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
       ],
     );
   }
 
   #[test]
-  fn list_index_get() {
-    let example = "
-      var a = [\"john\", \"joe\", \"jim\"];
-      print a[1];
-    "
-    .to_string();
+  fn class_setter() {
+    let example = "class Foo { set bar(a) {} }".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
 
-    assert_simple_bytecode(
+    assert_fun_bytecode(
       fun,
       &vec![
-        AlignedByteCode::List,
-        AlignedByteCode::Constant(1),
-        AlignedByteCode::Constant(2),
-        AlignedByteCode::Constant(3),
-        AlignedByteCode::ListInit(3),
-        AlignedByteCode::DefineGlobal(0),
-        AlignedByteCode::GetGlobal(4),
-        AlignedByteCode::Constant(5),
-        AlignedByteCode::GetIndex,
-        AlignedByteCode::Print,
-        AlignedByteCode::Nil,
-        AlignedByteCode::Return,
+        ByteCodeTest::Code(AlignedByteCode::Class(0)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(1)),
+        ByteCodeTest::Fun((
+          3,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::Setter(2)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
       ],
     );
   }
 
   #[test]
-  fn list_initializer() {
-    let example = "
-      var a = [1, 2, nil, false, \"cat\"];
-    "
-    .to_string();
+  fn doc_comments() {
+    let example = "/// Foo.\nclass Foo {\n  /// Bar.\n  bar() {}\n}".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
 
-    assert_simple_bytecode(
+    assert_fun_bytecode(
       fun,
       &vec![
-        AlignedByteCode::List,
-        AlignedByteCode::Constant(1),
-        AlignedByteCode::Constant(2),
-        AlignedByteCode::Nil,
-        AlignedByteCode::False,
-        AlignedByteCode::Constant(3),
-        AlignedByteCode::ListInit(5),
-        AlignedByteCode::DefineGlobal(0),
-        AlignedByteCode::Nil,
-        AlignedByteCode::Return,
+        ByteCodeTest::Code(AlignedByteCode::Class(0)),
+        ByteCodeTest::Code(AlignedByteCode::Doc(1)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
+        ByteCodeTest::Fun((
+          4,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::Method(3)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
       ],
     );
+
+    let constants = &fun.chunk().constants;
+    assert_eq!(&*constants[1].to_str(), "Foo.");
+    let method = constants[4].to_fun();
+    assert_eq!(&*method.doc.unwrap(), "Bar.");
   }
 
   #[test]
-  fn list_empty() {
-    let example = "
-      var a = [];
-    "
-    .to_string();
+  fn trait_mixin() {
+    let example = "trait Foo { bar(); baz() {} } class Qux with Foo {}".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
 
-    assert_simple_bytecode(
+    assert_fun_bytecode(
       fun,
       &vec![
-        AlignedByteCode::List,
-        AlignedByteCode::DefineGlobal(0),
-        AlignedByteCode::Nil,
-        AlignedByteCode::Return,
+        ByteCodeTest::Code(AlignedByteCode::Trait(0)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(1)),
+        ByteCodeTest::Code(AlignedByteCode::Require(2)),
+        ByteCodeTest::Fun((
+          4,
+          vec![
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Return),
+          ],
+        )),
+        ByteCodeTest::Code(AlignedByteCode::Method(3)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Class(5)),
+        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(5)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(6)),
+        ByteCodeTest::Code(AlignedByteCode::GetGlobal(7)),
+        ByteCodeTest::Code(AlignedByteCode::Mixin(1)),
+        ByteCodeTest::Code(AlignedByteCode::Pop),
+        ByteCodeTest::Code(AlignedByteCode::Nil),
+        ByteCodeTest::Code(AlignedByteCode::Return),
       ],
     );
   }
 
   #[test]
-  fn open_upvalue() {
-    let example = "
-    fun example() {
-      var x = 0;
-      fun middle() {
-        fun inner() {
-          return x;
-        }
-
-        return inner();
-      }
-
-      return middle();
-    }
-    example();
-    "
-    .to_string();
+  fn generator() {
+    let example = "fun gen() { var x = yield; yield x; }".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
+
     assert_fun_bytecode(
       fun,
       &vec![
         ByteCodeTest::Fun((
-          // example
           1,
           vec![
-            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
-            ByteCodeTest::Fun((
-              // middle
-              1,
-              vec![
-                ByteCodeTest::Fun((
-                  // inner
-                  0,
-                  vec![
-                    ByteCodeTest::Code(AlignedByteCode::GetUpvalue(0)),
-                    ByteCodeTest::Code(AlignedByteCode::Return),
-                    ByteCodeTest::Code(AlignedByteCode::Nil),
-                    ByteCodeTest::Code(AlignedByteCode::Return),
-                  ],
-                )),
-                ByteCodeTest::Code(AlignedByteCode::UpvalueIndex(UpvalueIndex::Upvalue(0))),
-                ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
-                ByteCodeTest::Code(AlignedByteCode::Call(0)),
-                ByteCodeTest::Code(AlignedByteCode::Return),
-                ByteCodeTest::Code(AlignedByteCode::Nil),
-                ByteCodeTest::Code(AlignedByteCode::Return),
-              ],
-            )),
-            ByteCodeTest::Code(AlignedByteCode::UpvalueIndex(UpvalueIndex::Local(1))),
-            ByteCodeTest::Code(AlignedByteCode::GetLocal(2)),
-            ByteCodeTest::Code(AlignedByteCode::Call(0)),
-            ByteCodeTest::Code(AlignedByteCode::Return),
+            ByteCodeTest::Code(AlignedByteCode::Nil),
+            ByteCodeTest::Code(AlignedByteCode::Yield),
+            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
+            ByteCodeTest::Code(AlignedByteCode::Yield),
+            ByteCodeTest::Code(AlignedByteCode::Pop),
             ByteCodeTest::Code(AlignedByteCode::Nil),
             ByteCodeTest::Code(AlignedByteCode::Return),
           ],
         )),
         ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
-        ByteCodeTest::Code(AlignedByteCode::Call(0)),
-        ByteCodeTest::Code(AlignedByteCode::Pop),
         ByteCodeTest::Code(AlignedByteCode::Nil),
         ByteCodeTest::Code(AlignedByteCode::Return),
       ],
@@ -1901,154 +4785,225 @@ mod test {
   }
 
   #[test]
-  fn close_upvalue() {
-    let example = "
-    fun example() {
-      var a = 10;
-      fun inner() {
-        return a;
-      }
-      return inner;
-    }
-    var inner = example();
-    inner();
-    "
-    .to_string();
+  fn for_in_loop() {
+    let example = "for (x in [1, 2]) { print(x); }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,            // 0
+        AlignedByteCode::Constant(3),     // 1
+        AlignedByteCode::Constant(4),     // 3
+        AlignedByteCode::ListInit(2),     // 5
+        AlignedByteCode::Invoke((0, 0)),  // 8
+        AlignedByteCode::Nil,             // 11
+        AlignedByteCode::Nil,             // 12
+        AlignedByteCode::PushHandler(23), // 13
+        AlignedByteCode::GetLocal(1),     // 16
+        AlignedByteCode::Invoke((1, 0)),  // 18
+        AlignedByteCode::JumpIfFalse(13), // 21
+        AlignedByteCode::Pop,             // 24
+        AlignedByteCode::GetLocal(1),     // 25
+        AlignedByteCode::Invoke((2, 0)),  // 27
+        AlignedByteCode::GetLocal(4),     // 30
+        AlignedByteCode::Print,           // 32
+        AlignedByteCode::Pop,             // 33
+        AlignedByteCode::Loop(21),        // 34
+        AlignedByteCode::Pop,             // 37
+        AlignedByteCode::PopHandler,      // 38
+        AlignedByteCode::GetLocal(1),     // 39
+        AlignedByteCode::CloseIterator,   // 41
+        AlignedByteCode::Pop,             // 42
+        AlignedByteCode::GetLocal(3),     // 43
+        AlignedByteCode::True,            // 45
+        AlignedByteCode::Equal,           // 46
+        AlignedByteCode::JumpIfFalse(4),  // 47
+        AlignedByteCode::Pop,             // 50
+        AlignedByteCode::GetLocal(2),     // 51
+        AlignedByteCode::Throw,           // 53
+        AlignedByteCode::Pop,             // 54
+        AlignedByteCode::Pop,             // 55
+        AlignedByteCode::Pop,             // 56
+        AlignedByteCode::Pop,             // 57
+        AlignedByteCode::Nil,             // 58
+        AlignedByteCode::Return,          // 59
+      ],
+    );
+  }
+
+  #[test]
+  fn destructuring_local() {
+    let example = "{ var [a, ...b] = c; }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::Nil,
+        AlignedByteCode::Nil,
+        AlignedByteCode::GetGlobal(0),
+        AlignedByteCode::GetLocal(3),
+        AlignedByteCode::DestructureListRest(1),
+        AlignedByteCode::GetLocal(3),
+        AlignedByteCode::Constant(1),
+        AlignedByteCode::GetIndex,
+        AlignedByteCode::SetLocal(1),
+        AlignedByteCode::Pop,
+        AlignedByteCode::GetLocal(3),
+        AlignedByteCode::ListRest(1),
+        AlignedByteCode::SetLocal(2),
+        AlignedByteCode::Pop,
+        AlignedByteCode::Pop,
+        AlignedByteCode::Pop,
+        AlignedByteCode::Pop,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn const_local() {
+    let example = "{ const x = 1; print x; }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::Constant(0), // 0
+        AlignedByteCode::GetLocal(1), // 2
+        AlignedByteCode::Print,       // 4
+        AlignedByteCode::Pop,         // 5
+        AlignedByteCode::Nil,         // 6
+        AlignedByteCode::Return,      // 7
+      ],
+    );
+  }
+
+  #[test]
+  fn match_statement() {
+    let example = "match (1) { case [a] => print a; }".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
-    assert_fun_bytecode(
+
+    assert_simple_bytecode(
       fun,
       &vec![
-        ByteCodeTest::Fun((
-          1,
-          vec![
-            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
-            ByteCodeTest::Fun((
-              1,
-              vec![
-                ByteCodeTest::Code(AlignedByteCode::GetUpvalue(0)),
-                ByteCodeTest::Code(AlignedByteCode::Return),
-                ByteCodeTest::Code(AlignedByteCode::Nil),
-                ByteCodeTest::Code(AlignedByteCode::Return),
-              ],
-            )),
-            ByteCodeTest::Code(AlignedByteCode::UpvalueIndex(UpvalueIndex::Local(1))),
-            ByteCodeTest::Code(AlignedByteCode::GetLocal(2)),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-            ByteCodeTest::Code(AlignedByteCode::Nil),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-          ],
-        )),
-        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(3)),
-        ByteCodeTest::Code(AlignedByteCode::Call(0)),
-        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(2)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(4)),
-        ByteCodeTest::Code(AlignedByteCode::Call(0)),
-        ByteCodeTest::Code(AlignedByteCode::Pop),
-        ByteCodeTest::Code(AlignedByteCode::Nil),
-        ByteCodeTest::Code(AlignedByteCode::Return),
+        AlignedByteCode::Constant(0),     // 0
+        AlignedByteCode::GetLocal(1),     // 2
+        AlignedByteCode::MatchList(1),    // 4
+        AlignedByteCode::JumpIfFalse(13), // 6
+        AlignedByteCode::Pop,             // 9
+        AlignedByteCode::GetLocal(1),     // 10
+        AlignedByteCode::Constant(1),     // 12
+        AlignedByteCode::GetIndex,        // 14
+        AlignedByteCode::GetLocal(2),     // 15
+        AlignedByteCode::Print,           // 17
+        AlignedByteCode::Pop,             // 18
+        AlignedByteCode::Jump(1),         // 19
+        AlignedByteCode::Pop,             // 22
+        AlignedByteCode::Pop,             // 23
+        AlignedByteCode::Nil,             // 24
+        AlignedByteCode::Return,          // 25
       ],
     );
   }
 
   #[test]
-  fn empty_fun() {
-    let example = "fun example() {} example();".to_string();
+  fn module_import_export() {
+    let example =
+      "import \"a.lox\" as a; from \"b.lox\" import c, d; export var e = 1;".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
-    assert_fun_bytecode(
+
+    assert_simple_bytecode(
       fun,
       &vec![
-        ByteCodeTest::Fun((
-          1,
-          vec![
-            ByteCodeTest::Code(AlignedByteCode::Nil),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-          ],
-        )),
-        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
-        ByteCodeTest::Code(AlignedByteCode::Call(0)),
-        ByteCodeTest::Code(AlignedByteCode::Pop),
-        ByteCodeTest::Code(AlignedByteCode::Nil),
-        ByteCodeTest::Code(AlignedByteCode::Return),
+        AlignedByteCode::Import(0),       // 0
+        AlignedByteCode::DefineGlobal(1), // 2
+        AlignedByteCode::Import(2),       // 4
+        AlignedByteCode::GetProperty(4),  // 6
+        AlignedByteCode::DefineGlobal(3), // 8
+        AlignedByteCode::Import(2),       // 10
+        AlignedByteCode::GetProperty(6),  // 12
+        AlignedByteCode::DefineGlobal(5), // 14
+        AlignedByteCode::Constant(8),     // 16
+        AlignedByteCode::DefineGlobal(7), // 18
+        AlignedByteCode::Export(9),       // 20
+        AlignedByteCode::Nil,             // 22
+        AlignedByteCode::Return,          // 23
       ],
     );
   }
 
   #[test]
-  fn param_fun() {
-    let example = "
-    fun example(a) {
-      return a;
-    }
-    var a = 1;
-    example(a);
-    "
-    .to_string();
+  fn arithmetic_and_bitwise_precedence() {
+    let example = "print -2 ** 2 | ~1 << 3 % 2;".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
-    assert_fun_bytecode(
+
+    assert_simple_bytecode(
       fun,
       &vec![
-        ByteCodeTest::Fun((
-          1,
-          vec![
-            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-            ByteCodeTest::Code(AlignedByteCode::Nil),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-          ],
-        )),
-        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
-        ByteCodeTest::Code(AlignedByteCode::Constant(3)),
-        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(2)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(4)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(5)),
-        ByteCodeTest::Code(AlignedByteCode::Call(1)),
-        ByteCodeTest::Code(AlignedByteCode::Pop),
-        ByteCodeTest::Code(AlignedByteCode::Nil),
-        ByteCodeTest::Code(AlignedByteCode::Return),
+        AlignedByteCode::Constant(0), // 0
+        AlignedByteCode::Constant(1), // 2
+        AlignedByteCode::Power,       // 4
+        AlignedByteCode::Negate,      // 5
+        AlignedByteCode::Constant(2), // 6
+        AlignedByteCode::BitNot,      // 8
+        AlignedByteCode::Constant(3), // 9
+        AlignedByteCode::Constant(4), // 11
+        AlignedByteCode::Modulo,      // 13
+        AlignedByteCode::ShiftLeft,   // 14
+        AlignedByteCode::BitOr,       // 15
+        AlignedByteCode::Print,       // 16
+        AlignedByteCode::Nil,         // 17
+        AlignedByteCode::Return,      // 18
       ],
     );
   }
 
   #[test]
-  fn empty_fun_basic() {
-    let example = "fun example() { var a = 10; return a; } example();".to_string();
+  fn range_precedence() {
+    let example = "print 1 + 1..=2 | 4 == 0..3;".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
-    assert_fun_bytecode(
+
+    assert_simple_bytecode(
       fun,
       &vec![
-        ByteCodeTest::Fun((
-          1,
-          vec![
-            ByteCodeTest::Code(AlignedByteCode::Constant(0)),
-            ByteCodeTest::Code(AlignedByteCode::GetLocal(1)),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-            ByteCodeTest::Code(AlignedByteCode::Nil),
-            ByteCodeTest::Code(AlignedByteCode::Return),
-          ],
-        )),
-        ByteCodeTest::Code(AlignedByteCode::DefineGlobal(0)),
-        ByteCodeTest::Code(AlignedByteCode::GetGlobal(2)),
-        ByteCodeTest::Code(AlignedByteCode::Call(0)),
-        ByteCodeTest::Code(AlignedByteCode::Pop),
-        ByteCodeTest::Code(AlignedByteCode::Nil),
-        ByteCodeTest::Code(AlignedByteCode::Return),
+        AlignedByteCode::Constant(0),    // 0
+        AlignedByteCode::Constant(1),    // 2
+        AlignedByteCode::Add,            // 4
+        AlignedByteCode::Constant(2),    // 5
+        AlignedByteCode::Constant(3),    // 7
+        AlignedByteCode::BitOr,          // 9
+        AlignedByteCode::RangeInclusive, // 10
+        AlignedByteCode::Constant(4),    // 11
+        AlignedByteCode::Constant(5),    // 13
+        AlignedByteCode::Range,          // 15
+        AlignedByteCode::Equal,          // 16
+        AlignedByteCode::Print,          // 17
+        AlignedByteCode::Nil,            // 18
+        AlignedByteCode::Return,         // 19
       ],
     );
   }
 
   #[test]
-  fn for_loop() {
-    let example = "for (var x = 0; x < 10; x = x + 1) { print(x); }".to_string();
+  fn conditional_and_coalesce() {
+    let example = "print true ? nil ?? 1 : 2;".to_string();
 
     let mut gc = Gc::new(Box::new(NativeStdIo::new()));
     let fun = test_compile(example, &mut gc);
@@ -2056,26 +5011,101 @@ mod test {
     assert_simple_bytecode(
       fun,
       &vec![
-        AlignedByteCode::Constant(0),     // 0
-        AlignedByteCode::GetLocal(1),     // 2
-        AlignedByteCode::Constant(1),     // 4
-        AlignedByteCode::Less,            // 6
-        AlignedByteCode::JumpIfFalse(21), // 7
-        AlignedByteCode::Pop,             // 10
-        AlignedByteCode::Jump(11),        // 11
-        AlignedByteCode::GetLocal(1),     // 14
-        AlignedByteCode::Constant(2),     // 16
-        AlignedByteCode::Add,             // 18
-        AlignedByteCode::SetLocal(1),     // 19
-        AlignedByteCode::Pop,             // 21
-        AlignedByteCode::Loop(23),        // 22
-        AlignedByteCode::GetLocal(1),     // 25
-        AlignedByteCode::Print,           // 27
-        AlignedByteCode::Loop(17),        // 28
-        AlignedByteCode::Pop,             // 31
+        AlignedByteCode::True,            // 0
+        AlignedByteCode::JumpIfFalse(14), // 1
+        AlignedByteCode::Pop,             // 4
+        AlignedByteCode::Nil,             // 5
+        AlignedByteCode::JumpIfNil(3),    // 6
+        AlignedByteCode::Jump(3),         // 9
+        AlignedByteCode::Pop,             // 12
+        AlignedByteCode::Constant(0),     // 13
+        AlignedByteCode::Jump(3),         // 15
+        AlignedByteCode::Pop,             // 18
+        AlignedByteCode::Constant(1),     // 19
+        AlignedByteCode::Print,           // 21
+        AlignedByteCode::Nil,             // 22
+        AlignedByteCode::Return,          // 23
+      ],
+    );
+  }
+
+  #[test]
+  fn spread_arguments() {
+    let example = "
+      var a = [1];
+      print [0, ...a];
+      a.push(2, ...a, 3);
+    "
+    .to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,
+        AlignedByteCode::Constant(1),
+        AlignedByteCode::ListInit(1),
+        AlignedByteCode::DefineGlobal(0),
+        AlignedByteCode::List,
+        AlignedByteCode::Constant(2),
+        AlignedByteCode::ListInit(1),
+        AlignedByteCode::GetGlobal(3),
+        AlignedByteCode::ListSpread,
+        AlignedByteCode::Print,
+        AlignedByteCode::GetGlobal(4),
+        AlignedByteCode::Constant(6),
+        AlignedByteCode::List,
+        AlignedByteCode::Rotate(1),
+        AlignedByteCode::ListInit(1),
+        AlignedByteCode::GetGlobal(7),
+        AlignedByteCode::ArgumentSpread,
+        AlignedByteCode::Constant(8),
+        AlignedByteCode::ListInit(1),
+        AlignedByteCode::InvokeSpread(5),
+        AlignedByteCode::Pop,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn compound_assignment_and_increment() {
+    let example = "var a = [1]; a[0] += 2; a[0]++;".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::List,            // 0
+        AlignedByteCode::Constant(1),     // 1
+        AlignedByteCode::ListInit(1),     // 3
+        AlignedByteCode::DefineGlobal(0), // 6
+        AlignedByteCode::GetGlobal(2),    // 8
+        AlignedByteCode::Constant(3),     // 10
+        AlignedByteCode::DupTwo,          // 12
+        AlignedByteCode::GetIndex,        // 13
+        AlignedByteCode::Constant(4),     // 14
+        AlignedByteCode::Add,             // 16
+        AlignedByteCode::SetIndex,        // 17
+        AlignedByteCode::Pop,             // 18
+        AlignedByteCode::GetGlobal(5),    // 19
+        AlignedByteCode::Constant(6),     // 21
+        AlignedByteCode::DupTwo,          // 23
+        AlignedByteCode::GetIndex,        // 24
+        AlignedByteCode::Dup,             // 25
+        AlignedByteCode::Rotate(3),       // 26
+        AlignedByteCode::Constant(7),     // 28
+        AlignedByteCode::Add,             // 30
+        AlignedByteCode::SetIndex,        // 31
         AlignedByteCode::Pop,             // 32
-        AlignedByteCode::Nil,             // 33
-        AlignedByteCode::Return,          // 34
+        AlignedByteCode::Pop,             // 33
+        AlignedByteCode::Nil,             // 34
+        AlignedByteCode::Return,          // 35
       ],
     );
   }
@@ -2102,6 +5132,50 @@ mod test {
     );
   }
 
+  #[test]
+  fn break_loop() {
+    let example = "while (true) { break; }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::True,           // 0
+        AlignedByteCode::JumpIfFalse(7), // 1
+        AlignedByteCode::Pop,            // 4
+        AlignedByteCode::Jump(4),        // 5
+        AlignedByteCode::Loop(11),       // 8
+        AlignedByteCode::Pop,            // 11
+        AlignedByteCode::Nil,            // 12
+        AlignedByteCode::Return,         // 13
+      ],
+    );
+  }
+
+  #[test]
+  fn continue_loop() {
+    let example = "while (true) { continue; }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::True,           // 0
+        AlignedByteCode::JumpIfFalse(7), // 1
+        AlignedByteCode::Pop,            // 4
+        AlignedByteCode::Loop(8),        // 5
+        AlignedByteCode::Loop(11),       // 8
+        AlignedByteCode::Pop,            // 11
+        AlignedByteCode::Nil,            // 12
+        AlignedByteCode::Return,         // 13
+      ],
+    );
+  }
+
   #[test]
   fn or_operator() {
     let example = "print false or true;".to_string();
@@ -2176,6 +5250,89 @@ mod test {
     );
   }
 
+  #[test]
+  fn interpolation() {
+    let example = "\"a${b}c\";".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::Constant(1),
+        AlignedByteCode::GetGlobal(2),
+        AlignedByteCode::Invoke((0, 0)),
+        AlignedByteCode::Add,
+        AlignedByteCode::Constant(3),
+        AlignedByteCode::Add,
+        AlignedByteCode::Pop,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn throw() {
+    let example = "throw 10;".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::Constant(0),
+        AlignedByteCode::Throw,
+        AlignedByteCode::Nil,
+        AlignedByteCode::Return,
+      ],
+    );
+  }
+
+  #[test]
+  fn try_catch() {
+    let example = "try { throw 1; } catch (e) { print e; }".to_string();
+
+    let mut gc = Gc::new(Box::new(NativeStdIo::new()));
+    let fun = test_compile(example, &mut gc);
+
+    assert_simple_bytecode(
+      fun,
+      &vec![
+        AlignedByteCode::Nil,             // 0
+        AlignedByteCode::Nil,             // 1
+        AlignedByteCode::PushHandler(21), // 2
+        AlignedByteCode::PushHandler(7),  // 5
+        AlignedByteCode::Constant(0),     // 8
+        AlignedByteCode::Throw,           // 10
+        AlignedByteCode::PopHandler,      // 11
+        AlignedByteCode::Jump(10),        // 12
+        AlignedByteCode::GetLocal(1),     // 15
+        AlignedByteCode::GetLocal(3),     // 17
+        AlignedByteCode::Print,           // 19
+        AlignedByteCode::Pop,             // 20
+        AlignedByteCode::Nil,             // 21
+        AlignedByteCode::SetLocal(2),     // 22
+        AlignedByteCode::Pop,             // 24
+        AlignedByteCode::PopHandler,      // 25
+        AlignedByteCode::GetLocal(2),     // 26
+        AlignedByteCode::True,            // 28
+        AlignedByteCode::Equal,           // 29
+        AlignedByteCode::JumpIfFalse(4),  // 30
+        AlignedByteCode::Pop,             // 33
+        AlignedByteCode::GetLocal(1),     // 34
+        AlignedByteCode::Throw,           // 36
+        AlignedByteCode::Pop,             // 37
+        AlignedByteCode::Pop,             // 38
+        AlignedByteCode::Pop,             // 39
+        AlignedByteCode::Nil,             // 40
+        AlignedByteCode::Return,          // 41
+      ],
+    );
+  }
+
   #[test]
   fn declare_local() {
     let example = "{ var x = 10; }".to_string();
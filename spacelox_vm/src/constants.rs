@@ -1,2 +1,4 @@
-pub const FRAME_MAX: usize = std::u8::MAX as usize;
+pub const FRAME_MAX: usize = u8::MAX as usize;
 pub const DEFAULT_STACK_MAX: usize = FRAME_MAX * 32;
+pub const FIBER_STACK_MIN: usize = 64;
+pub const FIBER_FRAME_MIN: usize = 8;
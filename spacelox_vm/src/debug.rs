@@ -32,7 +32,7 @@ pub fn disassemble_instruction<S: StdIo>(
     stdio.print(&format!("{:>4} ", chunk.get_line(offset)))
   }
 
-  let (instruction, offset) = AlignedByteCode::decode(&chunk.instructions, offset as usize);
+  let (instruction, offset) = AlignedByteCode::decode(&chunk.instructions, offset);
   match instruction {
     AlignedByteCode::Return => simple_instruction(stdio, "Return", offset),
     AlignedByteCode::Print => simple_instruction(stdio, "Print", offset),
@@ -41,24 +41,48 @@ pub fn disassemble_instruction<S: StdIo>(
     AlignedByteCode::Subtract => simple_instruction(stdio, "Subtract", offset),
     AlignedByteCode::Multiply => simple_instruction(stdio, "Multiply", offset),
     AlignedByteCode::Divide => simple_instruction(stdio, "Divide", offset),
+    AlignedByteCode::Modulo => simple_instruction(stdio, "Modulo", offset),
+    AlignedByteCode::Power => simple_instruction(stdio, "Power", offset),
+    AlignedByteCode::IntegerDivide => simple_instruction(stdio, "IntegerDivide", offset),
+    AlignedByteCode::BitAnd => simple_instruction(stdio, "BitAnd", offset),
+    AlignedByteCode::BitOr => simple_instruction(stdio, "BitOr", offset),
+    AlignedByteCode::BitXor => simple_instruction(stdio, "BitXor", offset),
+    AlignedByteCode::ShiftLeft => simple_instruction(stdio, "ShiftLeft", offset),
+    AlignedByteCode::ShiftRight => simple_instruction(stdio, "ShiftRight", offset),
+    AlignedByteCode::BitNot => simple_instruction(stdio, "BitNot", offset),
     AlignedByteCode::Not => simple_instruction(stdio, "Not", offset),
     AlignedByteCode::Nil => simple_instruction(stdio, "Nil", offset),
     AlignedByteCode::True => simple_instruction(stdio, "True", offset),
     AlignedByteCode::False => simple_instruction(stdio, "False", offset),
     AlignedByteCode::List => simple_instruction(stdio, "List", offset),
     AlignedByteCode::ListInit(arg_count) => short_instruction(stdio, "ListInit", arg_count, offset),
+    AlignedByteCode::ListSpread => simple_instruction(stdio, "ListSpread", offset),
     AlignedByteCode::Map => simple_instruction(stdio, "Map", offset),
     AlignedByteCode::MapInit(arg_count) => short_instruction(stdio, "MapInit", arg_count, offset),
+    AlignedByteCode::MapSpread => simple_instruction(stdio, "MapSpread", offset),
     AlignedByteCode::GetIndex => simple_instruction(stdio, "GetIndex", offset),
     AlignedByteCode::SetIndex => simple_instruction(stdio, "SetIndex", offset),
+    AlignedByteCode::Slice => simple_instruction(stdio, "Slice", offset),
+    AlignedByteCode::Range => simple_instruction(stdio, "Range", offset),
+    AlignedByteCode::RangeInclusive => simple_instruction(stdio, "RangeInclusive", offset),
     AlignedByteCode::Pop => simple_instruction(stdio, "Pop", offset),
+    AlignedByteCode::Dup => simple_instruction(stdio, "Dup", offset),
+    AlignedByteCode::DupTwo => simple_instruction(stdio, "DupTwo", offset),
+    AlignedByteCode::Rotate(count) => byte_instruction(stdio, "Rotate", count, offset),
     AlignedByteCode::Call(arg_count) => byte_instruction(stdio, "Call", arg_count, offset),
+    AlignedByteCode::CallSpread => simple_instruction(stdio, "CallSpread", offset),
     AlignedByteCode::Invoke((constant, arg_count)) => {
       invoke_instruction(stdio, "Invoke", chunk, constant, arg_count, offset)
     }
+    AlignedByteCode::InvokeSpread(constant) => {
+      constant_instruction(stdio, "InvokeSpread", chunk, constant, offset)
+    }
     AlignedByteCode::SuperInvoke((constant, arg_count)) => {
       invoke_instruction(stdio, "SuperInvoke", chunk, constant, arg_count, offset)
     }
+    AlignedByteCode::SuperInvokeSpread(constant) => {
+      constant_instruction(stdio, "SuperInvokeSpread", chunk, constant, offset)
+    }
     AlignedByteCode::Class(constant) => {
       constant_instruction(stdio, "Class", chunk, constant, offset)
     }
@@ -66,6 +90,14 @@ pub fn disassemble_instruction<S: StdIo>(
       constant_instruction(stdio, "GetSuper", chunk, constant, offset)
     }
     AlignedByteCode::Inherit => simple_instruction(stdio, "Inherit", offset),
+    AlignedByteCode::Trait(constant) => {
+      constant_instruction(stdio, "Trait", chunk, constant, offset)
+    }
+    AlignedByteCode::Require(constant) => {
+      constant_instruction(stdio, "Require", chunk, constant, offset)
+    }
+    AlignedByteCode::Doc(constant) => constant_instruction(stdio, "Doc", chunk, constant, offset),
+    AlignedByteCode::Mixin(count) => byte_instruction(stdio, "Mixin", count, offset),
     AlignedByteCode::Closure(constant) => {
       closure_instruction(stdio, "Closure", chunk, constant, offset)
     }
@@ -91,12 +123,54 @@ pub fn disassemble_instruction<S: StdIo>(
     AlignedByteCode::SetUpvalue(slot) => byte_instruction(stdio, "SetUpvalue", slot, offset),
     AlignedByteCode::SetProperty(slot) => byte_instruction(stdio, "SetProperty", slot, offset),
     AlignedByteCode::GetProperty(slot) => byte_instruction(stdio, "GetProperty", slot, offset),
+    AlignedByteCode::JumpIfNil(jump) => jump_instruction(stdio, "JumpIfNil", 1, jump, offset),
     AlignedByteCode::Jump(jump) => jump_instruction(stdio, "Jump", 1, jump, offset),
     AlignedByteCode::JumpIfFalse(jump) => jump_instruction(stdio, "JumpIfFalse", 1, jump, offset),
     AlignedByteCode::Loop(jump) => jump_instruction(stdio, "Loop", -1, jump, offset),
     AlignedByteCode::Equal => simple_instruction(stdio, "Equal", offset),
     AlignedByteCode::Greater => simple_instruction(stdio, "Greater", offset),
     AlignedByteCode::Less => simple_instruction(stdio, "Less", offset),
+    AlignedByteCode::PushHandler(jump) => jump_instruction(stdio, "PushHandler", 1, jump, offset),
+    AlignedByteCode::PopHandler => simple_instruction(stdio, "PopHandler", offset),
+    AlignedByteCode::Throw => simple_instruction(stdio, "Throw", offset),
+    AlignedByteCode::StaticMethod(constant) => {
+      constant_instruction(stdio, "StaticMethod", chunk, constant, offset)
+    }
+    AlignedByteCode::Getter(constant) => {
+      constant_instruction(stdio, "Getter", chunk, constant, offset)
+    }
+    AlignedByteCode::Setter(constant) => {
+      constant_instruction(stdio, "Setter", chunk, constant, offset)
+    }
+    AlignedByteCode::Import(constant) => {
+      constant_instruction(stdio, "Import", chunk, constant, offset)
+    }
+    AlignedByteCode::Export(constant) => {
+      constant_instruction(stdio, "Export", chunk, constant, offset)
+    }
+    AlignedByteCode::MatchList(count) => byte_instruction(stdio, "MatchList", count, offset),
+    AlignedByteCode::MatchListRest(count) => {
+      byte_instruction(stdio, "MatchListRest", count, offset)
+    }
+    AlignedByteCode::MatchMap => simple_instruction(stdio, "MatchMap", offset),
+    AlignedByteCode::MatchKey(constant) => {
+      constant_instruction(stdio, "MatchKey", chunk, constant, offset)
+    }
+    AlignedByteCode::MatchClass => simple_instruction(stdio, "MatchClass", offset),
+    AlignedByteCode::MatchField(constant) => {
+      constant_instruction(stdio, "MatchField", chunk, constant, offset)
+    }
+    AlignedByteCode::ListRest(count) => byte_instruction(stdio, "ListRest", count, offset),
+    AlignedByteCode::DestructureList(count) => {
+      byte_instruction(stdio, "DestructureList", count, offset)
+    }
+    AlignedByteCode::DestructureListRest(count) => {
+      byte_instruction(stdio, "DestructureListRest", count, offset)
+    }
+    AlignedByteCode::DestructureMap => simple_instruction(stdio, "DestructureMap", offset),
+    AlignedByteCode::Yield => simple_instruction(stdio, "Yield", offset),
+    AlignedByteCode::CloseIterator => simple_instruction(stdio, "CloseIterator", offset),
+    AlignedByteCode::ArgumentSpread => simple_instruction(stdio, "ArgumentSpread", offset),
     AlignedByteCode::Constant(constant) => {
       constant_instruction(stdio, "Constant", chunk, constant, offset)
     }
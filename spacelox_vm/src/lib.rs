@@ -1,5 +1,13 @@
 #![deny(clippy::all)]
-pub mod call_frame;
+#![cfg_attr(
+  test,
+  allow(
+    clippy::assertions_on_constants,
+    clippy::bool_assert_comparison,
+    clippy::extra_unused_lifetimes,
+    clippy::useless_vec
+  )
+)]
 pub mod compiler;
 pub mod constants;
 pub mod debug;
@@ -3,6 +3,7 @@ use spacelox_core::utils::{next_boundary, previous_boundary};
 
 /// A scanner for the lox language. This struct is
 /// responsible for taking a source string and tokenizing it
+#[derive(Clone)]
 pub struct Scanner<'a> {
   /// The input source string
   source: &'a str,
@@ -18,10 +19,16 @@ pub struct Scanner<'a> {
 
   /// The start of the current character
   char_start: usize,
+
+  /// The brace depth of each string interpolation currently open
+  interpolations: Vec<usize>,
 }
 
 const STRING_ERROR: &str = "Unterminated string";
+const ESCAPE_ERROR: &str = "Invalid escape sequence";
+const UNICODE_ERROR: &str = "Invalid unicode escape sequence";
 const UNKNOWN_CHARACTER: &str = "Unexpected character";
+const COMMENT_ERROR: &str = "Unterminated block comment";
 const END_OF_FILE: &str = "";
 
 impl<'a> Scanner<'a> {
@@ -47,7 +54,7 @@ impl<'a> Scanner<'a> {
   /// assert_eq!(token.lexeme, "var");
   /// ```
   pub fn new(source: &'a str) -> Scanner<'a> {
-    let current = next_boundary(&source, 0);
+    let current = next_boundary(source, 0);
 
     Scanner {
       source,
@@ -55,6 +62,7 @@ impl<'a> Scanner<'a> {
       start: 0,
       current,
       char_start: 0,
+      interpolations: Vec::new(),
 
       line: 0,
     }
@@ -93,10 +101,12 @@ impl<'a> Scanner<'a> {
   /// ```
   pub fn scan_token(&mut self) -> Token {
     // advance whitespace
-    self.skip_white_space();
+    if let Err(message) = self.skip_white_space() {
+      return self.error_token(message);
+    }
 
     // find the previous unicode boundary
-    self.start = previous_boundary(&self.source, self.current);
+    self.start = previous_boundary(self.source, self.current);
     self.char_start = self.start;
 
     // if at end return oef token
@@ -112,12 +122,26 @@ impl<'a> Scanner<'a> {
     match &self.source[char_start..current] {
       "(" => self.make_token_source(TokenKind::LeftParen),
       ")" => self.make_token_source(TokenKind::RightParen),
-      "{" => self.make_token_source(TokenKind::LeftBrace),
-      "}" => self.make_token_source(TokenKind::RightBrace),
+      "{" => {
+        self.open_brace();
+        self.make_token_source(TokenKind::LeftBrace)
+      }
+      "}" => match self.interpolations.last_mut() {
+        Some(0) => {
+          self.interpolations.pop();
+          self.string()
+        }
+        Some(depth) => {
+          *depth -= 1;
+          self.make_token_source(TokenKind::RightBrace)
+        }
+        None => self.make_token_source(TokenKind::RightBrace),
+      },
       "[" => self.make_token_source(TokenKind::LeftBracket),
       "]" => self.make_token_source(TokenKind::RightBracket),
       ":" => {
         if self.match_token("{") {
+          self.open_brace();
           self.make_token_source(TokenKind::MapOpen)
         } else {
           self.make_token_source(TokenKind::Colon)
@@ -125,14 +149,86 @@ impl<'a> Scanner<'a> {
       }
       ";" => self.make_token_source(TokenKind::Semicolon),
       "," => self.make_token_source(TokenKind::Comma),
-      "." => self.make_token_source(TokenKind::Dot),
-      "-" => self.make_token_source(TokenKind::Minus),
-      "+" => self.make_token_source(TokenKind::Plus),
-      "/" => self.make_token_source(TokenKind::Slash),
-      "*" => self.make_token_source(TokenKind::Star),
+      "." => {
+        if self.match_token(".") {
+          if self.match_token(".") {
+            self.make_token_source(TokenKind::Ellipsis)
+          } else if self.match_token("=") {
+            self.make_token_source(TokenKind::DotDotEqual)
+          } else {
+            self.make_token_source(TokenKind::DotDot)
+          }
+        } else {
+          self.make_token_source(TokenKind::Dot)
+        }
+      }
+      "?" => {
+        if self.match_token("?") {
+          self.make_token_source(TokenKind::QuestionQuestion)
+        } else if self.match_token(".") {
+          self.make_token_source(TokenKind::QuestionDot)
+        } else {
+          self.make_token_source(TokenKind::Question)
+        }
+      }
+      "-" => {
+        if self.match_token("=") {
+          self.make_token_source(TokenKind::MinusEqual)
+        } else if self.match_token("-") {
+          self.make_token_source(TokenKind::MinusMinus)
+        } else {
+          self.make_token_source(TokenKind::Minus)
+        }
+      }
+      "+" => {
+        if self.match_token("=") {
+          self.make_token_source(TokenKind::PlusEqual)
+        } else if self.match_token("+") {
+          self.make_token_source(TokenKind::PlusPlus)
+        } else {
+          self.make_token_source(TokenKind::Plus)
+        }
+      }
+      "/" => {
+        if self.match_token("/") {
+          self.doc_comment()
+        } else if self.match_token("=") {
+          self.make_token_source(TokenKind::SlashEqual)
+        } else {
+          self.make_token_source(TokenKind::Slash)
+        }
+      }
+      "*" => {
+        if self.match_token("*") {
+          self.make_token_source(TokenKind::StarStar)
+        } else if self.match_token("=") {
+          self.make_token_source(TokenKind::StarEqual)
+        } else {
+          self.make_token_source(TokenKind::Star)
+        }
+      }
+      "~" => {
+        if self.match_token("/") {
+          self.make_token_source(TokenKind::TildeSlash)
+        } else {
+          self.make_token_source(TokenKind::Tilde)
+        }
+      }
+      "&" => self.make_token_source(TokenKind::Ampersand),
+      "|" => self.make_token_source(TokenKind::Pipe),
+      "^" => self.make_token_source(TokenKind::Caret),
+      "%" => {
+        if self.match_token("=") {
+          self.make_token_source(TokenKind::PercentEqual)
+        } else {
+          self.make_token_source(TokenKind::Percent)
+        }
+      }
       "=" => {
         if self.match_token("=") {
           self.make_token_source(TokenKind::EqualEqual)
+        } else if self.match_token(">") {
+          self.make_token_source(TokenKind::Arrow)
         } else {
           self.make_token_source(TokenKind::Equal)
         }
@@ -140,6 +236,8 @@ impl<'a> Scanner<'a> {
       "<" => {
         if self.match_token("=") {
           self.make_token_source(TokenKind::LessEqual)
+        } else if self.match_token("<") {
+          self.make_token_source(TokenKind::LessLess)
         } else {
           self.make_token_source(TokenKind::Less)
         }
@@ -147,6 +245,8 @@ impl<'a> Scanner<'a> {
       ">" => {
         if self.match_token("=") {
           self.make_token_source(TokenKind::GreaterEqual)
+        } else if self.match_token(">") {
+          self.make_token_source(TokenKind::GreaterGreater)
         } else {
           self.make_token_source(TokenKind::Greater)
         }
@@ -188,7 +288,7 @@ impl<'a> Scanner<'a> {
     self.make_token_source(self.identifier_type())
   }
 
-  /// Generate a number token
+  /// Generate a number token, or an integer token if there is no fractional part
   fn number(&mut self) -> Token {
     // advance consecutive digits
     while !self.is_at_end() && is_digit(self.peek()) {
@@ -203,32 +303,120 @@ impl<'a> Scanner<'a> {
           while !self.is_at_end() && is_digit(self.peek()) {
             self.advance_indices();
           }
+
+          return self.make_token_source(TokenKind::Number);
         }
       }
     }
 
-    self.make_token_source(TokenKind::Number)
+    self.make_token_source(TokenKind::Integer)
   }
 
-  /// Generate a string token
+  /// Generate a string token. If an interpolation is found an interpolation
+  /// token is generated instead and the string is resumed after its closing brace
   fn string(&mut self) -> Token {
+    let mut value = String::new();
+    let mut error: Option<&'static str> = None;
+
     while !self.is_at_end() && self.peek() != "\"" {
-      if self.peek() == "\n" {
-        self.line += 1;
+      match self.peek() {
+        "\\" => {
+          self.advance_indices();
+          if self.is_at_end() {
+            break;
+          }
+
+          match self.escape() {
+            Ok(c) => value.push(c),
+            Err(message) => error = error.or(Some(message)),
+          }
+        }
+        "$" if error.is_none() && self.peek_next() == Some("{") => {
+          self.advance_indices();
+          self.advance_indices();
+          self.interpolations.push(0);
+
+          return make_token(
+            TokenKind::Interpolation,
+            &format!("\"{}\"", value),
+            self.line,
+          );
+        }
+        _ => {
+          if self.peek() == "\n" {
+            self.line += 1;
+          }
+
+          value.push_str(self.peek());
+          self.advance_indices();
+        }
       }
-      self.advance_indices();
     }
 
     if self.is_at_end() {
-      return self.error_token(&STRING_ERROR);
+      return self.error_token(STRING_ERROR);
     }
 
     self.advance_indices();
-    self.make_token_source(TokenKind::String)
+    match error {
+      Some(message) => self.error_token(message),
+      None => make_token(TokenKind::String, &format!("\"{}\"", value), self.line),
+    }
+  }
+
+  /// Decode the escape sequence following a backslash
+  fn escape(&mut self) -> Result<char, &'static str> {
+    let escaped = match self.peek() {
+      "n" => '\n',
+      "t" => '\t',
+      "r" => '\r',
+      "0" => '\0',
+      "\"" => '"',
+      "\\" => '\\',
+      "$" => '$',
+      "u" => return self.unicode_escape(),
+      _ => {
+        self.advance_indices();
+        return Err(ESCAPE_ERROR);
+      }
+    };
+
+    self.advance_indices();
+    Ok(escaped)
+  }
+
+  /// Decode a unicode escape of the form `\u{1F600}`
+  fn unicode_escape(&mut self) -> Result<char, &'static str> {
+    self.advance_indices();
+    if !self.match_token("{") {
+      return Err(UNICODE_ERROR);
+    }
+
+    let mut digits = String::new();
+    while !self.is_at_end() && self.peek() != "}" && self.peek() != "\"" {
+      digits.push_str(self.peek());
+      self.advance_indices();
+    }
+
+    if !self.match_token("}") || digits.is_empty() || digits.len() > 6 {
+      return Err(UNICODE_ERROR);
+    }
+
+    u32::from_str_radix(&digits, 16)
+      .ok()
+      .and_then(std::char::from_u32)
+      .ok_or(UNICODE_ERROR)
+  }
+
+  /// Track an opening brace inside of an interpolation
+  fn open_brace(&mut self) {
+    if let Some(depth) = self.interpolations.last_mut() {
+      *depth += 1;
+    }
   }
 
   /// Advance through whitespace effectively throwing it away
-  fn skip_white_space(&mut self) {
+  fn skip_white_space(&mut self) -> Result<(), &'static str> {
     while !self.is_at_end() {
       let c = self.peek();
 
@@ -241,20 +429,74 @@ impl<'a> Scanner<'a> {
           self.advance_indices();
         }
         "/" => match self.peek_next() {
-          Some(next) => {
-            if next == "/" {
-              while !self.is_at_end() && self.peek() != "\n" {
-                self.advance_indices();
-              }
-            } else {
-              return;
+          Some("/") => {
+            // doc comments are scanned as tokens
+            if self.is_doc_comment() {
+              return Ok(());
+            }
+
+            while !self.is_at_end() && self.peek() != "\n" {
+              self.advance_indices();
             }
           }
-          None => return,
+          Some("*") => self.block_comment()?,
+          _ => return Ok(()),
         },
-        _ => return,
+        _ => return Ok(()),
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Is the scanner at a doc comment, three slashes not followed by a fourth
+  fn is_doc_comment(&self) -> bool {
+    self.nth_char_from(self.char_start, 2) == Some("/")
+      && self.nth_char_from(self.char_start, 3) != Some("/")
+  }
+
+  /// Skip a block comment counting lines. Block comments nest so
+  /// commenting out code that contains one is safe
+  fn block_comment(&mut self) -> Result<(), &'static str> {
+    // skip the opening "/*"
+    self.advance_indices();
+    self.advance_indices();
+    let line = self.line;
+    let mut depth: usize = 1;
+
+    while depth > 0 {
+      if self.is_at_end() {
+        // report the line the comment was opened on
+        self.line = line;
+        return Err(COMMENT_ERROR);
+      }
+
+      match (self.peek(), self.peek_next()) {
+        ("/", Some("*")) => {
+          depth += 1;
+          self.advance_indices();
+        }
+        ("*", Some("/")) => {
+          depth -= 1;
+          self.advance_indices();
+        }
+        ("\n", _) => self.line += 1,
+        _ => (),
       }
+
+      self.advance_indices();
+    }
+
+    Ok(())
+  }
+
+  /// Generate a doc comment token running to the end of the line
+  fn doc_comment(&mut self) -> Token {
+    while !self.is_at_end() && self.peek() != "\n" {
+      self.advance_indices();
     }
+
+    self.make_token_source(TokenKind::DocComment)
   }
 
   /// Identify if the current slice is a keyword.
@@ -263,33 +505,103 @@ impl<'a> Scanner<'a> {
     match self.nth_char_from(self.start, 0) {
       Some(c1) => match c1 {
         "a" => self.check_keyword(1, "nd", TokenKind::And),
-        "c" => self.check_keyword(1, "lass", TokenKind::Class),
-        "e" => self.check_keyword(1, "lse", TokenKind::Else),
+        "b" => self.check_keyword(1, "reak", TokenKind::Break),
+        "c" => match self.nth_char_from(self.start, 1) {
+          Some(c2) => match c2 {
+            "a" => match self.nth_char_from(self.start, 2) {
+              Some(c3) => match c3 {
+                "s" => self.check_keyword(3, "e", TokenKind::Case),
+                "t" => self.check_keyword(3, "ch", TokenKind::Catch),
+                _ => TokenKind::Identifier,
+              },
+              None => TokenKind::Identifier,
+            },
+            "l" => self.check_keyword(2, "ass", TokenKind::Class),
+            "o" => match self.nth_char_from(self.start, 3) {
+              Some(c4) => match c4 {
+                "s" => self.check_keyword(2, "nst", TokenKind::Const),
+                "t" => self.check_keyword(2, "ntinue", TokenKind::Continue),
+                _ => TokenKind::Identifier,
+              },
+              None => TokenKind::Identifier,
+            },
+            _ => TokenKind::Identifier,
+          },
+          None => TokenKind::Identifier,
+        },
+        "e" => match self.nth_char_from(self.start, 1) {
+          Some(c2) => match c2 {
+            "l" => self.check_keyword(2, "se", TokenKind::Else),
+            "x" => self.check_keyword(2, "port", TokenKind::Export),
+            _ => TokenKind::Identifier,
+          },
+          None => TokenKind::Identifier,
+        },
         "f" => match self.nth_char_from(self.start, 1) {
           Some(c2) => match c2 {
             "a" => self.check_keyword(2, "lse", TokenKind::False),
+            "i" => self.check_keyword(2, "nally", TokenKind::Finally),
             "o" => self.check_keyword(2, "r", TokenKind::For),
             "u" => self.check_keyword(2, "n", TokenKind::Fun),
             _ => TokenKind::Identifier,
           },
           None => TokenKind::Identifier,
         },
-        "i" => self.check_keyword(1, "f", TokenKind::If),
+        "i" => match self.nth_char_from(self.start, 1) {
+          Some(c2) => match c2 {
+            "f" => self.check_keyword(2, "", TokenKind::If),
+            "m" => self.check_keyword(2, "port", TokenKind::Import),
+            "n" => self.check_keyword(2, "", TokenKind::In),
+            _ => TokenKind::Identifier,
+          },
+          None => TokenKind::Identifier,
+        },
+        "m" => self.check_keyword(1, "atch", TokenKind::Match),
         "n" => self.check_keyword(1, "il", TokenKind::Nil),
         "o" => self.check_keyword(1, "r", TokenKind::Or),
         "p" => self.check_keyword(1, "rint", TokenKind::Print),
         "r" => self.check_keyword(1, "eturn", TokenKind::Return),
-        "s" => self.check_keyword(1, "uper", TokenKind::Super),
+        "s" => match self.nth_char_from(self.start, 1) {
+          Some(c2) => match c2 {
+            "t" => self.check_keyword(2, "atic", TokenKind::Static),
+            "u" => self.check_keyword(2, "per", TokenKind::Super),
+            _ => TokenKind::Identifier,
+          },
+          None => TokenKind::Identifier,
+        },
         "t" => match self.nth_char_from(self.start, 1) {
           Some(c2) => match c2 {
-            "h" => self.check_keyword(2, "is", TokenKind::This),
-            "r" => self.check_keyword(2, "ue", TokenKind::True),
+            "h" => match self.nth_char_from(self.start, 2) {
+              Some(c3) => match c3 {
+                "i" => self.check_keyword(3, "s", TokenKind::This),
+                "r" => self.check_keyword(3, "ow", TokenKind::Throw),
+                _ => TokenKind::Identifier,
+              },
+              None => TokenKind::Identifier,
+            },
+            "r" => match self.nth_char_from(self.start, 2) {
+              Some(c3) => match c3 {
+                "a" => self.check_keyword(3, "it", TokenKind::Trait),
+                "u" => self.check_keyword(3, "e", TokenKind::True),
+                "y" => self.check_keyword(3, "", TokenKind::Try),
+                _ => TokenKind::Identifier,
+              },
+              None => TokenKind::Identifier,
+            },
             _ => TokenKind::Identifier,
           },
           None => TokenKind::Identifier,
         },
         "v" => self.check_keyword(1, "ar", TokenKind::Var),
-        "w" => self.check_keyword(1, "hile", TokenKind::While),
+        "w" => match self.nth_char_from(self.start, 1) {
+          Some(c2) => match c2 {
+            "h" => self.check_keyword(2, "ile", TokenKind::While),
+            "i" => self.check_keyword(2, "th", TokenKind::With),
+            _ => TokenKind::Identifier,
+          },
+          None => TokenKind::Identifier,
+        },
+        "y" => self.check_keyword(1, "ield", TokenKind::Yield),
         _ => TokenKind::Identifier,
       },
       None => panic!(""),
@@ -322,7 +634,7 @@ impl<'a> Scanner<'a> {
   /// Peek the next token
   fn peek_next(&self) -> Option<&str> {
     let start = self.current;
-    let end = next_boundary(&self.source, self.current);
+    let end = next_boundary(self.source, self.current);
 
     if self.char_index_at_end(end) {
       return None;
@@ -338,12 +650,12 @@ impl<'a> Scanner<'a> {
 
   /// Find the nth char from the current index
   fn nth_char_from(&self, start: usize, n: u8) -> Option<&str> {
-    let mut current_index = next_boundary(&self.source, start);
+    let mut current_index = next_boundary(self.source, start);
     let mut start_index = start;
 
     for _ in 0..n {
       start_index = current_index;
-      current_index = next_boundary(&self.source, current_index);
+      current_index = next_boundary(self.source, current_index);
     }
 
     if self.char_index_at_end(current_index) {
@@ -361,14 +673,14 @@ impl<'a> Scanner<'a> {
   /// Advance the housekeeping indices
   fn advance_indices(&mut self) {
     self.char_start = self.current;
-    self.current = next_boundary(&self.source, self.current);
+    self.current = next_boundary(self.source, self.current);
   }
 
   /// Find the nth next char boundary
   fn nth_next_boundary(&self, start: usize, n: usize) -> usize {
     let mut current = start;
     for _ in 0..n {
-      current = next_boundary(&self.source, current);
+      current = next_boundary(self.source, current);
     }
 
     current
@@ -418,12 +730,12 @@ fn make_token(kind: TokenKind, raw: &str, line: u32) -> Token {
 
 /// Is the str slice a digit. Assumes single char
 fn is_digit(c: &str) -> bool {
-  c >= "0" && c <= "9"
+  ("0"..="9").contains(&c)
 }
 
 /// Is the str slice a alphabetic. Assumes single char
 fn is_alpha(c: &str) -> bool {
-  (c >= "a" && c <= "z") || (c >= "A" && c <= "Z") || c == "_"
+  ("a"..="z").contains(&c) || ("A"..="Z").contains(&c) || c == "_"
 }
 
 #[cfg(test)]
@@ -464,6 +776,22 @@ mod test {
       TokenKind::Dot,
       TokenGen::Symbol(Box::new(|| ".".to_string())),
     );
+    map.insert(
+      TokenKind::DocComment,
+      TokenGen::Symbol(Box::new(|| "/// doc".to_string())),
+    );
+    map.insert(
+      TokenKind::DotDot,
+      TokenGen::Symbol(Box::new(|| "..".to_string())),
+    );
+    map.insert(
+      TokenKind::DotDotEqual,
+      TokenGen::Symbol(Box::new(|| "..=".to_string())),
+    );
+    map.insert(
+      TokenKind::Ellipsis,
+      TokenGen::Symbol(Box::new(|| "...".to_string())),
+    );
     map.insert(
       TokenKind::Minus,
       TokenGen::Symbol(Box::new(|| "-".to_string())),
@@ -484,6 +812,86 @@ mod test {
       TokenKind::Star,
       TokenGen::Symbol(Box::new(|| "*".to_string())),
     );
+    map.insert(
+      TokenKind::Percent,
+      TokenGen::Symbol(Box::new(|| "%".to_string())),
+    );
+    map.insert(
+      TokenKind::PlusEqual,
+      TokenGen::Symbol(Box::new(|| "+=".to_string())),
+    );
+    map.insert(
+      TokenKind::MinusEqual,
+      TokenGen::Symbol(Box::new(|| "-=".to_string())),
+    );
+    map.insert(
+      TokenKind::StarEqual,
+      TokenGen::Symbol(Box::new(|| "*=".to_string())),
+    );
+    map.insert(
+      TokenKind::SlashEqual,
+      TokenGen::Symbol(Box::new(|| "/=".to_string())),
+    );
+    map.insert(
+      TokenKind::PercentEqual,
+      TokenGen::Symbol(Box::new(|| "%=".to_string())),
+    );
+    map.insert(
+      TokenKind::PlusPlus,
+      TokenGen::Symbol(Box::new(|| "++".to_string())),
+    );
+    map.insert(
+      TokenKind::MinusMinus,
+      TokenGen::Symbol(Box::new(|| "--".to_string())),
+    );
+    map.insert(
+      TokenKind::StarStar,
+      TokenGen::Symbol(Box::new(|| "**".to_string())),
+    );
+    map.insert(
+      TokenKind::TildeSlash,
+      TokenGen::Symbol(Box::new(|| "~/".to_string())),
+    );
+    map.insert(
+      TokenKind::Ampersand,
+      TokenGen::Symbol(Box::new(|| "&".to_string())),
+    );
+    map.insert(
+      TokenKind::Pipe,
+      TokenGen::Symbol(Box::new(|| "|".to_string())),
+    );
+    map.insert(
+      TokenKind::Caret,
+      TokenGen::Symbol(Box::new(|| "^".to_string())),
+    );
+    map.insert(
+      TokenKind::Tilde,
+      TokenGen::Symbol(Box::new(|| "~".to_string())),
+    );
+    map.insert(
+      TokenKind::LessLess,
+      TokenGen::Symbol(Box::new(|| "<<".to_string())),
+    );
+    map.insert(
+      TokenKind::GreaterGreater,
+      TokenGen::Symbol(Box::new(|| ">>".to_string())),
+    );
+    map.insert(
+      TokenKind::Arrow,
+      TokenGen::Symbol(Box::new(|| "=>".to_string())),
+    );
+    map.insert(
+      TokenKind::Question,
+      TokenGen::Symbol(Box::new(|| "?".to_string())),
+    );
+    map.insert(
+      TokenKind::QuestionDot,
+      TokenGen::Symbol(Box::new(|| "?.".to_string())),
+    );
+    map.insert(
+      TokenKind::QuestionQuestion,
+      TokenGen::Symbol(Box::new(|| "??".to_string())),
+    );
     map.insert(
       TokenKind::Bang,
       TokenGen::Comparator(Box::new(|| "!".to_string())),
@@ -526,24 +934,56 @@ mod test {
     );
     map.insert(
       TokenKind::Number,
+      TokenGen::ALpha(Box::new(|| "123.45".to_string())),
+    );
+    map.insert(
+      TokenKind::Integer,
       TokenGen::ALpha(Box::new(|| "12345".to_string())),
     );
     map.insert(
       TokenKind::And,
       TokenGen::ALpha(Box::new(|| "and".to_string())),
     );
+    map.insert(
+      TokenKind::Break,
+      TokenGen::ALpha(Box::new(|| "break".to_string())),
+    );
+    map.insert(
+      TokenKind::Case,
+      TokenGen::ALpha(Box::new(|| "case".to_string())),
+    );
+    map.insert(
+      TokenKind::Catch,
+      TokenGen::ALpha(Box::new(|| "catch".to_string())),
+    );
     map.insert(
       TokenKind::Class,
       TokenGen::ALpha(Box::new(|| "class".to_string())),
     );
+    map.insert(
+      TokenKind::Const,
+      TokenGen::ALpha(Box::new(|| "const".to_string())),
+    );
+    map.insert(
+      TokenKind::Continue,
+      TokenGen::ALpha(Box::new(|| "continue".to_string())),
+    );
     map.insert(
       TokenKind::Else,
       TokenGen::ALpha(Box::new(|| "else".to_string())),
     );
+    map.insert(
+      TokenKind::Export,
+      TokenGen::ALpha(Box::new(|| "export".to_string())),
+    );
     map.insert(
       TokenKind::False,
       TokenGen::ALpha(Box::new(|| "false".to_string())),
     );
+    map.insert(
+      TokenKind::Finally,
+      TokenGen::ALpha(Box::new(|| "finally".to_string())),
+    );
     map.insert(
       TokenKind::For,
       TokenGen::ALpha(Box::new(|| "for".to_string())),
@@ -556,6 +996,18 @@ mod test {
       TokenKind::If,
       TokenGen::ALpha(Box::new(|| "if".to_string())),
     );
+    map.insert(
+      TokenKind::Import,
+      TokenGen::ALpha(Box::new(|| "import".to_string())),
+    );
+    map.insert(
+      TokenKind::In,
+      TokenGen::ALpha(Box::new(|| "in".to_string())),
+    );
+    map.insert(
+      TokenKind::Match,
+      TokenGen::ALpha(Box::new(|| "match".to_string())),
+    );
     map.insert(
       TokenKind::Nil,
       TokenGen::ALpha(Box::new(|| "nil".to_string())),
@@ -572,6 +1024,10 @@ mod test {
       TokenKind::Return,
       TokenGen::ALpha(Box::new(|| "return".to_string())),
     );
+    map.insert(
+      TokenKind::Static,
+      TokenGen::ALpha(Box::new(|| "static".to_string())),
+    );
     map.insert(
       TokenKind::Super,
       TokenGen::ALpha(Box::new(|| "super".to_string())),
@@ -580,10 +1036,22 @@ mod test {
       TokenKind::This,
       TokenGen::ALpha(Box::new(|| "this".to_string())),
     );
+    map.insert(
+      TokenKind::Throw,
+      TokenGen::ALpha(Box::new(|| "throw".to_string())),
+    );
+    map.insert(
+      TokenKind::Trait,
+      TokenGen::ALpha(Box::new(|| "trait".to_string())),
+    );
     map.insert(
       TokenKind::True,
       TokenGen::ALpha(Box::new(|| "true".to_string())),
     );
+    map.insert(
+      TokenKind::Try,
+      TokenGen::ALpha(Box::new(|| "try".to_string())),
+    );
     map.insert(
       TokenKind::Var,
       TokenGen::ALpha(Box::new(|| "var".to_string())),
@@ -592,6 +1060,14 @@ mod test {
       TokenKind::While,
       TokenGen::ALpha(Box::new(|| "while".to_string())),
     );
+    map.insert(
+      TokenKind::With,
+      TokenGen::ALpha(Box::new(|| "with".to_string())),
+    );
+    map.insert(
+      TokenKind::Yield,
+      TokenGen::ALpha(Box::new(|| "yield".to_string())),
+    );
     map.insert(
       TokenKind::Error,
       TokenGen::ALpha(Box::new(|| "$$".to_string())),
@@ -626,13 +1102,98 @@ mod test {
     }
   }
 
+  #[test]
+  fn test_string_escapes() {
+    let source = r#""a\tb\n\"c\" \\ \u{1F600} \${}""#.to_string();
+    let mut scanner = Scanner::new(&source);
+
+    let token = scanner.scan_token();
+    assert_eq!(token.kind, TokenKind::String);
+    assert_eq!(token.lexeme, "\"a\tb\n\"c\" \\ \u{1F600} ${}\"");
+  }
+
+  #[test]
+  fn test_invalid_escapes() {
+    for example in &[r#""\q""#, r#""\u{110000}""#, r#""\u{}""#, r#""\u1F600""#] {
+      let source = example.to_string();
+      let mut scanner = Scanner::new(&source);
+
+      let token = scanner.scan_token();
+      assert_eq!(token.kind, TokenKind::Error);
+
+      let eof = scanner.scan_token();
+      assert_eq!(eof.kind, TokenKind::Eof);
+    }
+  }
+
+  #[test]
+  fn test_interpolation() {
+    let source = r#""a${b + "c${d}"}e${ :{} }""#.to_string();
+    let mut scanner = Scanner::new(&source);
+
+    let expected = [
+      (TokenKind::Interpolation, "\"a\""),
+      (TokenKind::Identifier, "b"),
+      (TokenKind::Plus, "+"),
+      (TokenKind::Interpolation, "\"c\""),
+      (TokenKind::Identifier, "d"),
+      (TokenKind::String, "\"\""),
+      (TokenKind::Interpolation, "\"e\""),
+      (TokenKind::MapOpen, ":{"),
+      (TokenKind::RightBrace, "}"),
+      (TokenKind::String, "\"\""),
+      (TokenKind::Eof, ""),
+    ];
+
+    for (kind, lexeme) in expected.iter() {
+      let token = scanner.scan_token();
+      assert_eq!(token.kind, *kind);
+      assert_eq!(token.lexeme, *lexeme);
+    }
+  }
+
+  #[test]
+  fn test_comments() {
+    let source = "// line\n/* block\n /* nested */ */ a /**/ b //// c\n/// doc\nd".to_string();
+    let mut scanner = Scanner::new(&source);
+
+    let expected = [
+      (TokenKind::Identifier, "a", 2),
+      (TokenKind::Identifier, "b", 2),
+      (TokenKind::DocComment, "/// doc", 3),
+      (TokenKind::Identifier, "d", 4),
+      (TokenKind::Eof, "", 4),
+    ];
+
+    for (kind, lexeme, line) in expected.iter() {
+      let token = scanner.scan_token();
+      assert_eq!(token.kind, *kind);
+      assert_eq!(token.lexeme, *lexeme);
+      assert_eq!(token.line, *line);
+    }
+  }
+
+  #[test]
+  fn test_unterminated_block_comment() {
+    let source = "a\n/* b /* c */\n".to_string();
+    let mut scanner = Scanner::new(&source);
+
+    let token = scanner.scan_token();
+    assert_eq!(token.kind, TokenKind::Identifier);
+
+    let token = scanner.scan_token();
+    assert_eq!(token.kind, TokenKind::Error);
+    assert_eq!(token.lexeme, COMMENT_ERROR);
+    assert_eq!(token.line, 1);
+  }
+
   #[test]
   fn test_multiple_tokens() {
     let basic = "10 + 3".to_string();
     let mut scanner = Scanner::new(&basic);
 
     let token_ten = scanner.scan_token().clone();
-    assert_eq!(token_ten.kind, TokenKind::Number);
+    assert_eq!(token_ten.kind, TokenKind::Integer);
     assert_eq!(token_ten.lexeme, "10");
 
     let token_plus = scanner.scan_token().clone();
@@ -640,7 +1201,7 @@ mod test {
     assert_eq!(token_plus.lexeme, "+");
 
     let token_three = scanner.scan_token().clone();
-    assert_eq!(token_three.kind, TokenKind::Number);
+    assert_eq!(token_three.kind, TokenKind::Integer);
     assert_eq!(token_three.lexeme, "3");
 
     let token_eof = scanner.scan_token().clone();
@@ -1,22 +1,48 @@
 use spacelox_core::hooks::NoContext;
 use spacelox_core::hooks::{Hooks, HookContext};
-use crate::call_frame::CallFrame;
 use crate::compiler::{Compiler, CompilerResult, Parser};
-use crate::constants::{DEFAULT_STACK_MAX, FRAME_MAX};
-use fnv::FnvHashMap;
+use crate::constants::{DEFAULT_STACK_MAX, FIBER_FRAME_MIN, FIBER_STACK_MIN, FRAME_MAX};
+use fnv::{FnvHashMap, FnvHashSet};
+use num_bigint::{BigInt, Sign};
+use num_traits::{ToPrimitive, Zero};
 use spacelox_core::{
+  call_frame::CallFrame,
   chunk::{ByteCode, UpvalueIndex},
-  constants::{PLACEHOLDER_NAME, SCRIPT},
+  constants::{
+    ADD, BIT_AND, BIT_NOT, BIT_OR, BIT_XOR, CURRENT, DIV, EQUAL, GREATER, INDEX, INT_DIV, ITER,
+    LESS, MOD, MUL, NEG, NEXT, PLACEHOLDER_NAME, POW, RECV, REFLECTED_ADD, REFLECTED_BIT_AND,
+    REFLECTED_BIT_OR, REFLECTED_BIT_XOR, REFLECTED_DIV, REFLECTED_INT_DIV, REFLECTED_MOD,
+    REFLECTED_MUL, REFLECTED_POW, REFLECTED_SHIFT_LEFT, REFLECTED_SHIFT_RIGHT, REFLECTED_SUB,
+    RESUME, RETURN, SCRIPT, SEND, SET_INDEX, SHIFT_LEFT, SHIFT_RIGHT, SLEEP, SPAWN, SUB, YIELD,
+  },
   io::{Io, NativeIo, StdIo},
   managed::{Managed, Trace},
   memory::{Gc, NO_GC},
+  module::Module,
   native::{NativeFun, NativeMethod, NativeResult},
-  utils::use_sentinel_nan,
-  value::{ArityKind, BuiltInClasses, Class, Closure, Fun, Instance, Method, Upvalue, Value},
+  utils::{exact_integer, use_sentinel_nan},
+  value::{
+    ArityKind, BuiltInClasses, Channel, Class, Closure, Fiber, FiberState, Fun, Generator,
+    GeneratorState, Instance, Method, Range, Trait, Upvalue, Value,
+  },
 };
-use spacelox_lib::{assert::assert_funs, builtin::make_builtin_classes, time::clock_funs};
-use std::convert::TryInto;
+use spacelox_lib::{
+  assert::assert_funs,
+  builtin::{
+    big_int::big_int_funs,
+    channel::CHANNEL_CLASS_NAME,
+    error::{ERROR_CLASS_NAME, ERROR_MESSAGE, ERROR_STACK},
+    fiber::FIBER_CLASS_NAME,
+    make_builtin_classes,
+  },
+  time::clock_funs,
+};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::convert::{TryFrom, TryInto};
+use std::fs::read_to_string;
 use std::mem;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::ptr::NonNull;
 
@@ -27,6 +53,7 @@ use crate::debug::disassemble_instruction;
 enum Signal {
   Ok(u32),
   OkReturn(u32),
+  Throw,
   Exit,
   RuntimeError,
 }
@@ -39,6 +66,174 @@ pub enum ExecuteResult {
   CompileError,
 }
 
+/// An active exception handler installed by a try statement
+#[derive(Debug, Clone, Copy)]
+struct Handler {
+  /// The frame depth when the handler was installed
+  frame_count: usize,
+
+  /// The stack height when the handler was installed
+  stack_top: usize,
+
+  /// The instruction to resume at when an error is caught
+  ip: u32,
+}
+
+/// A module whose script is currently being executed by an import
+#[derive(Debug, Clone, Copy)]
+struct Import {
+  /// The module being loaded
+  module: Managed<Module>,
+
+  /// The frame depth when the import began
+  frame_count: usize,
+}
+
+/// A generator whose frame is currently on the frame stack
+#[derive(Debug, Clone, Copy)]
+struct Resumed {
+  /// The running generator
+  generator: Managed<Generator>,
+
+  /// The frame depth of the generator's frame
+  frame_count: usize,
+
+  /// Was the generator resumed by return, its result then goes to the caller
+  returning: bool,
+}
+
+/// The names of the special methods operators dispatch to, interned
+/// once so the hot path never has to allocate them
+#[derive(Debug, Clone, Copy)]
+struct SpecialMethods {
+  add: Managed<String>,
+  reflected_add: Managed<String>,
+  sub: Managed<String>,
+  reflected_sub: Managed<String>,
+  mul: Managed<String>,
+  reflected_mul: Managed<String>,
+  div: Managed<String>,
+  reflected_div: Managed<String>,
+  modulo: Managed<String>,
+  reflected_modulo: Managed<String>,
+  pow: Managed<String>,
+  reflected_pow: Managed<String>,
+  int_div: Managed<String>,
+  reflected_int_div: Managed<String>,
+  bit_and: Managed<String>,
+  reflected_bit_and: Managed<String>,
+  bit_or: Managed<String>,
+  reflected_bit_or: Managed<String>,
+  bit_xor: Managed<String>,
+  reflected_bit_xor: Managed<String>,
+  shift_left: Managed<String>,
+  reflected_shift_left: Managed<String>,
+  shift_right: Managed<String>,
+  reflected_shift_right: Managed<String>,
+  less: Managed<String>,
+  greater: Managed<String>,
+  equal: Managed<String>,
+  neg: Managed<String>,
+  bit_not: Managed<String>,
+  index: Managed<String>,
+  set_index: Managed<String>,
+}
+
+impl SpecialMethods {
+  fn new(gc: &Gc) -> Self {
+    let name = |name: &str| gc.manage_str(String::from(name), &NO_GC);
+
+    SpecialMethods {
+      add: name(ADD),
+      reflected_add: name(REFLECTED_ADD),
+      sub: name(SUB),
+      reflected_sub: name(REFLECTED_SUB),
+      mul: name(MUL),
+      reflected_mul: name(REFLECTED_MUL),
+      div: name(DIV),
+      reflected_div: name(REFLECTED_DIV),
+      modulo: name(MOD),
+      reflected_modulo: name(REFLECTED_MOD),
+      pow: name(POW),
+      reflected_pow: name(REFLECTED_POW),
+      int_div: name(INT_DIV),
+      reflected_int_div: name(REFLECTED_INT_DIV),
+      bit_and: name(BIT_AND),
+      reflected_bit_and: name(REFLECTED_BIT_AND),
+      bit_or: name(BIT_OR),
+      reflected_bit_or: name(REFLECTED_BIT_OR),
+      bit_xor: name(BIT_XOR),
+      reflected_bit_xor: name(REFLECTED_BIT_XOR),
+      shift_left: name(SHIFT_LEFT),
+      reflected_shift_left: name(REFLECTED_SHIFT_LEFT),
+      shift_right: name(SHIFT_RIGHT),
+      reflected_shift_right: name(REFLECTED_SHIFT_RIGHT),
+      less: name(LESS),
+      greater: name(GREATER),
+      equal: name(EQUAL),
+      neg: name(NEG),
+      bit_not: name(BIT_NOT),
+      index: name(INDEX),
+      set_index: name(SET_INDEX),
+    }
+  }
+
+  /// Every special method name
+  fn names(&self) -> [Managed<String>; 31] {
+    [
+      self.add,
+      self.reflected_add,
+      self.sub,
+      self.reflected_sub,
+      self.mul,
+      self.reflected_mul,
+      self.div,
+      self.reflected_div,
+      self.modulo,
+      self.reflected_modulo,
+      self.pow,
+      self.reflected_pow,
+      self.int_div,
+      self.reflected_int_div,
+      self.bit_and,
+      self.reflected_bit_and,
+      self.bit_or,
+      self.reflected_bit_or,
+      self.bit_xor,
+      self.reflected_bit_xor,
+      self.shift_left,
+      self.reflected_shift_left,
+      self.shift_right,
+      self.reflected_shift_right,
+      self.less,
+      self.greater,
+      self.equal,
+      self.neg,
+      self.bit_not,
+      self.index,
+      self.set_index,
+    ]
+  }
+}
+
+impl Trace for SpecialMethods {
+  fn trace(&self) -> bool {
+    self.names().iter().for_each(|name| {
+      name.trace();
+    });
+
+    true
+  }
+
+  fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
+    self.names().iter().for_each(|name| {
+      name.trace_debug(stdio);
+    });
+
+    true
+  }
+}
+
 pub fn default_native_vm() -> Vm<NativeIo> {
   let io = NativeIo::new();
   Vm::new(io)
@@ -60,6 +255,9 @@ pub struct VmDependencies<I: Io + 'static> {
 
   /// The native functions
   natives: Vec<Box<dyn NativeFun>>,
+
+  /// The directories searched for imported modules
+  search_paths: Vec<PathBuf>,
 }
 
 /// The virtual machine for the spacelox programming language
@@ -73,44 +271,72 @@ pub struct Vm<I: Io + 'static> {
   /// A collection of built in classes
   builtin: BuiltInClasses,
 
+  /// The interned names of operator special methods
+  special: SpecialMethods,
+
   /// The vm's garbage collector
   gc: Gc,
 
   /// The environments io access
   io: I,
 
-  /// A persisted set of globals most for a repl context
+  /// The globals shared by every module
   globals: FnvHashMap<Managed<String>, Value>,
+
+  /// The main module persisted mostly for a repl context
+  main: Managed<Module>,
+
+  /// The main module's globals declared const, kept across repl lines
+  const_globals: FnvHashSet<String>,
+
+  /// Modules already loaded keyed by their canonical path
+  modules: FnvHashMap<PathBuf, Managed<Module>>,
+
+  /// The directories searched for imported modules
+  search_paths: Vec<PathBuf>,
 }
 
 impl<I: Io> Vm<I> {
   pub fn new(io: I) -> Vm<I> {
     let gc = Gc::new(Box::new(io.stdio()));
     let fun = Fun::new(gc.manage_str(String::from(PLACEHOLDER_NAME), &NO_GC));
+    let main = make_main_module(&gc);
 
     let managed_fun = gc.manage(fun, &NO_GC);
-    let closure = gc.manage(Closure::new(managed_fun), &NO_GC);
+    let closure = gc.manage(Closure::new(managed_fun, main), &NO_GC);
 
     let frames = vec![CallFrame::new(closure); FRAME_MAX];
     let stack = vec![Value::Nil; DEFAULT_STACK_MAX];
 
     let mut natives = Vec::new();
-    natives.extend(assert_funs().into_iter());
-    natives.extend(clock_funs().into_iter());
+    natives.extend(assert_funs());
+    natives.extend(clock_funs());
+    natives.extend(big_int_funs());
 
     let builtin = make_builtin_classes(&Hooks::new(&mut NoContext::new(&gc)));
-    let globals = define_globals(&gc, natives);
+    let globals = define_globals(&gc, &builtin, natives);
+    let special = SpecialMethods::new(&gc);
 
     Vm {
       io,
       stack,
       frames,
       builtin,
+      special,
       gc,
       globals,
+      main,
+      const_globals: FnvHashSet::default(),
+      modules: FnvHashMap::default(),
+      search_paths: Vec::new(),
     }
   }
 
+  /// Add a directory to search when resolving imported modules
+  pub fn add_search_path<P: Into<PathBuf>>(&mut self, path: P) {
+    self.search_paths.push(path.into());
+  }
+
   pub fn repl(&mut self) {
     let stdio = self.io.stdio();
     loop {
@@ -123,7 +349,7 @@ impl<I: Io> Vm<I> {
         Ok(_) => {
           self.interpret(&buffer);
         }
-        Err(error) => panic!(error),
+        Err(error) => panic!("{}", error),
       }
     }
   }
@@ -132,6 +358,13 @@ impl<I: Io> Vm<I> {
     self.interpret(source)
   }
 
+  /// Run the provided source as the main module found at path. Relative
+  /// imports will be resolved from the directory containing path
+  pub fn run_module(&mut self, source: &str, path: &Path) -> ExecuteResult {
+    self.main.set_path(Some(path.to_path_buf()));
+    self.interpret(source)
+  }
+
   /// Interpret the provided spacelox script returning the execution result
   fn interpret(&mut self, source: &str) -> ExecuteResult {
     let result = self.compile(source);
@@ -140,10 +373,10 @@ impl<I: Io> Vm<I> {
       return ExecuteResult::CompileError;
     }
 
-    let script_closure = self.gc.manage(Closure::new(result.fun), &NO_GC);
+    let script_closure = self.gc.manage(Closure::new(result.fun, self.main), &NO_GC);
     let script = Value::Closure(script_closure);
     let mut executor = VmExecutor::new(self, script);
-    executor.run(false)
+    executor.run()
   }
 
   /// Compile the provided spacelox source into the virtual machine's bytecode
@@ -153,8 +386,14 @@ impl<I: Io> Vm<I> {
     let mut compiler_context = NoContext::new(&self.gc);
     let hooks = Hooks::new(&mut compiler_context);
 
-    let compiler = Compiler::new(self.io, &mut parser, &hooks);
-    compiler.compile()
+    let compiler =
+      Compiler::new(self.io, &mut parser, &hooks).with_const_globals(self.const_globals.clone());
+    let mut result = compiler.compile();
+
+    if result.success {
+      self.const_globals = mem::take(&mut result.const_globals);
+    }
+    result
   }
 }
 
@@ -164,7 +403,9 @@ impl<I: Io> From<VmDependencies<I>> for Vm<I> {
   fn from(dependencies: VmDependencies<I>) -> Self {
     let gc = dependencies.gc;
     let builtin = make_builtin_classes(&Hooks::new(&mut NoContext::new(&gc)));
-    let globals = define_globals(&gc, dependencies.natives);
+    let globals = define_globals(&gc, &builtin, dependencies.natives);
+    let special = SpecialMethods::new(&gc);
+    let main = make_main_module(&gc);
 
     Vm {
       io: dependencies.io,
@@ -173,12 +414,27 @@ impl<I: Io> From<VmDependencies<I>> for Vm<I> {
       gc,
       globals,
       builtin,
+      special,
+      main,
+      const_globals: FnvHashSet::default(),
+      modules: FnvHashMap::default(),
+      search_paths: dependencies.search_paths,
     }
   }
 }
 
-fn define_globals(gc: &Gc, natives: Vec<Box<dyn NativeFun>>) -> FnvHashMap<Managed<String>, Value> {
-  let mut globals = FnvHashMap::with_capacity_and_hasher(natives.len(), Default::default());
+/// Create the module scripts run by the vm directly belong to
+fn make_main_module(gc: &Gc) -> Managed<Module> {
+  let name = gc.manage_str(String::from(SCRIPT), &NO_GC);
+  gc.manage(Module::new(name, None), &NO_GC)
+}
+
+fn define_globals(
+  gc: &Gc,
+  builtin: &BuiltInClasses,
+  natives: Vec<Box<dyn NativeFun>>,
+) -> FnvHashMap<Managed<String>, Value> {
+  let mut globals = FnvHashMap::with_capacity_and_hasher(natives.len() + 3, Default::default());
 
   natives.into_iter().for_each(|native| {
     let name = gc.manage_str(native.meta().name.to_string(), &NO_GC);
@@ -187,6 +443,15 @@ fn define_globals(gc: &Gc, natives: Vec<Box<dyn NativeFun>>) -> FnvHashMap<Manag
     globals.insert(name, native_value);
   });
 
+  let error_name = gc.manage_str(String::from(ERROR_CLASS_NAME), &NO_GC);
+  globals.insert(error_name, Value::Class(builtin.error));
+
+  let fiber_name = gc.manage_str(String::from(FIBER_CLASS_NAME), &NO_GC);
+  globals.insert(fiber_name, Value::Class(builtin.fiber));
+
+  let channel_name = gc.manage_str(String::from(CHANNEL_CLASS_NAME), &NO_GC);
+  globals.insert(channel_name, Value::Class(builtin.channel));
+
   globals
 }
 
@@ -197,12 +462,21 @@ struct VmExecutor<'a, I: Io + 'static> {
   /// A stack holding all local variable currently in use
   stack: &'a mut Vec<Value>,
 
-  /// global variable present in the vm
+  /// global variable shared by every module
   globals: &'a mut FnvHashMap<Managed<String>, Value>,
 
+  /// Modules already loaded keyed by their canonical path
+  modules: &'a mut FnvHashMap<PathBuf, Managed<Module>>,
+
+  /// The directories searched for imported modules
+  search_paths: &'a [PathBuf],
+
   /// A collection of built in classes
   builtin: &'a BuiltInClasses,
 
+  /// The interned names of operator special methods
+  special: SpecialMethods,
+
   /// A reference to a object currently in the vm
   gc: &'a mut Gc,
 
@@ -212,6 +486,30 @@ struct VmExecutor<'a, I: Io + 'static> {
   /// A collection of currently available upvalues
   open_upvalues: Vec<Managed<Upvalue>>,
 
+  /// The exception handlers currently installed
+  handlers: Vec<Handler>,
+
+  /// The modules currently being imported
+  imports: Vec<Import>,
+
+  /// The generators currently running
+  generators: Vec<Resumed>,
+
+  /// The fiber currently running
+  fiber: Managed<Fiber>,
+
+  /// The fiber running the main script
+  main_fiber: Managed<Fiber>,
+
+  /// Fibers ready to run in the order they will run
+  ready: VecDeque<Managed<Fiber>>,
+
+  /// Sleeping fibers paired with the time they wake, soonest first
+  sleeping: Vec<(f64, Managed<Fiber>)>,
+
+  /// The scheduler's virtual clock, moved forward as sleeping fibers wake
+  clock: f64,
+
   /// the main script level function
   script: Value,
 
@@ -234,6 +532,12 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     let current_frame = vm.frames[0];
     let current_fun = current_frame.closure.fun;
 
+    let mut main_fiber = vm.gc.manage(
+      Fiber::new(script.to_closure(), Vec::new(), Vec::new()),
+      &NO_GC,
+    );
+    main_fiber.state = FiberState::Running;
+
     let mut executor = VmExecutor {
       frames: &mut vm.frames,
       frame_count: 0,
@@ -242,11 +546,22 @@ impl<'a, I: Io> VmExecutor<'a, I> {
       current_fun,
       current_frame,
       builtin: &vm.builtin,
+      special: vm.special,
       gc: &mut vm.gc,
       io: &mut vm.io,
       stack_top: 1,
       globals: &mut vm.globals,
+      modules: &mut vm.modules,
+      search_paths: &vm.search_paths,
       open_upvalues: Vec::with_capacity(100),
+      handlers: Vec::new(),
+      imports: Vec::new(),
+      generators: Vec::new(),
+      fiber: main_fiber,
+      main_fiber,
+      ready: VecDeque::new(),
+      sleeping: Vec::new(),
+      clock: 0.0,
     };
 
     let result = executor.call(executor.script.to_closure(), 0, 0);
@@ -258,7 +573,7 @@ impl<'a, I: Io> VmExecutor<'a, I> {
 
   /// Main virtual machine execution loop. This will run the until the program interrupts
   /// from a normal exit or from a runtime error.
-  pub fn run(&mut self, execute_fun: bool) -> ExecuteResult {
+  pub fn run(&mut self) -> ExecuteResult {
     let mut ip: u32 = 0;
 
     #[cfg(feature = "debug")]
@@ -281,16 +596,54 @@ impl<'a, I: Io> VmExecutor<'a, I> {
         ByteCode::Subtract => self.op_sub(ip),
         ByteCode::Multiply => self.op_mul(ip),
         ByteCode::Divide => self.op_div(ip),
+        ByteCode::Modulo => self.op_mod(ip),
+        ByteCode::Power => self.op_pow(ip),
+        ByteCode::IntegerDivide => self.op_int_div(ip),
+        ByteCode::BitAnd => self.op_bitwise(
+          ip,
+          (self.special.bit_and, self.special.reflected_bit_and),
+          |left, right| Some(left & right),
+          |left, right| Some(left & right),
+        ),
+        ByteCode::BitOr => self.op_bitwise(
+          ip,
+          (self.special.bit_or, self.special.reflected_bit_or),
+          |left, right| Some(left | right),
+          |left, right| Some(left | right),
+        ),
+        ByteCode::BitXor => self.op_bitwise(
+          ip,
+          (self.special.bit_xor, self.special.reflected_bit_xor),
+          |left, right| Some(left ^ right),
+          |left, right| Some(left ^ right),
+        ),
+        ByteCode::ShiftLeft => self.op_bitwise(
+          ip,
+          (self.special.shift_left, self.special.reflected_shift_left),
+          shift_left,
+          big_shift_left,
+        ),
+        ByteCode::ShiftRight => self.op_bitwise(
+          ip,
+          (self.special.shift_right, self.special.reflected_shift_right),
+          shift_right,
+          big_shift_right,
+        ),
+        ByteCode::BitNot => self.op_bit_not(ip),
         ByteCode::Not => self.op_not(ip),
         ByteCode::Equal => self.op_equal(ip),
         ByteCode::Greater => self.op_greater(ip),
         ByteCode::Less => self.op_less(ip),
         ByteCode::JumpIfFalse => self.op_jump_if_not_false(ip),
+        ByteCode::JumpIfNil => self.op_jump_if_nil(ip),
         ByteCode::Jump => self.op_jump(ip),
         ByteCode::Loop => self.op_loop(ip),
         ByteCode::DefineGlobal => self.op_define_global(ip),
         ByteCode::GetIndex => self.op_get_index(ip),
         ByteCode::SetIndex => self.op_set_index(ip),
+        ByteCode::Slice => self.op_slice(ip),
+        ByteCode::Range => self.op_range(ip, false),
+        ByteCode::RangeInclusive => self.op_range(ip, true),
         ByteCode::GetGlobal => self.op_get_global(ip),
         ByteCode::SetGlobal => self.op_set_global(ip),
         ByteCode::GetLocal => self.op_get_local(ip),
@@ -300,39 +653,73 @@ impl<'a, I: Io> VmExecutor<'a, I> {
         ByteCode::GetProperty => self.op_get_property(ip),
         ByteCode::SetProperty => self.op_set_property(ip),
         ByteCode::Pop => self.op_pop(ip),
+        ByteCode::Dup => self.op_dup(ip),
+        ByteCode::DupTwo => self.op_dup_two(ip),
+        ByteCode::Rotate => self.op_rotate(ip),
         ByteCode::Nil => self.op_literal(ip, Value::Nil),
         ByteCode::True => self.op_literal(ip, Value::Bool(true)),
         ByteCode::False => self.op_literal(ip, Value::Bool(false)),
         ByteCode::List => self.op_literal(ip, Value::List(self.gc.manage(Vec::new(), self))),
         ByteCode::ListInit => self.op_list(ip),
+        ByteCode::ListSpread => self.op_list_spread(ip),
         ByteCode::Map => {
           self.op_literal(ip, Value::Map(self.gc.manage(FnvHashMap::default(), self)))
         }
         ByteCode::MapInit => self.op_map(ip),
+        ByteCode::MapSpread => self.op_map_spread(ip),
         ByteCode::Constant => self.op_constant(ip),
         ByteCode::Print => self.op_print(ip),
         ByteCode::Call => self.op_call(ip),
+        ByteCode::CallSpread => self.op_call_spread(ip),
         ByteCode::Invoke => self.op_invoke(ip),
+        ByteCode::InvokeSpread => self.op_invoke_spread(ip),
         ByteCode::SuperInvoke => self.op_super_invoke(ip),
+        ByteCode::SuperInvokeSpread => self.op_super_invoke_spread(ip),
         ByteCode::Closure => self.op_closure(ip),
         ByteCode::Method => self.op_method(ip),
+        ByteCode::StaticMethod => self.op_static_method(ip),
+        ByteCode::Getter => self.op_getter(ip),
+        ByteCode::Setter => self.op_setter(ip),
         ByteCode::Class => self.op_class(ip),
         ByteCode::Inherit => self.op_inherit(ip),
+        ByteCode::Trait => self.op_trait(ip),
+        ByteCode::Require => self.op_require(ip),
+        ByteCode::Doc => self.op_doc(ip),
+        ByteCode::Mixin => self.op_mixin(ip),
         ByteCode::GetSuper => self.op_get_super(ip),
         ByteCode::CloseUpvalue => self.op_close_upvalue(ip),
         ByteCode::Return => self.op_return(ip),
+        ByteCode::PushHandler => self.op_push_handler(ip),
+        ByteCode::PopHandler => self.op_pop_handler(ip),
+        ByteCode::Throw => Signal::Throw,
+        ByteCode::Import => self.op_import(ip),
+        ByteCode::Export => self.op_export(ip),
+        ByteCode::MatchList => self.op_match_list(ip, false),
+        ByteCode::MatchListRest => self.op_match_list(ip, true),
+        ByteCode::MatchMap => self.op_match_map(ip),
+        ByteCode::MatchKey => self.op_match_key(ip),
+        ByteCode::MatchClass => self.op_match_class(ip),
+        ByteCode::MatchField => self.op_match_field(ip),
+        ByteCode::ListRest => self.op_list_rest(ip),
+        ByteCode::DestructureList => self.op_destructure_list(ip, false),
+        ByteCode::DestructureListRest => self.op_destructure_list(ip, true),
+        ByteCode::DestructureMap => self.op_destructure_map(ip),
+        ByteCode::Yield => self.op_yield(ip),
+        ByteCode::CloseIterator => self.op_close_iterator(ip),
+        ByteCode::ArgumentSpread => self.op_argument_spread(ip),
       };
 
       match result {
-        Signal::Ok(new_ip) => ip = new_ip,
-        Signal::OkReturn(new_ip) => {
-          if execute_fun {
-
+        Signal::Ok(new_ip) | Signal::OkReturn(new_ip) => ip = new_ip,
+        Signal::Throw => match self.throw(ip) {
+          Signal::Ok(new_ip) => ip = new_ip,
+          _ => {
+            self.restore_main();
+            return ExecuteResult::RuntimeError;
           }
-
-          ip = new_ip;
-        }
+        },
         Signal::RuntimeError => {
+          self.restore_main();
           return ExecuteResult::RuntimeError;
         }
         Signal::Exit => {
@@ -357,14 +744,14 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   /// Get an immutable reference to value on the stack
   #[inline]
   fn get_val(&self, index: usize) -> Value {
-    unsafe { *self.stack.get_unchecked(index as usize) }
+    unsafe { *self.stack.get_unchecked(index) }
   }
 
   /// Set a value on the stack
   #[inline]
   fn set_val(&mut self, index: usize, val: Value) {
     unsafe {
-      *self.stack.get_unchecked_mut(index as usize) = val;
+      *self.stack.get_unchecked_mut(index) = val;
     }
   }
 
@@ -409,15 +796,45 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   /// push a value onto the stack
   #[inline]
   fn push(&mut self, value: Value) {
+    if self.stack_top == self.stack.len() {
+      self.grow_stack(self.stack_top + 1);
+    }
+
     self.set_val(self.stack_top, value);
     self.stack_top += 1;
   }
 
+  /// Grow the stack to hold at least `len` values. Fibers start with a
+  /// small stack so open upvalues are repointed at the moved slots
+  #[cold]
+  fn grow_stack(&mut self, len: usize) {
+    let base = self.stack.as_ptr() as usize;
+    let new_len = len.max(self.stack.len() * 2);
+    self.stack.resize(new_len, Value::Nil);
+
+    for upvalue in self.open_upvalues.iter_mut() {
+      if let Upvalue::Open(index) = **upvalue {
+        let offset = (index.as_ptr() as usize - base) / mem::size_of::<Value>();
+        **upvalue = Upvalue::Open(NonNull::from(&self.stack[offset]));
+      }
+    }
+  }
+
+  /// The frame above the current one, allocated if a fiber's frames have
+  /// not grown this deep yet
+  fn next_frame(&mut self, closure: Managed<Closure>) -> &mut CallFrame {
+    if self.frame_count == self.frames.len() {
+      self.frames.push(CallFrame::new(closure));
+    }
+
+    &mut self.frames[self.frame_count]
+  }
+
   /// pop a value off the stack
   #[inline]
   fn pop(&mut self) -> Value {
     self.stack_top -= 1;
-    unsafe { *self.stack.get_unchecked(self.stack_top as usize) }
+    unsafe { *self.stack.get_unchecked(self.stack_top) }
   }
 
   /// reference a value n slots from the stack head
@@ -449,6 +866,9 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     self.stack_top = 1;
     self.frame_count = 0;
     self.open_upvalues.clear();
+    self.handlers.clear();
+    self.unwind_imports();
+    self.unwind_generators();
   }
 
   /// push a literal value onto the stack
@@ -463,13 +883,54 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     Signal::Ok(ip + 1)
   }
 
+  /// duplicate the top value on the stack
+  fn op_dup(&mut self, ip: u32) -> Signal {
+    self.push(self.peek(0));
+    Signal::Ok(ip + 1)
+  }
+
+  /// duplicate the top two values on the stack
+  fn op_dup_two(&mut self, ip: u32) -> Signal {
+    let first = self.peek(1);
+    let second = self.peek(0);
+    self.push(first);
+    self.push(second);
+    Signal::Ok(ip + 1)
+  }
+
+  /// move the top value on the stack below the next count values
+  fn op_rotate(&mut self, ip: u32) -> Signal {
+    let count = self.read_byte(ip + 1) as usize;
+    let start = self.stack_top - count - 1;
+    self.stack[start..self.stack_top].rotate_right(1);
+    Signal::Ok(ip + 2)
+  }
+
+  /// install an exception handler for the following try block
+  fn op_push_handler(&mut self, ip: u32) -> Signal {
+    let jump = self.read_short(ip + 1);
+    self.handlers.push(Handler {
+      frame_count: self.frame_count,
+      stack_top: self.stack_top,
+      ip: ip + 3 + jump as u32,
+    });
+
+    Signal::Ok(ip + 3)
+  }
+
+  /// remove the most recent exception handler
+  fn op_pop_handler(&mut self, ip: u32) -> Signal {
+    self.handlers.pop();
+    Signal::Ok(ip + 1)
+  }
+
   /// create a list from a list literal
   fn op_list(&mut self, ip: u32) -> Signal {
     let arg_count = self.read_short(ip + 1);
     let args = unsafe {
       self
         .stack
-        .get_unchecked(self.stack_top as usize - arg_count as usize..self.stack_top as usize)
+        .get_unchecked(self.stack_top - arg_count as usize..self.stack_top)
     };
     let mut list = self.peek(arg_count as u32).to_list();
     list.extend(args);
@@ -478,6 +939,33 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     Signal::Ok(ip + 3)
   }
 
+  /// spread a list into the list being built below it
+  fn op_list_spread(&mut self, ip: u32) -> Signal {
+    self.spread(ip, "a list")
+  }
+
+  /// spread a list into the arguments of a call collected below it
+  fn op_argument_spread(&mut self, ip: u32) -> Signal {
+    self.spread(ip, "call arguments")
+  }
+
+  /// extend the list below the top of the stack with the list on top,
+  /// naming the target in the error when the top is not a list
+  fn spread(&mut self, ip: u32, target: &str) -> Signal {
+    match self.pop() {
+      Value::List(spread) => {
+        let mut list = self.peek(0).to_list();
+        list.extend(spread.iter());
+        Signal::Ok(ip + 1)
+      }
+      value => self.runtime_error(&format!(
+        "Cannot spread {} into {}.",
+        value.value_type(),
+        target
+      )),
+    }
+  }
+
   /// create a list from a list literal
   fn op_map(&mut self, ip: u32) -> Signal {
     let arg_count = self.read_short(ip + 1);
@@ -503,6 +991,20 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     Signal::Ok(ip + 3)
   }
 
+  /// spread a map into the map being built below it
+  fn op_map_spread(&mut self, ip: u32) -> Signal {
+    match self.pop() {
+      Value::Map(spread) => {
+        let mut map = self.peek(0).to_map();
+        for (key, value) in spread.iter() {
+          map.insert(*key, *value);
+        }
+        Signal::Ok(ip + 1)
+      }
+      value => self.runtime_error(&format!("Cannot spread {} into a map.", value.value_type())),
+    }
+  }
+
   /// call a function or method
   fn op_call(&mut self, ip: u32) -> Signal {
     let arg_count = self.read_byte(ip + 1);
@@ -511,43 +1013,123 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     self.resolve_call(callee, arg_count, ip + 2)
   }
 
+  /// call a function or method with a list of arguments
+  fn op_call_spread(&mut self, ip: u32) -> Signal {
+    let arg_count = match self.unpack_args() {
+      Ok(arg_count) => arg_count,
+      Err(error) => return error,
+    };
+    let callee = self.peek(arg_count as u32);
+
+    self.resolve_call(callee, arg_count, ip + 1)
+  }
+
+  /// unpack a list of arguments built at runtime onto the stack
+  fn unpack_args(&mut self) -> Result<u8, Signal> {
+    let args = self.pop().to_list();
+
+    if args.len() > u8::MAX as usize {
+      return Err(self.runtime_error(&format!(
+        "Cannot call with more than {} arguments.",
+        u8::MAX
+      )));
+    }
+
+    if self.stack_top + args.len() > DEFAULT_STACK_MAX {
+      return Err(self.runtime_error("Stack overflow."));
+    }
+
+    for arg in args.iter() {
+      self.push(*arg);
+    }
+
+    Ok(args.len() as u8)
+  }
+
   /// invoke a method on an instance's class
   fn op_invoke(&mut self, ip: u32) -> Signal {
     let constant = self.read_byte(ip + 1);
     let arg_count = self.read_byte(ip + 2);
 
     let method_name = self.read_string(constant);
+    self.invoke(method_name, arg_count, ip + 3)
+  }
+
+  /// invoke a method on an instance's class with a list of arguments
+  fn op_invoke_spread(&mut self, ip: u32) -> Signal {
+    let constant = self.read_byte(ip + 1);
+    let arg_count = match self.unpack_args() {
+      Ok(arg_count) => arg_count,
+      Err(error) => return error,
+    };
+
+    let method_name = self.read_string(constant);
+    self.invoke(method_name, arg_count, ip + 2)
+  }
+
+  /// invoke a method on the receiver below the arguments
+  fn invoke(&mut self, method_name: Managed<String>, arg_count: u8, ip: u32) -> Signal {
     let receiver = self.peek(arg_count as u32);
 
     match receiver {
       Value::Instance(instance) => match instance.get_field(&method_name) {
         Some(field) => {
           self.set_val(self.stack_top - (arg_count as usize) - 1, *field);
-          self.resolve_call(*field, arg_count, ip + 3)
+          self.resolve_call(*field, arg_count, ip)
         }
-        None => self.invoke_from_class(instance.class, method_name, arg_count, ip + 3),
+        None => self.invoke_from_class(instance.class, method_name, arg_count, ip),
       },
-      Value::Bool(_) => self.invoke_from_class(self.builtin.bool, method_name, arg_count, ip + 3),
-      Value::Number(_) => {
-        self.invoke_from_class(self.builtin.number, method_name, arg_count, ip + 3)
+      Value::Class(class) if class == self.builtin.fiber => {
+        self.invoke_fiber_class(method_name, arg_count, ip)
       }
-      Value::Nil => self.invoke_from_class(self.builtin.nil, method_name, arg_count, ip + 3),
-      Value::String(_) => {
-        self.invoke_from_class(self.builtin.string, method_name, arg_count, ip + 3)
+      Value::Class(class) => match class.get_field(&method_name) {
+        Some(field) => {
+          self.set_val(self.stack_top - (arg_count as usize) - 1, *field);
+          self.resolve_call(*field, arg_count, ip)
+        }
+        None => match class.get_static_method(&method_name) {
+          Some(method) => self.resolve_call(*method, arg_count, ip),
+          None => self.invoke_from_class(self.builtin.class, method_name, arg_count, ip),
+        },
+      },
+      Value::Bool(_) => self.invoke_from_class(self.builtin.bool, method_name, arg_count, ip),
+      Value::Number(_) | Value::Int(_) => {
+        self.invoke_from_class(self.builtin.number, method_name, arg_count, ip)
       }
-      Value::Fun(_) => self.invoke_from_class(self.builtin.fun, method_name, arg_count, ip + 3),
+      Value::BigInt(_) => self.invoke_from_class(self.builtin.big_int, method_name, arg_count, ip),
+      Value::Nil => self.invoke_from_class(self.builtin.nil, method_name, arg_count, ip),
+      Value::String(_) => self.invoke_from_class(self.builtin.string, method_name, arg_count, ip),
+      Value::Fun(_) => self.invoke_from_class(self.builtin.fun, method_name, arg_count, ip),
+      Value::Generator(generator) => self.invoke_generator(generator, method_name, arg_count, ip),
+      Value::Fiber(fiber) => self.invoke_fiber(fiber, method_name, arg_count, ip),
+      Value::Channel(channel) => self.invoke_channel(channel, method_name, arg_count, ip),
       Value::Closure(closure) => {
         self.set_val(self.stack_top - 1, Value::Fun(closure.fun));
-        self.invoke_from_class(self.builtin.fun, method_name, arg_count, ip + 3)
+        self.invoke_from_class(self.builtin.fun, method_name, arg_count, ip)
       }
-      Value::List(_) => self.invoke_from_class(self.builtin.list, method_name, arg_count, ip + 3),
-      Value::Map(_) => self.invoke_from_class(self.builtin.map, method_name, arg_count, ip + 3),
+      // bound methods answer for the function they bind
+      Value::Method(method) => {
+        self.set_val(self.stack_top - (arg_count as usize) - 1, method.method);
+        self.invoke(method_name, arg_count, ip)
+      }
+      Value::List(_) => self.invoke_from_class(self.builtin.list, method_name, arg_count, ip),
+      Value::Map(_) => self.invoke_from_class(self.builtin.map, method_name, arg_count, ip),
+      Value::Range(_) => self.invoke_from_class(self.builtin.range, method_name, arg_count, ip),
+      Value::Trait(_) => self.invoke_from_class(self.builtin.trait_, method_name, arg_count, ip),
       Value::NativeFun(_) => {
-        self.invoke_from_class(self.builtin.native, method_name, arg_count, ip + 3)
+        self.invoke_from_class(self.builtin.native, method_name, arg_count, ip)
       }
       Value::NativeMethod(_) => {
-        self.invoke_from_class(self.builtin.native, method_name, arg_count, ip + 3)
+        self.invoke_from_class(self.builtin.native, method_name, arg_count, ip)
       }
+      Value::Iter(_) => self.invoke_from_class(self.builtin.iter, method_name, arg_count, ip),
+      Value::Module(module) => match module.get_export(&method_name) {
+        Some(export) => {
+          self.set_val(self.stack_top - (arg_count as usize) - 1, *export);
+          self.resolve_call(*export, arg_count, ip)
+        }
+        None => self.invoke_from_class(self.builtin.module, method_name, arg_count, ip),
+      },
       _ => self.runtime_error(&format!("{} does not have methods.", receiver.value_type())),
     }
   }
@@ -563,6 +1145,70 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     self.invoke_from_class(super_class, method_name, arg_count, ip + 3)
   }
 
+  /// Invoke a method on a instance's super class with a list of arguments
+  fn op_super_invoke_spread(&mut self, ip: u32) -> Signal {
+    let constant = self.read_byte(ip + 1);
+    let super_class = self.pop().to_class();
+    let arg_count = match self.unpack_args() {
+      Ok(arg_count) => arg_count,
+      Err(error) => return error,
+    };
+
+    let method_name = self.read_string(constant);
+    self.invoke_from_class(super_class, method_name, arg_count, ip + 2)
+  }
+
+  /// Add a static method to the class on the stack
+  fn op_static_method(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    match (self.peek(1), self.peek(0)) {
+      (Value::Class(ref mut class), Value::Closure(_)) => {
+        let method = self.peek(0);
+        class.add_static_method(&Hooks::new(self), name, method);
+      }
+      _ => panic!("Internal spacelox error. stack invalid for op_static_method"),
+    }
+
+    self.pop();
+    Signal::Ok(ip + 2)
+  }
+
+  /// Add a property getter to the class on the stack
+  fn op_getter(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    match (self.peek(1), self.peek(0)) {
+      (Value::Class(ref mut class), Value::Closure(_)) => {
+        let getter = self.peek(0);
+        class.add_getter(&Hooks::new(self), name, getter);
+      }
+      _ => panic!("Internal spacelox error. stack invalid for op_getter"),
+    }
+
+    self.pop();
+    Signal::Ok(ip + 2)
+  }
+
+  /// Add a property setter to the class on the stack
+  fn op_setter(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    match (self.peek(1), self.peek(0)) {
+      (Value::Class(ref mut class), Value::Closure(_)) => {
+        let setter = self.peek(0);
+        class.add_setter(&Hooks::new(self), name, setter);
+      }
+      _ => panic!("Internal spacelox error. stack invalid for op_setter"),
+    }
+
+    self.pop();
+    Signal::Ok(ip + 2)
+  }
+
   /// Generate a new class
   fn op_class(&mut self, ip: u32) -> Signal {
     let slot = self.read_byte(ip + 1);
@@ -578,6 +1224,11 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     let name = self.read_string(slot);
     let super_class = self.pop().to_class();
 
+    // this is left on the stack as the getter's receiver
+    if let Some(getter) = super_class.get_getter(&name) {
+      return self.resolve_call(*getter, 0, ip + 2);
+    }
+
     self.bind_method(super_class, name, ip + 2)
   }
 
@@ -585,6 +1236,15 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     let mut class = self.peek(0).to_class();
 
     match self.peek(1) {
+      // instances of a subclass could not hold the native state these need
+      Value::Class(super_class)
+        if super_class == self.builtin.fiber || super_class == self.builtin.channel =>
+      {
+        self.runtime_error(&format!(
+          "Cannot inherit from builtin class {}.",
+          super_class.name
+        ))
+      }
       Value::Class(super_class) => {
         class.inherit(&Hooks::new(self), super_class);
 
@@ -595,6 +1255,106 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     }
   }
 
+  /// Generate a new trait
+  fn op_trait(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+    let trait_ = Value::Trait(self.gc.manage(Trait::new(name), self));
+    self.push(trait_);
+    Signal::Ok(ip + 2)
+  }
+
+  /// Add a required method to the trait on the stack
+  fn op_require(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+    let mut trait_ = self.peek(0).to_trait();
+
+    trait_.add_required(&Hooks::new(self), name);
+    Signal::Ok(ip + 2)
+  }
+
+  /// attach a doc comment to the class on top of the stack
+  fn op_doc(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let doc = self.read_string(slot);
+    let mut class = self.peek(0).to_class();
+
+    class.doc = Some(doc);
+    Signal::Ok(ip + 2)
+  }
+
+  /// Mix the traits on the stack into the class below them. Methods the
+  /// class defines itself win over trait methods, trait methods win over
+  /// inherited methods, and a method provided by more than one trait
+  /// must be defined by the class
+  fn op_mixin(&mut self, ip: u32) -> Signal {
+    let count = self.read_byte(ip + 1) as u32;
+    let mut class = self.peek(count).to_class();
+
+    let mut traits: Vec<Managed<Trait>> = Vec::with_capacity(count as usize);
+    for distance in (0..count).rev() {
+      match self.peek(distance) {
+        Value::Trait(trait_) => traits.push(trait_),
+        value => {
+          return self.runtime_error(&format!(
+            "Can only mix traits into a class, found {}.",
+            value.value_type()
+          ))
+        }
+      }
+    }
+
+    let mut provided: FnvHashMap<Managed<String>, (Value, Managed<Trait>)> = FnvHashMap::default();
+    for trait_ in &traits {
+      let mut methods = vec![];
+      trait_.for_each_method(|(name, method)| methods.push((*name, *method)));
+
+      for (name, method) in methods {
+        let own = match class.get_method(&name) {
+          Some(existing) => {
+            class
+              .super_class
+              .and_then(|super_class| super_class.get_method(&name).copied())
+              != Some(*existing)
+          }
+          None => false,
+        };
+
+        if own {
+          continue;
+        }
+
+        if let Some((_, other)) = provided.get(&name) {
+          return self.runtime_error(&format!(
+            "Method {} is provided by both {} and {}, class {} must define it.",
+            name, other.name, trait_.name, class.name
+          ));
+        }
+
+        provided.insert(name, (method, *trait_));
+      }
+    }
+
+    for (name, (method, _)) in provided {
+      class.add_method(&Hooks::new(self), name, method);
+    }
+
+    for trait_ in &traits {
+      for name in trait_.required() {
+        if class.get_method(name).is_none() {
+          return self.runtime_error(&format!(
+            "Class {} must implement method {} required by trait {}.",
+            class.name, name, trait_.name
+          ));
+        }
+      }
+    }
+
+    self.stack_top -= count as usize;
+    Signal::Ok(ip + 2)
+  }
+
   fn op_loop(&mut self, ip: u32) -> Signal {
     Signal::Ok(ip + 3 - self.read_short(ip + 1) as u32)
   }
@@ -608,6 +1368,15 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     Signal::Ok(ip + 3)
   }
 
+  fn op_jump_if_nil(&mut self, ip: u32) -> Signal {
+    let jump = self.read_short(ip + 1);
+    if let Value::Nil = self.peek(0) {
+      return Signal::Ok(ip + 3 + jump as u32);
+    }
+
+    Signal::Ok(ip + 3)
+  }
+
   fn op_jump(&mut self, ip: u32) -> Signal {
     let jump = self.read_short(ip + 1);
     Signal::Ok(ip + 3 + jump as u32)
@@ -617,48 +1386,51 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     let slot = self.read_byte(ip + 1);
     let name = self.read_string(slot);
     let global = self.pop();
-    self.globals.insert(name, global);
+    let mut module = self.current_frame.closure.module;
+    module.define_global(&Hooks::new(self), name, global);
     Signal::Ok(ip + 2)
   }
 
   fn op_set_index(&mut self, ip: u32) -> Signal {
     let mut target = self.peek(2);
+    if let Some(method) = self.special_method(target, self.special.set_index) {
+      return self.resolve_call(method, 2, ip + 1);
+    }
+
     let index = self.peek(1);
 
     match (&mut target, index) {
-      (Value::List(list), Value::Number(num)) => {
-        let rounded = num as usize;
-        if rounded >= list.len() {
-          return self.runtime_error(&format!(
-            "Index out of bounds. list was length {} but attempted to index with {}.",
-            list.len(),
-            rounded
-          ));
-        }
-
-        list[rounded] = self.pop();
-        self.pop();
-        Signal::Ok(ip + 1)
-      }
+      (Value::List(list), _) => match resolve_index("list", index, list.len()) {
+        Ok(resolved) => list[resolved] = self.peek(0),
+        Err(message) => return self.runtime_error(&message),
+      },
       (Value::Map(map), Value::Number(num)) => {
-        map.insert(Value::Number(use_sentinel_nan(num)), self.pop());
-        self.pop();
-        Signal::Ok(ip + 1)
+        map.insert(Value::Number(use_sentinel_nan(num)), self.peek(0));
       }
       (Value::Map(map), _) => {
-        map.insert(index, self.pop());
-        self.pop();
-        Signal::Ok(ip + 1)
+        map.insert(index, self.peek(0));
       }
-      _ => self.runtime_error(&format!("{} cannot be indexed", target.value_type())),
+      (Value::String(_), _) => return self.runtime_error("Strings cannot be modified by index."),
+      _ => return self.runtime_error(&format!("{} cannot be indexed", target.value_type())),
     }
+
+    // leave the assigned value on the stack
+    let value = self.pop();
+    self.stack_top -= 2;
+    self.push(value);
+    Signal::Ok(ip + 1)
   }
 
   fn op_set_global(&mut self, ip: u32) -> Signal {
     let slot = self.read_byte(ip + 1);
     let string = self.read_string(slot);
 
-    if self.globals.insert(string, self.peek(0)).is_none() {
+    let value = self.peek(0);
+    if self.current_frame.closure.module.set_global(string, value) {
+      return Signal::Ok(ip + 2);
+    }
+
+    if self.globals.insert(string, value).is_none() {
       self.globals.remove_entry(&string);
       return self.runtime_error(&format!("Undefined variable {}", string.as_str()));
     }
@@ -680,18 +1452,34 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     let mut value = self.peek(1);
     let name = self.read_string(slot);
 
-    if let Value::Instance(ref mut instance) = value {
-      let value = self.peek(0);
-      instance.set_field(&Hooks::new(self), name, value);
+    match value {
+      Value::Instance(ref mut instance) => {
+        if let Some(setter) = instance.class.get_setter(&name) {
+          return self.resolve_call(*setter, 1, ip + 2);
+        }
 
-      let popped = self.pop();
-      self.pop();
-      self.push(popped);
+        if instance.class.get_getter(&name).is_some() {
+          return self.runtime_error(&format!(
+            "Cannot set property {} which only has a getter.",
+            name.as_str()
+          ));
+        }
 
-      return Signal::Ok(ip + 2);
+        let value = self.peek(0);
+        instance.set_field(&Hooks::new(self), name, value);
+      }
+      Value::Class(ref mut class) => {
+        let value = self.peek(0);
+        class.set_field(&Hooks::new(self), name, value);
+      }
+      _ => return self.runtime_error("Only instances and classes have fields."),
     }
 
-    self.runtime_error("Only instances have fields.")
+    let popped = self.pop();
+    self.pop();
+    self.push(popped);
+
+    Signal::Ok(ip + 2)
   }
 
   fn op_set_upvalue(&mut self, ip: u32) -> Signal {
@@ -715,21 +1503,31 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   }
 
   fn op_get_index(&mut self, ip: u32) -> Signal {
+    if let Some(method) = self.special_method(self.peek(1), self.special.index) {
+      return self.resolve_call(method, 1, ip + 1);
+    }
+
     let index = self.pop();
     let target = self.pop();
 
     match (target, index) {
-      (Value::List(list), Value::Number(num)) => {
-        let rounded = num as usize;
-        if rounded >= list.len() {
-          return self.runtime_error(&format!(
-            "Index out of bounds. list was length 0 but attempted to index with {}.",
-            rounded
-          ));
+      (Value::List(list), _) => match resolve_index("list", index, list.len()) {
+        Ok(resolved) => {
+          self.push(list[resolved]);
+          Signal::Ok(ip + 1)
+        }
+        Err(message) => self.runtime_error(&message),
+      },
+      (Value::String(string), _) => {
+        let chars: Vec<char> = string.chars().collect();
+        match resolve_index("string", index, chars.len()) {
+          Ok(resolved) => {
+            let string = self.gc.manage_str(chars[resolved].to_string(), self);
+            self.push(Value::String(string));
+            Signal::Ok(ip + 1)
+          }
+          Err(message) => self.runtime_error(&message),
         }
-
-        self.push(list[rounded]);
-        Signal::Ok(ip + 1)
       }
       (Value::Map(map), Value::Number(num)) => {
         match map.get(&Value::Number(use_sentinel_nan(num))) {
@@ -751,11 +1549,195 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     }
   }
 
+  /// slice a list or string into a new list or string
+  fn op_slice(&mut self, ip: u32) -> Signal {
+    let (start, stop, step) = (self.peek(2), self.peek(1), self.peek(0));
+
+    let slice = match self.peek(3) {
+      Value::List(list) => {
+        let indices = match slice_indices(list.len(), start, stop, step) {
+          Ok(indices) => indices,
+          Err(message) => return self.runtime_error(&message),
+        };
+
+        let mut slice = self.gc.manage(Vec::with_capacity(indices.len()), self);
+        slice.extend(indices.iter().map(|index| list[*index]));
+        Value::List(slice)
+      }
+      Value::String(string) => {
+        let chars: Vec<char> = string.chars().collect();
+        let indices = match slice_indices(chars.len(), start, stop, step) {
+          Ok(indices) => indices,
+          Err(message) => return self.runtime_error(&message),
+        };
+
+        let result: String = indices.iter().map(|index| chars[*index]).collect();
+        Value::String(self.gc.manage_str(result, self))
+      }
+      target => {
+        return self.runtime_error(&format!("{} cannot be sliced", target.value_type()));
+      }
+    };
+
+    self.stack_top -= 4;
+    self.push(slice);
+    Signal::Ok(ip + 1)
+  }
+
+  /// create a range between the top two values of the stack
+  fn op_range(&mut self, ip: u32, inclusive: bool) -> Signal {
+    let (start, end) = match (to_integer(self.peek(1)), to_integer(self.peek(0))) {
+      (Some(start), Some(end)) => (start, end),
+      _ => return self.runtime_error("Range bounds must be integers."),
+    };
+
+    let range = self.gc.manage(Range::new(start, end, inclusive), self);
+    self.stack_top -= 2;
+    self.push(Value::Range(range));
+    Signal::Ok(ip + 1)
+  }
+
+  /// check if a list has a given length, or at least that length for rest patterns
+  fn op_match_list(&mut self, ip: u32, rest: bool) -> Signal {
+    let len = self.read_byte(ip + 1) as usize;
+    let matched = match self.pop() {
+      Value::List(list) => list.len() == len || (rest && list.len() > len),
+      _ => false,
+    };
+
+    self.push(Value::Bool(matched));
+    Signal::Ok(ip + 2)
+  }
+
+  /// check if a value is a map
+  fn op_match_map(&mut self, ip: u32) -> Signal {
+    let matched = matches!(self.pop(), Value::Map(_));
+    self.push(Value::Bool(matched));
+    Signal::Ok(ip + 1)
+  }
+
+  /// check if a map contains a key
+  fn op_match_key(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let key = match self.read_constant(slot) {
+      Value::Number(num) => Value::Number(use_sentinel_nan(num)),
+      key => key,
+    };
+
+    let map = self.pop().to_map();
+    self.push(Value::Bool(map.contains_key(&key)));
+    Signal::Ok(ip + 2)
+  }
+
+  /// check if a value is an instance of a class or one of its subclasses
+  fn op_match_class(&mut self, ip: u32) -> Signal {
+    let class = match self.pop() {
+      Value::Class(class) => class,
+      _ => return self.runtime_error("Pattern must be a class."),
+    };
+
+    let matched = match self.pop() {
+      Value::Instance(instance) => {
+        let mut current = Some(instance.class);
+        let mut matched = false;
+
+        while let Some(candidate) = current {
+          if candidate == class {
+            matched = true;
+            break;
+          }
+          current = candidate.super_class;
+        }
+
+        matched
+      }
+      _ => false,
+    };
+
+    self.push(Value::Bool(matched));
+    Signal::Ok(ip + 1)
+  }
+
+  /// check if an instance has a field or getter
+  fn op_match_field(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    let instance = self.pop().to_instance();
+    let matched = instance.get_field(&name).is_some() || instance.class.get_getter(&name).is_some();
+
+    self.push(Value::Bool(matched));
+    Signal::Ok(ip + 2)
+  }
+
+  /// collect the remaining elements of a list from an index
+  fn op_list_rest(&mut self, ip: u32) -> Signal {
+    let start = self.read_byte(ip + 1) as usize;
+    let list = self.peek(0).to_list();
+
+    let rest = if start < list.len() {
+      list[start..].to_vec()
+    } else {
+      Vec::new()
+    };
+
+    let rest = Value::List(self.gc.manage(rest, self));
+    self.pop();
+    self.push(rest);
+    Signal::Ok(ip + 2)
+  }
+
+  /// check a value can be destructured into a list of a given length, or
+  /// at least that length for rest patterns
+  fn op_destructure_list(&mut self, ip: u32, rest: bool) -> Signal {
+    let len = self.read_byte(ip + 1) as usize;
+
+    match self.pop() {
+      Value::List(list) => {
+        if list.len() == len || (rest && list.len() > len) {
+          Signal::Ok(ip + 2)
+        } else if rest {
+          self.runtime_error(&format!(
+            "Cannot destructure list of length {} into at least {} elements.",
+            list.len(),
+            len
+          ))
+        } else {
+          self.runtime_error(&format!(
+            "Cannot destructure list of length {} into {} elements.",
+            list.len(),
+            len
+          ))
+        }
+      }
+      value => self.runtime_error(&format!(
+        "Cannot destructure {} as a list.",
+        value.value_type()
+      )),
+    }
+  }
+
+  /// check a value can be destructured as a map
+  fn op_destructure_map(&mut self, ip: u32) -> Signal {
+    match self.pop() {
+      Value::Map(_) => Signal::Ok(ip + 1),
+      value => self.runtime_error(&format!(
+        "Cannot destructure {} as a map.",
+        value.value_type()
+      )),
+    }
+  }
+
   fn op_get_global(&mut self, ip: u32) -> Signal {
     let store_index = self.read_byte(ip + 1);
     let string = self.read_string(store_index);
 
-    match self.globals.get(&string) {
+    let module = self.current_frame.closure.module;
+    let global = module
+      .get_global(&string)
+      .or_else(|| self.globals.get(&string));
+
+    match global {
       Some(gbl) => {
         let copy = *gbl;
         self.push(copy);
@@ -792,24 +1774,51 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     let name = self.read_string(slot);
 
     match value {
-      Value::Instance(instance) => match instance.get_field(&name) {
+      Value::Instance(instance) => {
+        if let Some(getter) = instance.class.get_getter(&name) {
+          return self.resolve_call(*getter, 0, ip + 2);
+        }
+
+        match instance.get_field(&name) {
+          Some(value) => {
+            self.set_val(self.stack_top - 1, *value);
+            Signal::Ok(ip + 2)
+          }
+          None => self.bind_method(instance.class, name, ip + 2),
+        }
+      }
+      Value::Class(class) => match class.get_field(&name) {
+        Some(value) => {
+          self.set_val(self.stack_top - 1, *value);
+          Signal::Ok(ip + 2)
+        }
+        None => self.bind_static_method(class, name, ip + 2),
+      },
+      Value::Module(module) => match module.get_export(&name) {
         Some(value) => {
           self.set_val(self.stack_top - 1, *value);
           Signal::Ok(ip + 2)
         }
-        None => self.bind_method(instance.class, name, ip + 2),
+        None => self.runtime_error(&format!(
+          "Module {} does not export {}.",
+          module.name.as_str(),
+          name.as_str()
+        )),
       },
-      Value::Bool(_) => self.bind_method(self.builtin.bool, name, ip),
-      Value::Number(_) => self.bind_method(self.builtin.number, name, ip),
-      Value::Nil => self.bind_method(self.builtin.nil, name, ip),
-      Value::String(_) => self.bind_method(self.builtin.string, name, ip),
-      Value::Fun(_) => self.bind_method(self.builtin.fun, name, ip),
+      Value::Bool(_) => self.bind_method(self.builtin.bool, name, ip + 2),
+      Value::Number(_) | Value::Int(_) => self.bind_method(self.builtin.number, name, ip + 2),
+      Value::BigInt(_) => self.bind_method(self.builtin.big_int, name, ip + 2),
+      Value::Nil => self.bind_method(self.builtin.nil, name, ip + 2),
+      Value::String(_) => self.bind_method(self.builtin.string, name, ip + 2),
+      Value::Fun(_) => self.bind_method(self.builtin.fun, name, ip + 2),
       Value::Closure(closure) => {
         self.set_val(self.stack_top - 1, Value::Fun(closure.fun));
-        self.bind_method(self.builtin.fun, name, ip)
+        self.bind_method(self.builtin.fun, name, ip + 2)
       }
-      Value::List(_) => self.bind_method(self.builtin.list, name, ip),
-      Value::NativeFun(_) => self.bind_method(self.builtin.native, name, ip),
+      Value::List(_) => self.bind_method(self.builtin.list, name, ip + 2),
+      Value::Range(_) => self.bind_method(self.builtin.range, name, ip + 2),
+      Value::Trait(_) => self.bind_method(self.builtin.trait_, name, ip + 2),
+      Value::NativeFun(_) => self.bind_method(self.builtin.native, name, ip + 2),
       _ => self.runtime_error(&format!("{} does not have properties.", value.value_type())),
     }
   }
@@ -817,16 +1826,47 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   /// return from a spacelox function placing the result on top of the stack
   fn op_return(&mut self, _: u32) -> Signal {
     // get the function result close upvalues and pop frame
-    let result = self.pop();
+    let mut result = self.pop();
     self.close_upvalues(NonNull::from(
       &self.stack[self.current_frame.slots as usize],
     ));
+
+    // a returning generator is done, whoever resumed it gets false
+    // unless they asked it to return
+    if let Some(resumed) = self.generators.last() {
+      if resumed.frame_count == self.frame_count {
+        let mut generator = resumed.generator;
+        generator.state = GeneratorState::Done;
+        generator.current = result;
+        if !resumed.returning {
+          result = Value::Bool(false);
+        }
+        self.generators.pop();
+      }
+    }
+
     self.frame_count -= 1;
 
-    // if the frame was the whole script signal an ok interrupt
+    // a finished module script results in the module itself
+    if let Some(import) = self.imports.last() {
+      if import.frame_count == self.frame_count {
+        result = Value::Module(import.module);
+        self.imports.pop();
+      }
+    }
+
+    // drop any handlers installed by the returning frame
+    while let Some(handler) = self.handlers.last() {
+      if handler.frame_count <= self.frame_count {
+        break;
+      }
+      self.handlers.pop();
+    }
+
+    // if the frame was the whole fiber or script the fiber is finished
     if self.frame_count == 0 {
       self.pop();
-      return Signal::Exit;
+      return self.finish_fiber(result);
     }
 
     // pull the current frame out of the stack and set the cached frame
@@ -840,11 +1880,17 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   }
 
   fn op_negate(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.unary_overload(self.special.neg, ip) {
+      return signal;
+    }
+
     match self.pop() {
       Value::Number(num) => {
         self.push(Value::Number(-num));
         Signal::Ok(ip + 1)
       }
+      Value::Int(int) => self.push_int(int.checked_neg(), ip),
+      Value::BigInt(big) => self.push_big(-(*big).clone(), ip),
       _ => self.runtime_error("Operand must be a number."),
     }
   }
@@ -856,6 +1902,10 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   }
 
   fn op_add(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.add, self.special.reflected_add), ip) {
+      return signal;
+    }
+
     match (self.pop(), self.pop()) {
       (Value::String(right), Value::String(left)) => {
         let result = format!("{}{}", left.as_str(), right.as_str());
@@ -863,135 +1913,533 @@ impl<'a, I: Io> VmExecutor<'a, I> {
         self.push(Value::String(string));
         Signal::Ok(ip + 1)
       }
-      (Value::Number(right), Value::Number(left)) => {
-        self.push(Value::Number(left + right));
-        Signal::Ok(ip + 1)
-      }
-      _ => self.runtime_error("Operands must be two numbers or two strings."),
+      (Value::Int(right), Value::Int(left)) => self.push_int(left.checked_add(right), ip),
+      (right, left) => match big_operands(left, right) {
+        Some((left, right)) => self.push_big(left + right, ip),
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Number(left + right));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be two numbers or two strings."),
+        },
+      },
     }
   }
 
   fn op_sub(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.sub, self.special.reflected_sub), ip) {
+      return signal;
+    }
+
     match (self.pop(), self.pop()) {
-      (Value::Number(right), Value::Number(left)) => {
-        self.push(Value::Number(left - right));
-        Signal::Ok(ip + 1)
-      }
-      _ => self.runtime_error("Operands must be numbers."),
+      (Value::Int(right), Value::Int(left)) => self.push_int(left.checked_sub(right), ip),
+      (right, left) => match big_operands(left, right) {
+        Some((left, right)) => self.push_big(left - right, ip),
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Number(left - right));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
     }
   }
 
   fn op_mul(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.mul, self.special.reflected_mul), ip) {
+      return signal;
+    }
+
     match (self.pop(), self.pop()) {
-      (Value::Number(right), Value::Number(left)) => {
-        self.push(Value::Number(left * right));
-        Signal::Ok(ip + 1)
-      }
-      _ => self.runtime_error("Operands must be numbers."),
+      (Value::Int(right), Value::Int(left)) => self.push_int(left.checked_mul(right), ip),
+      (right, left) => match big_operands(left, right) {
+        Some((left, right)) => self.push_big(left * right, ip),
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Number(left * right));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
     }
   }
 
+  /// divide always produces a float, integer division is done with '~/'
   fn op_div(&mut self, ip: u32) -> Signal {
-    match (self.pop(), self.pop()) {
-      (Value::Number(right), Value::Number(left)) => {
+    if let Some(signal) = self.binary_overload((self.special.div, self.special.reflected_div), ip) {
+      return signal;
+    }
+
+    let (right, left) = (self.pop(), self.pop());
+    match float_operands(left, right) {
+      Some((left, right)) => {
         self.push(Value::Number(left / right));
         Signal::Ok(ip + 1)
       }
-      _ => self.runtime_error("Operands must be numbers."),
+      None => self.runtime_error("Operands must be numbers."),
     }
   }
 
-  fn op_less(&mut self, ip: u32) -> Signal {
+  fn op_mod(&mut self, ip: u32) -> Signal {
+    if let Some(signal) =
+      self.binary_overload((self.special.modulo, self.special.reflected_modulo), ip)
+    {
+      return signal;
+    }
+
     match (self.pop(), self.pop()) {
-      (Value::Number(right), Value::Number(left)) => {
-        self.push(Value::Bool(left < right));
-        Signal::Ok(ip + 1)
-      }
-      _ => self.runtime_error("Operands must be numbers."),
+      (Value::Int(0), Value::Int(_)) => self.runtime_error("Division by zero."),
+      (Value::Int(right), Value::Int(left)) => self.push_int(floor_mod(left, right), ip),
+      (right, left) => match big_operands(left, right) {
+        Some((_, right)) if right.is_zero() => self.runtime_error("Division by zero."),
+        Some((left, right)) => self.push_big(big_floor_mod(left, right), ip),
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Number(float_floor_mod(left, right)));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
     }
   }
 
-  fn op_greater(&mut self, ip: u32) -> Signal {
+  fn op_pow(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.pow, self.special.reflected_pow), ip) {
+      return signal;
+    }
+
     match (self.pop(), self.pop()) {
-      (Value::Number(right), Value::Number(left)) => {
-        self.push(Value::Bool(left > right));
-        Signal::Ok(ip + 1)
+      (Value::Int(right), Value::Int(left)) if right >= 0 => {
+        let result = u32::try_from(right)
+          .ok()
+          .and_then(|right| left.checked_pow(right));
+        self.push_int(result, ip)
       }
-      _ => self.runtime_error("Operands must be numbers."),
+      (right, left) => match big_operands(left, right) {
+        Some((left, right)) if right.sign() != Sign::Minus => match u32::try_from(&right) {
+          Ok(right) => self.push_big(left.pow(right), ip),
+          Err(_) => self.runtime_error("Integer overflow."),
+        },
+        _ => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Number(left.powf(right)));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
     }
   }
 
-  fn op_equal(&mut self, ip: u32) -> Signal {
-    let right = self.pop();
-    let left = self.pop();
+  fn op_int_div(&mut self, ip: u32) -> Signal {
+    if let Some(signal) =
+      self.binary_overload((self.special.int_div, self.special.reflected_int_div), ip)
+    {
+      return signal;
+    }
 
-    self.push(Value::Bool(left == right));
-    Signal::Ok(ip + 1)
+    match (self.pop(), self.pop()) {
+      (Value::Int(0), Value::Int(_)) => self.runtime_error("Division by zero."),
+      (Value::Int(right), Value::Int(left)) => self.push_int(floor_div(left, right), ip),
+      (right, left) => match big_operands(left, right) {
+        Some((_, right)) if right.is_zero() => self.runtime_error("Division by zero."),
+        Some((left, right)) => self.push_big(big_floor_div(left, right), ip),
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Number((left / right).floor()));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
+    }
   }
 
-  fn op_method(&mut self, ip: u32) -> Signal {
-    let slot = self.read_byte(ip + 1);
-    let name = self.read_string(slot);
-
-    match (self.peek(1), self.peek(0)) {
-      (Value::Class(ref mut class), Value::Closure(_)) => {
-        let method = self.peek(0);
-        class.add_method(&Hooks::new(self), name, method);
-      }
-      _ => panic!("Internal spacelox error. stack invalid for op_method"),
+  /// apply a bitwise operator to the top two integral operands, using
+  /// `big_op` if either is a big int. The operators return None when a
+  /// shift is out of range
+  fn op_bitwise(
+    &mut self,
+    ip: u32,
+    methods: (Managed<String>, Managed<String>),
+    op: fn(i64, i64) -> Option<i64>,
+    big_op: fn(&BigInt, &BigInt) -> Option<BigInt>,
+  ) -> Signal {
+    if let Some(signal) = self.binary_overload(methods, ip) {
+      return signal;
     }
 
-    self.pop();
-    Signal::Ok(ip + 2)
-  }
-
-  fn op_closure(&mut self, ip: u32) -> Signal {
-    let slot = self.read_byte(ip + 1);
-    let fun = self.read_constant(slot).to_fun();
-    let mut closure = Closure::new(fun);
-    let mut current_ip = ip + 2;
+    let right = self.pop();
+    let left = self.pop();
 
-    for _ in 0..fun.upvalue_count {
-      let upvalue_index: UpvalueIndex = unsafe { mem::transmute(self.read_short(current_ip)) };
+    if let (Value::BigInt(_), _) | (_, Value::BigInt(_)) = (left, right) {
+      return match (to_big_integer(left), to_big_integer(right)) {
+        (Some(left), Some(right)) => match big_op(&left, &right) {
+          Some(result) => self.push_big(result, ip),
+          None => self.runtime_error("Shift amount out of range."),
+        },
+        _ => self.runtime_error("Operands must be integers or big ints."),
+      };
+    }
 
-      match upvalue_index {
-        UpvalueIndex::Local(index) => {
-          let total_index = self.current_frame.slots as usize + index as usize;
-          closure
-            .upvalues
-            .push(self.capture_upvalue(NonNull::from(&self.stack[total_index])));
-        }
-        UpvalueIndex::Upvalue(upvalue) => {
-          let upvalue = self.current_frame.closure.upvalues[upvalue as usize];
-          closure.upvalues.push(upvalue);
+    match (to_integer(left), to_integer(right)) {
+      (Some(left), Some(right)) => match op(left, right) {
+        Some(result) => {
+          self.push(Value::Int(result));
+          Signal::Ok(ip + 1)
         }
-      }
-
-      current_ip += 2;
+        None => self.runtime_error("Shift amount must be between 0 and 63."),
+      },
+      _ => self.runtime_error("Operands must be integers."),
     }
-
-    let closure = Value::Closure(self.gc.manage(closure, self));
-    self.push(closure);
-    Signal::Ok(current_ip)
   }
 
-  fn op_close_upvalue(&mut self, ip: u32) -> Signal {
-    self.close_upvalues(NonNull::from(&self.stack[self.stack_top as usize - 1]));
-    self.pop();
-    Signal::Ok(ip + 1)
-  }
+  fn op_bit_not(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.unary_overload(self.special.bit_not, ip) {
+      return signal;
+    }
 
-  fn op_print(&mut self, ip: u32) -> Signal {
-    self.io.stdio().println(&format!("{}", self.pop()));
-    Signal::Ok(ip + 1)
+    match self.pop() {
+      Value::BigInt(big) => self.push_big(!&*big, ip),
+      value => match to_integer(value) {
+        Some(int) => {
+          self.push(Value::Int(!int));
+          Signal::Ok(ip + 1)
+        }
+        None => self.runtime_error("Operand must be an integer."),
+      },
+    }
   }
 
-  fn op_constant(&mut self, ip: u32) -> Signal {
-    let slot = self.read_byte(ip + 1);
-    let constant = self.read_constant(slot);
-    self.push(constant);
-    Signal::Ok(ip + 2)
-  }
+  fn op_less(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.less, self.special.greater), ip) {
+      return signal;
+    }
+
+    match (self.pop(), self.pop()) {
+      (Value::Int(right), Value::Int(left)) => {
+        self.push(Value::Bool(left < right));
+        Signal::Ok(ip + 1)
+      }
+      (Value::Number(right), Value::Int(left)) => {
+        let result = compare_int_float(left, right) == Some(Ordering::Less);
+        self.push(Value::Bool(result));
+        Signal::Ok(ip + 1)
+      }
+      (Value::Int(right), Value::Number(left)) => {
+        let result = compare_int_float(right, left) == Some(Ordering::Greater);
+        self.push(Value::Bool(result));
+        Signal::Ok(ip + 1)
+      }
+      (right, left) => match big_operands(left, right) {
+        Some((left, right)) => {
+          self.push(Value::Bool(left < right));
+          Signal::Ok(ip + 1)
+        }
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Bool(left < right));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
+    }
+  }
+
+  fn op_greater(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.greater, self.special.less), ip) {
+      return signal;
+    }
+
+    match (self.pop(), self.pop()) {
+      (Value::Int(right), Value::Int(left)) => {
+        self.push(Value::Bool(left > right));
+        Signal::Ok(ip + 1)
+      }
+      (Value::Number(right), Value::Int(left)) => {
+        let result = compare_int_float(left, right) == Some(Ordering::Greater);
+        self.push(Value::Bool(result));
+        Signal::Ok(ip + 1)
+      }
+      (Value::Int(right), Value::Number(left)) => {
+        let result = compare_int_float(right, left) == Some(Ordering::Less);
+        self.push(Value::Bool(result));
+        Signal::Ok(ip + 1)
+      }
+      (right, left) => match big_operands(left, right) {
+        Some((left, right)) => {
+          self.push(Value::Bool(left > right));
+          Signal::Ok(ip + 1)
+        }
+        None => match float_operands(left, right) {
+          Some((left, right)) => {
+            self.push(Value::Bool(left > right));
+            Signal::Ok(ip + 1)
+          }
+          None => self.runtime_error("Operands must be numbers."),
+        },
+      },
+    }
+  }
+
+  /// Find a special method on the class of an instance operand
+  fn special_method(&self, operand: Value, name: Managed<String>) -> Option<Value> {
+    match operand {
+      Value::Instance(instance) => instance.class.get_method(&name).copied(),
+      _ => None,
+    }
+  }
+
+  /// Dispatch a binary operator to the left operand's special method, or
+  /// failing that to the right operand's reflected method with the
+  /// operands swapped. Returns None if neither operand overloads it
+  fn binary_overload(
+    &mut self,
+    (method, reflected): (Managed<String>, Managed<String>),
+    ip: u32,
+  ) -> Option<Signal> {
+    let left = self.peek(1);
+    let right = self.peek(0);
+
+    if let Some(method) = self.special_method(left, method) {
+      return Some(self.resolve_call(method, 1, ip + 1));
+    }
+
+    if let Some(method) = self.special_method(right, reflected) {
+      self.set_val(self.stack_top - 2, right);
+      self.set_val(self.stack_top - 1, left);
+      return Some(self.resolve_call(method, 1, ip + 1));
+    }
+
+    None
+  }
+
+  /// Dispatch a unary operator to the operand's special method
+  fn unary_overload(&mut self, method: Managed<String>, ip: u32) -> Option<Signal> {
+    let operand = self.peek(0);
+
+    self
+      .special_method(operand, method)
+      .map(|method| self.resolve_call(method, 0, ip + 1))
+  }
+
+  /// push the result of integer arithmetic raising an error if it overflowed
+  fn push_int(&mut self, result: Option<i64>, ip: u32) -> Signal {
+    match result {
+      Some(int) => {
+        self.push(Value::Int(int));
+        Signal::Ok(ip + 1)
+      }
+      None => self.runtime_error("Integer overflow."),
+    }
+  }
+
+  /// push the result of big int arithmetic
+  fn push_big(&mut self, result: BigInt, ip: u32) -> Signal {
+    let big = self.gc.manage(result, self);
+    self.push(Value::BigInt(big));
+    Signal::Ok(ip + 1)
+  }
+
+  fn op_equal(&mut self, ip: u32) -> Signal {
+    if let Some(signal) = self.binary_overload((self.special.equal, self.special.equal), ip) {
+      return signal;
+    }
+
+    let right = self.pop();
+    let left = self.pop();
+
+    self.push(Value::Bool(left == right));
+    Signal::Ok(ip + 1)
+  }
+
+  fn op_method(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    match (self.peek(1), self.peek(0)) {
+      (Value::Class(ref mut class), Value::Closure(_)) => {
+        let method = self.peek(0);
+        class.add_method(&Hooks::new(self), name, method);
+      }
+      (Value::Trait(ref mut trait_), Value::Closure(_)) => {
+        let method = self.peek(0);
+        trait_.add_method(&Hooks::new(self), name, method);
+      }
+      _ => panic!("Internal spacelox error. stack invalid for op_method"),
+    }
+
+    self.pop();
+    Signal::Ok(ip + 2)
+  }
+
+  fn op_closure(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let fun = self.read_constant(slot).to_fun();
+    let mut closure = Closure::new(fun, self.current_frame.closure.module);
+    let mut current_ip = ip + 2;
+
+    for _ in 0..fun.upvalue_count {
+      let upvalue_index: UpvalueIndex = unsafe { mem::transmute(self.read_short(current_ip)) };
+
+      match upvalue_index {
+        UpvalueIndex::Local(index) => {
+          let total_index = self.current_frame.slots as usize + index as usize;
+          closure
+            .upvalues
+            .push(self.capture_upvalue(NonNull::from(&self.stack[total_index])));
+        }
+        UpvalueIndex::Upvalue(upvalue) => {
+          let upvalue = self.current_frame.closure.upvalues[upvalue as usize];
+          closure.upvalues.push(upvalue);
+        }
+      }
+
+      current_ip += 2;
+    }
+
+    let closure = Value::Closure(self.gc.manage(closure, self));
+    self.push(closure);
+    Signal::Ok(current_ip)
+  }
+
+  fn op_close_upvalue(&mut self, ip: u32) -> Signal {
+    self.close_upvalues(NonNull::from(&self.stack[self.stack_top - 1]));
+    self.pop();
+    Signal::Ok(ip + 1)
+  }
+
+  fn op_print(&mut self, ip: u32) -> Signal {
+    self.io.stdio().println(&format!("{}", self.pop()));
+    Signal::Ok(ip + 1)
+  }
+
+  fn op_constant(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let constant = self.read_constant(slot);
+    self.push(constant);
+    Signal::Ok(ip + 2)
+  }
+
+  /// import a module loading and running its script if not already loaded
+  fn op_import(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    let path = match self.resolve_module(&name) {
+      Some(path) => path,
+      None => return self.runtime_error(&format!("Could not find module {}.", name.as_str())),
+    };
+
+    if let Some(module) = self.modules.get(&path) {
+      let module = *module;
+      if self.imports.iter().any(|import| import.module == module) {
+        return self.runtime_error(&format!("Cyclic import of module {}.", name.as_str()));
+      }
+
+      self.push(Value::Module(module));
+      return Signal::Ok(ip + 2);
+    }
+
+    let fun = match read_to_string(&path) {
+      Ok(source) => match self.compile_module(&source) {
+        Some(fun) => fun,
+        None => return self.runtime_error(&format!("Could not compile module {}.", name.as_str())),
+      },
+      Err(_) => return self.runtime_error(&format!("Could not read module {}.", name.as_str())),
+    };
+
+    self.push(Value::Fun(fun));
+    let module = self.gc.manage(Module::new(name, Some(path.clone())), self);
+    self.push(Value::Module(module));
+    let closure = self.gc.manage(Closure::new(fun, module), self);
+    self.stack_top -= 2;
+    self.push(Value::Closure(closure));
+
+    self.modules.insert(path, module);
+    self.imports.push(Import {
+      module,
+      frame_count: self.frame_count,
+    });
+
+    self.call(closure, 0, ip + 2)
+  }
+
+  /// export a global from the current module
+  fn op_export(&mut self, ip: u32) -> Signal {
+    let slot = self.read_byte(ip + 1);
+    let name = self.read_string(slot);
+
+    let mut module = self.current_frame.closure.module;
+    module.export(&Hooks::new(self), name);
+    Signal::Ok(ip + 2)
+  }
+
+  /// Find the file for an imported module first relative to the importing
+  /// module then in each search path
+  fn resolve_module(&self, name: &str) -> Option<PathBuf> {
+    let module = self.current_frame.closure.module;
+    let directory = module
+      .path()
+      .and_then(Path::parent)
+      .map(Path::to_path_buf)
+      .unwrap_or_default();
+
+    std::iter::once(&directory)
+      .chain(self.search_paths.iter())
+      .map(|search_path| search_path.join(name))
+      .find(|path| path.is_file())
+      .and_then(|path| path.canonicalize().ok())
+  }
+
+  /// Compile the source of an imported module
+  fn compile_module(&self, source: &str) -> Option<Managed<Fun>> {
+    let mut parser = Parser::new(self.io.stdio(), source);
+
+    let mut compiler_context = NoContext::new(self.gc);
+    let hooks = Hooks::new(&mut compiler_context);
+
+    let compiler = Compiler::new(*self.io, &mut parser, &hooks);
+    let result = compiler.compile();
+
+    if result.success {
+      Some(result.fun)
+    } else {
+      None
+    }
+  }
+
+  /// Forget any module whose script was unwound before it finished loading
+  fn unwind_imports(&mut self) {
+    while let Some(import) = self.imports.last() {
+      if import.frame_count < self.frame_count {
+        break;
+      }
+
+      if let Some(path) = import.module.path() {
+        self.modules.remove(path);
+      }
+      self.imports.pop();
+    }
+  }
+
+  /// Finish any generators whose frames have been unwound
+  fn unwind_generators(&mut self) {
+    while let Some(resumed) = self.generators.last() {
+      if resumed.frame_count <= self.frame_count {
+        break;
+      }
+
+      let mut generator = resumed.generator;
+      generator.state = GeneratorState::Done;
+      self.generators.pop();
+    }
+  }
 
   fn resolve_call(&mut self, callee: Value, arg_count: u8, ip: u32) -> Signal {
     match callee {
@@ -1006,6 +2454,10 @@ impl<'a, I: Io> VmExecutor<'a, I> {
   }
 
   fn call_class(&mut self, class: Managed<Class>, arg_count: u8, ip: u32) -> Signal {
+    if class == self.builtin.fiber || class == self.builtin.channel {
+      return self.call_builtin_class(class, arg_count, ip);
+    }
+
     let value = Value::Instance(self.gc.manage(Instance::new(class), self));
     self.set_val(self.stack_top - (arg_count as usize) - 1, value);
 
@@ -1021,98 +2473,842 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     }
   }
 
-  /// call a native function immediately returning the result
-  fn call_native_fun(
+  /// call a native function immediately returning the result
+  fn call_native_fun(
+    &mut self,
+    native: Managed<Box<dyn NativeFun>>,
+    arg_count: u8,
+    ip: u32,
+  ) -> Signal {
+    let meta = native.meta();
+
+    // check that the current function is called with the right number of args
+    if let Some(error) = self.check_arity(meta.arity, arg_count, || String::from(meta.name)) {
+      return error;
+    }
+
+    let args = Vec::from(unsafe {
+      self
+        .stack
+        .get_unchecked((self.stack_top - arg_count as usize)..self.stack_top)
+    });
+
+    match native.call(&Hooks::new(self), &args) {
+      NativeResult::Success(value) => {
+        self.stack_top -= arg_count as usize + 1;
+        self.push(value);
+        Signal::Ok(ip)
+      }
+      NativeResult::RuntimeError(message) => self.runtime_error(&message),
+    }
+  }
+
+  /// call a native method immediately returning the result
+  fn call_native_method(
+    &mut self,
+    native: Managed<Box<dyn NativeMethod>>,
+    arg_count: u8,
+    ip: u32,
+  ) -> Signal {
+    let meta = native.meta();
+
+    // check that the current function is called with the right number of args
+    if let Some(error) = self.check_arity(meta.arity, arg_count, || String::from(meta.name)) {
+      return error;
+    }
+
+    let args: Vec<Value> = Vec::from(unsafe {
+      self
+        .stack
+        .get_unchecked((self.stack_top - arg_count as usize)..self.stack_top)
+    });
+    let this = self.get_val(self.stack_top - arg_count as usize - 1);
+
+    match native.call(
+      &Hooks::new(self),
+      this,
+      &args,
+    ) {
+      NativeResult::Success(value) => {
+        self.stack_top -= arg_count as usize + 1;
+        self.push(value);
+        Signal::Ok(ip)
+      }
+      NativeResult::RuntimeError(message) => self.runtime_error(&message),
+    }
+  }
+
+  /// call a spacelox function setting it as the new call frame
+  fn call(&mut self, closure: Managed<Closure>, arg_count: u8, ip: u32) -> Signal {
+    // check that the current function is called with the right number of args
+    if let Some(error) = self.check_arity(closure.fun.arity, arg_count, || {
+      closure.fun.name.to_string()
+    }) {
+      return error;
+    }
+
+    // set the current current instruction pointer. check for overflow
+    match self.frame_count {
+      0 => (),
+      FRAME_MAX => {
+        return self.runtime_error("Stack overflow.");
+      }
+      _ => self.current_mut_frame().ip = ip,
+    }
+
+    let (arg_count, start) = self.prepare_args(closure.fun, arg_count);
+
+    if closure.fun.is_generator {
+      return self.create_generator(closure, arg_count, start, ip);
+    }
+
+    let slots = self.stack_top as u32 - (arg_count as u32 + 1);
+    let frame = self.next_frame(closure);
+    frame.closure = closure;
+    frame.ip = start;
+    frame.slots = slots;
+
+    self.current_frame = *frame;
+    self.current_fun = closure.fun;
+    self.frame_count += 1;
+    Signal::Ok(start)
+  }
+
+  /// Package the callee and arguments of a call to a generator function
+  /// into a generator that will start executing when first resumed
+  fn create_generator(
+    &mut self,
+    closure: Managed<Closure>,
+    arg_count: u8,
+    start: u32,
+    ip: u32,
+  ) -> Signal {
+    let slots = self.stack_top - (arg_count as usize + 1);
+    let frame = self.stack[slots..self.stack_top].to_vec();
+    let generator = self.gc.manage(Generator::new(closure, start, frame), self);
+
+    self.stack_top = slots;
+    self.push(Value::Generator(generator));
+    Signal::Ok(ip)
+  }
+
+  /// Call a method on a generator. Resuming a generator pushes its saved
+  /// frame back onto the frame stack
+  fn invoke_generator(
+    &mut self,
+    generator: Managed<Generator>,
+    method_name: Managed<String>,
+    arg_count: u8,
+    ip: u32,
+  ) -> Signal {
+    let arity = match method_name.as_str() {
+      NEXT | CURRENT | ITER => ArityKind::Fixed(0),
+      SEND => ArityKind::Fixed(1),
+      RETURN => ArityKind::Default(0, 1),
+      _ => return self.runtime_error(&format!("Undefined property {}.", method_name.as_str())),
+    };
+
+    if let Some(error) = self.check_arity(arity, arg_count, || method_name.to_string()) {
+      return error;
+    }
+
+    match method_name.as_str() {
+      NEXT | SEND => self.resume(generator, arg_count, ip),
+      CURRENT => {
+        self.set_val(self.stack_top - 1, generator.current);
+        Signal::Ok(ip)
+      }
+      ITER => Signal::Ok(ip),
+      _ => {
+        if generator.state == GeneratorState::Running {
+          return self.runtime_error("Generator is already running.");
+        }
+
+        self.return_generator(generator, arg_count, ip)
+      }
+    }
+  }
+
+  /// Stop a generator that is not running as if it returned the value on
+  /// top of the stack, or nil without one
+  fn return_generator(
+    &mut self,
+    mut generator: Managed<Generator>,
+    arg_count: u8,
+    ip: u32,
+  ) -> Signal {
+    // finally blocks around the suspended yield run before it returns
+    if generator.state == GeneratorState::Suspended && !generator.handlers.is_empty() {
+      return self.resume_return(generator, arg_count, ip);
+    }
+
+    let value = if arg_count == 1 {
+      self.pop()
+    } else {
+      Value::Nil
+    };
+    generator.state = GeneratorState::Done;
+    generator.current = value;
+    generator.slots.clear();
+    generator.upvalues.clear();
+    generator.handlers.clear();
+
+    self.set_val(self.stack_top - 1, value);
+    Signal::Ok(ip)
+  }
+
+  /// Resume a generator from where it last yielded. The generator's frame
+  /// replaces the receiver and any value sent on the stack
+  fn resume(&mut self, mut generator: Managed<Generator>, arg_count: u8, ip: u32) -> Signal {
+    match generator.state {
+      GeneratorState::Running => return self.runtime_error("Generator is already running."),
+      GeneratorState::Done => {
+        self.stack_top -= arg_count as usize;
+        self.set_val(self.stack_top - 1, Value::Bool(false));
+        return Signal::Ok(ip);
+      }
+      _ => (),
+    }
+
+    let sent = if arg_count == 1 {
+      self.peek(0)
+    } else {
+      Value::Nil
+    };
+    let slots = self.stack_top - (arg_count as usize + 1);
+
+    let len = slots + generator.slots.len() + 1;
+    if self.frame_count == FRAME_MAX || len > DEFAULT_STACK_MAX {
+      return self.runtime_error("Stack overflow.");
+    }
+    if len > self.stack.len() {
+      self.grow_stack(len);
+    }
+
+    self.current_mut_frame().ip = ip;
+
+    // restore the frame's stack slots, a suspended upvalue holds the
+    // latest value of the slot it refers to
+    for (offset, value) in generator.slots.iter().enumerate() {
+      self.set_val(slots + offset, *value);
+    }
+    self.stack_top = slots + generator.slots.len();
+
+    for (offset, mut upvalue) in generator.upvalues.drain(..) {
+      if let Upvalue::Closed(store) = &*upvalue {
+        self.stack[slots + offset] = **store;
+      }
+
+      *upvalue = Upvalue::Open(NonNull::from(&self.stack[slots + offset]));
+      self.open_upvalues.push(upvalue);
+    }
+
+    let frame_count = self.frame_count + 1;
+    for (offset, handler_ip) in generator.handlers.drain(..) {
+      self.handlers.push(Handler {
+        frame_count,
+        stack_top: slots + offset,
+        ip: handler_ip,
+      });
+    }
+
+    let frame = self.next_frame(generator.closure);
+    frame.closure = generator.closure;
+    frame.ip = generator.ip;
+    frame.slots = slots as u32;
+
+    self.current_frame = *frame;
+    self.current_fun = generator.closure.fun;
+    self.frame_count = frame_count;
+
+    // the value sent becomes the result of the suspended yield
+    if generator.state == GeneratorState::Suspended {
+      self.push(sent);
+    }
+
+    generator.state = GeneratorState::Running;
+    generator.slots.clear();
+    self.generators.push(Resumed {
+      generator,
+      frame_count,
+      returning: false,
+    });
+
+    Signal::Ok(generator.ip)
+  }
+
+  /// Resume a suspended generator as if its yield were a return, entering
+  /// the innermost handler so the enclosing finally blocks run
+  fn resume_return(&mut self, generator: Managed<Generator>, arg_count: u8, ip: u32) -> Signal {
+    let signal = self.resume(generator, arg_count, ip);
+    if generator.state != GeneratorState::Running {
+      return signal;
+    }
+
+    let value = self.pop();
+    if let Some(resumed) = self.generators.last_mut() {
+      resumed.returning = true;
+    }
+
+    match self.handlers.pop() {
+      Some(handler) => self.enter_handler(handler, value, Value::Bool(false)),
+      None => panic!("Internal spacelox error. resumed generator has no handlers"),
+    }
+  }
+
+  /// Close the iterator of a for-in loop left before it was exhausted. A
+  /// suspended generator returns so its pending finally blocks run, any
+  /// other iterator is left as is
+  fn op_close_iterator(&mut self, ip: u32) -> Signal {
+    match self.peek(0) {
+      Value::Generator(generator) if generator.state == GeneratorState::Suspended => {
+        self.return_generator(generator, 0, ip + 1)
+      }
+      _ => Signal::Ok(ip + 1),
+    }
+  }
+
+  /// Suspend the running generator, saving its frame and passing the
+  /// yielded value to whoever resumed it
+  fn op_yield(&mut self, ip: u32) -> Signal {
+    let mut generator = match self.generators.last() {
+      Some(resumed) if resumed.frame_count == self.frame_count => resumed.generator,
+      _ => panic!("Internal spacelox error. yield outside of a running generator"),
+    };
+
+    generator.current = self.pop();
+    generator.ip = ip + 1;
+    generator.state = GeneratorState::Suspended;
+
+    let slots = self.current_frame.slots as usize;
+    let base = NonNull::from(&self.stack[slots]);
+
+    // close the frame's upvalues remembering which slot each refers to
+    let mut upvalues = vec![];
+    for upvalue in self.open_upvalues.iter_mut() {
+      if let Upvalue::Open(index) = **upvalue {
+        if index >= base {
+          let offset = (index.as_ptr() as usize - base.as_ptr() as usize) / mem::size_of::<Value>();
+          upvalue.hoist();
+          upvalues.push((offset, *upvalue));
+        }
+      }
+    }
+    self.open_upvalues.retain(|upvalue| upvalue.is_open());
+
+    let mut handlers = vec![];
+    while let Some(handler) = self.handlers.last() {
+      if handler.frame_count < self.frame_count {
+        break;
+      }
+      handlers.push((handler.stack_top - slots, handler.ip));
+      self.handlers.pop();
+    }
+    handlers.reverse();
+
+    let frame = &self.stack[slots..self.stack_top];
+    self.gc.resize(&mut *generator, self, |generator| {
+      generator.slots.extend_from_slice(frame);
+      generator.upvalues.extend_from_slice(&upvalues);
+      generator.handlers.extend_from_slice(&handlers);
+    });
+
+    self.generators.pop();
+    self.frame_count -= 1;
+    self.stack_top = slots;
+    self.current_frame = *self.current_frame();
+    self.current_fun = self.current_frame.closure.fun;
+
+    self.push(Value::Bool(true));
+    Signal::OkReturn(self.current_frame.ip)
+  }
+
+  /// Create a fiber that will run the provided function
+  fn create_fiber(&mut self, fun: Value) -> Result<Managed<Fiber>, Signal> {
+    let closure = match fun {
+      Value::Closure(closure) if closure.fun.is_generator => {
+        return Err(self.runtime_error("Cannot run a generator function in a fiber."));
+      }
+      Value::Closure(closure) => closure,
+      _ => {
+        return Err(self.runtime_error(&format!(
+          "Fiber expected a function but got {}.",
+          fun.value_type()
+        )))
+      }
+    };
+
+    // a fiber's function is handed the first value it is resumed with
+    match closure.fun.arity {
+      ArityKind::Fixed(0) | ArityKind::Fixed(1) => (),
+      ArityKind::Default(min, _) | ArityKind::Variadic(min) if min <= 1 => (),
+      _ => {
+        return Err(self.runtime_error(&format!(
+          "Fiber function {} can take at most one argument.",
+          closure.fun.name
+        )))
+      }
+    }
+
+    // fibers start small and grow their stack and frames as they call deeper
+    let stack = vec![Value::Nil; FIBER_STACK_MIN];
+    let frames = vec![CallFrame::new(closure); FIBER_FRAME_MIN];
+    Ok(self.gc.manage(Fiber::new(closure, stack, frames), self))
+  }
+
+  /// Call the fiber or channel class creating a new fiber or channel
+  fn call_builtin_class(&mut self, class: Managed<Class>, arg_count: u8, ip: u32) -> Signal {
+    let value = if class == self.builtin.fiber {
+      if let Some(error) =
+        self.check_arity(ArityKind::Fixed(1), arg_count, || class.name.to_string())
+      {
+        return error;
+      }
+
+      match self.create_fiber(self.peek(0)) {
+        Ok(fiber) => Value::Fiber(fiber),
+        Err(error) => return error,
+      }
+    } else {
+      if let Some(error) = self.check_arity(ArityKind::Default(0, 1), arg_count, || {
+        class.name.to_string()
+      }) {
+        return error;
+      }
+
+      let capacity = if arg_count == 1 {
+        self.peek(0)
+      } else {
+        Value::Int(0)
+      };
+      let capacity = match capacity {
+        Value::Int(int) if int >= 0 => int as usize,
+        Value::Number(num) => match exact_integer(num) {
+          Some(int) if int >= 0 => int as usize,
+          _ => return self.runtime_error("Channel capacity must be a non-negative integer."),
+        },
+        _ => return self.runtime_error("Channel capacity must be a non-negative integer."),
+      };
+
+      Value::Channel(self.gc.manage(Channel::new(capacity), self))
+    };
+
+    self.stack_top -= arg_count as usize + 1;
+    self.push(value);
+    Signal::Ok(ip)
+  }
+
+  /// Call a method on a fiber. Resuming a fiber runs it until it yields
+  /// back or returns
+  fn invoke_fiber(
+    &mut self,
+    mut fiber: Managed<Fiber>,
+    method_name: Managed<String>,
+    arg_count: u8,
+    ip: u32,
+  ) -> Signal {
+    if method_name.as_str() != RESUME {
+      return self.invoke_from_class(self.builtin.fiber, method_name, arg_count, ip);
+    }
+
+    if let Some(error) = self.check_arity(ArityKind::Default(0, 1), arg_count, || {
+      method_name.to_string()
+    }) {
+      return error;
+    }
+
+    match fiber.state {
+      FiberState::Created | FiberState::Suspended => (),
+      FiberState::Done => return self.runtime_error("Cannot resume a finished fiber."),
+      FiberState::Waiting => return self.runtime_error("Cannot resume a scheduled fiber."),
+      FiberState::Running => return self.runtime_error("Fiber is already running."),
+    }
+
+    let value = if arg_count == 1 {
+      self.pop()
+    } else {
+      Value::Nil
+    };
+    self.pop();
+
+    fiber.caller = Some(self.fiber);
+    self.store_fiber(FiberState::Running, ip);
+    self.enter_fiber(fiber, value)
+  }
+
+  /// Call a static method on the fiber class. These yield the running
+  /// fiber or hand fibers to the scheduler
+  fn invoke_fiber_class(&mut self, method_name: Managed<String>, arg_count: u8, ip: u32) -> Signal {
+    let arity = match method_name.as_str() {
+      YIELD => ArityKind::Default(0, 1),
+      SPAWN | SLEEP => ArityKind::Fixed(1),
+      _ => return self.runtime_error(&format!("Undefined property {}.", method_name.as_str())),
+    };
+
+    if let Some(error) = self.check_arity(arity, arg_count, || method_name.to_string()) {
+      return error;
+    }
+
+    match method_name.as_str() {
+      YIELD => {
+        // the main script can only give its turn to other fibers
+        if self.fiber == self.main_fiber {
+          if self.ready.is_empty() && self.sleeping.is_empty() {
+            return self.runtime_error("Cannot yield outside a fiber.");
+          }
+          if arg_count == 1 {
+            return self.runtime_error("Cannot yield a value outside a fiber.");
+          }
+        }
+
+        let value = if arg_count == 1 {
+          self.pop()
+        } else {
+          Value::Nil
+        };
+        self.pop();
+
+        // a fiber nobody resumed gives the other fibers a turn instead
+        match self.fiber.caller.take() {
+          Some(caller) => {
+            self.store_fiber(FiberState::Suspended, ip);
+            self.enter_fiber(caller, value)
+          }
+          None => {
+            let fiber = self.fiber;
+            self.store_fiber(FiberState::Waiting, ip);
+            self.ready.push_back(fiber);
+            self.run_next()
+          }
+        }
+      }
+      SPAWN => match self.create_fiber(self.peek(0)) {
+        Ok(mut fiber) => {
+          fiber.state = FiberState::Waiting;
+          self.ready.push_back(fiber);
+
+          self.stack_top -= 2;
+          self.push(Value::Fiber(fiber));
+          Signal::Ok(ip)
+        }
+        Err(error) => error,
+      },
+      _ => {
+        let duration = match self.peek(0) {
+          Value::Number(num) if num >= 0.0 => num,
+          Value::Int(int) if int >= 0 => int as f64,
+          _ => return self.runtime_error("Fiber.sleep expected a non-negative number."),
+        };
+        self.stack_top -= 2;
+
+        // sleeping fibers wake in order, those waking together in the order they slept
+        let fiber = self.fiber;
+        let wake = self.clock + duration;
+        let index = self
+          .sleeping
+          .iter()
+          .position(|(time, _)| *time > wake)
+          .unwrap_or(self.sleeping.len());
+
+        self.store_fiber(FiberState::Waiting, ip);
+        self.sleeping.insert(index, (wake, fiber));
+        self.run_next()
+      }
+    }
+  }
+
+  /// Call a method on a channel. Sending to a full channel or receiving
+  /// from an empty one waits while the scheduler runs other fibers
+  fn invoke_channel(
     &mut self,
-    native: Managed<Box<dyn NativeFun>>,
+    mut channel: Managed<Channel>,
+    method_name: Managed<String>,
     arg_count: u8,
     ip: u32,
   ) -> Signal {
-    let meta = native.meta();
+    let arity = match method_name.as_str() {
+      SEND => ArityKind::Fixed(1),
+      RECV => ArityKind::Fixed(0),
+      _ => return self.invoke_from_class(self.builtin.channel, method_name, arg_count, ip),
+    };
 
-    // check that the current function is called with the right number of args
-    if let Some(error) = self.check_arity(meta.arity, arg_count, || String::from(meta.name)) {
+    if let Some(error) = self.check_arity(arity, arg_count, || method_name.to_string()) {
       return error;
     }
 
-    let args = Vec::from(unsafe {
-      self
-        .stack
-        .get_unchecked((self.stack_top - arg_count as usize) as usize..self.stack_top as usize)
-    });
+    if method_name.as_str() == SEND {
+      let value = self.peek(0);
 
-    match native.call(&Hooks::new(self), &args) {
-      NativeResult::Success(value) => {
-        self.stack_top -= arg_count as usize + 1;
-        self.push(value);
-        Signal::Ok(ip)
+      // hand the value straight to a waiting receiver if there is one
+      if let Some(mut receiver) = channel.receivers.pop_front() {
+        receiver.value = value;
+        self.ready.push_back(receiver);
+      } else if channel.buffer.len() < channel.capacity {
+        self.gc.resize(&mut *channel, self, |channel| {
+          channel.buffer.push_back(value)
+        });
+      } else {
+        let fiber = self.fiber;
+        self.gc.resize(&mut *channel, self, |channel| {
+          channel.senders.push_back((fiber, value))
+        });
+
+        self.stack_top -= 2;
+        self.store_fiber(FiberState::Waiting, ip);
+        return self.run_next();
       }
-      NativeResult::RuntimeError(message) => self.runtime_error(&message),
+
+      self.stack_top -= 2;
+      self.push(Value::Nil);
+      return Signal::Ok(ip);
     }
+
+    // take a buffered value making room for a waiting sender, otherwise
+    // take the value of a waiting sender directly
+    let value = match channel.buffer.pop_front() {
+      Some(value) => {
+        if let Some((sender, sent)) = channel.senders.pop_front() {
+          channel.buffer.push_back(sent);
+          self.ready.push_back(sender);
+        }
+        value
+      }
+      None => match channel.senders.pop_front() {
+        Some((sender, sent)) => {
+          self.ready.push_back(sender);
+          sent
+        }
+        None => {
+          let fiber = self.fiber;
+          self.gc.resize(&mut *channel, self, |channel| {
+            channel.receivers.push_back(fiber)
+          });
+
+          self.stack_top -= 1;
+          self.store_fiber(FiberState::Waiting, ip);
+          return self.run_next();
+        }
+      },
+    };
+
+    self.set_val(self.stack_top - 1, value);
+    Signal::Ok(ip)
   }
 
-  /// call a native method immediately returning the result
-  fn call_native_method(
-    &mut self,
-    native: Managed<Box<dyn NativeMethod>>,
-    arg_count: u8,
-    ip: u32,
-  ) -> Signal {
-    let meta = native.meta();
+  /// Move the running fiber's execution state into the fiber so another
+  /// can be loaded. Nothing may run until a fiber is loaded again
+  fn store_fiber(&mut self, state: FiberState, ip: u32) {
+    if self.frame_count > 0 {
+      self.current_mut_frame().ip = ip;
+    }
 
-    // check that the current function is called with the right number of args
-    if let Some(error) = self.check_arity(meta.arity, arg_count, || String::from(meta.name)) {
-      return error;
+    let mut fiber = self.fiber;
+    fiber.state = state;
+
+    // close the fiber's upvalues remembering which slot each refers to
+    let base = self.stack.as_ptr() as usize;
+    for upvalue in self.open_upvalues.iter_mut() {
+      if let Upvalue::Open(index) = **upvalue {
+        let offset = (index.as_ptr() as usize - base) / mem::size_of::<Value>();
+        upvalue.hoist();
+        fiber.upvalues.push((offset, *upvalue));
+      }
     }
+    self.open_upvalues.clear();
 
-    let args: Vec<Value> = Vec::from(unsafe {
+    fiber.handlers.extend(
       self
-        .stack
-        .get_unchecked((self.stack_top - arg_count as usize) as usize..self.stack_top as usize)
-    });
-    let this = self.get_val(self.stack_top - arg_count as usize - 1);
+        .handlers
+        .drain(..)
+        .map(|handler| (handler.frame_count, handler.stack_top, handler.ip)),
+    );
+    fiber.imports.extend(
+      self
+        .imports
+        .drain(..)
+        .map(|import| (import.module, import.frame_count)),
+    );
+    fiber.generators.extend(
+      self
+        .generators
+        .drain(..)
+        .map(|resumed| (resumed.generator, resumed.frame_count, resumed.returning)),
+    );
+
+    mem::swap(self.stack, &mut fiber.stack);
+    mem::swap(self.frames, &mut fiber.frames);
+    fiber.stack_top = self.stack_top;
+    fiber.frame_count = self.frame_count;
+    self.stack_top = 0;
+    self.frame_count = 0;
+  }
 
-    match native.call(
-      &Hooks::new(self),
-      this,
-      &args,
-    ) {
-      NativeResult::Success(value) => {
-        self.stack_top -= arg_count as usize + 1;
+  /// Load a stored fiber's execution state making it the running fiber
+  fn load_fiber(&mut self, mut fiber: Managed<Fiber>) {
+    mem::swap(self.stack, &mut fiber.stack);
+    mem::swap(self.frames, &mut fiber.frames);
+    self.stack_top = fiber.stack_top;
+    self.frame_count = fiber.frame_count;
+    fiber.stack_top = 0;
+    fiber.frame_count = 0;
+
+    // a suspended upvalue holds the latest value of the slot it refers to
+    for (offset, mut upvalue) in fiber.upvalues.drain(..) {
+      if let Upvalue::Closed(store) = &*upvalue {
+        self.stack[offset] = **store;
+      }
+
+      *upvalue = Upvalue::Open(NonNull::from(&self.stack[offset]));
+      self.open_upvalues.push(upvalue);
+    }
+
+    self.handlers.extend(
+      fiber
+        .handlers
+        .drain(..)
+        .map(|(frame_count, stack_top, ip)| Handler {
+          frame_count,
+          stack_top,
+          ip,
+        }),
+    );
+    self
+      .imports
+      .extend(fiber.imports.drain(..).map(|(module, frame_count)| Import {
+        module,
+        frame_count,
+      }));
+    self.generators.extend(fiber.generators.drain(..).map(
+      |(generator, frame_count, returning)| Resumed {
+        generator,
+        frame_count,
+        returning,
+      },
+    ));
+
+    if self.frame_count > 0 {
+      self.current_frame = *self.current_frame();
+      self.current_fun = self.current_frame.closure.fun;
+    }
+
+    fiber.state = FiberState::Running;
+    self.fiber = fiber;
+  }
+
+  /// Run a stored fiber handing it a value. A fiber that has not started
+  /// yet calls its function with the value
+  fn enter_fiber(&mut self, fiber: Managed<Fiber>, value: Value) -> Signal {
+    let started = fiber.frame_count > 0;
+    self.load_fiber(fiber);
+
+    if started {
+      self.push(value);
+      return Signal::Ok(self.current_frame.ip);
+    }
+
+    let closure = fiber.closure;
+    self.push(Value::Closure(closure));
+    match closure.fun.arity {
+      ArityKind::Fixed(0) => self.call(closure, 0, 0),
+      _ => {
         self.push(value);
-        Signal::Ok(ip)
+        self.call(closure, 1, 0)
       }
-      NativeResult::RuntimeError(message) => self.runtime_error(&message),
     }
   }
 
-  /// call a spacelox function setting it as the new call frame
-  fn call(&mut self, closure: Managed<Closure>, arg_count: u8, ip: u32) -> Signal {
-    // check that the current function is called with the right number of args
-    if let Some(error) = self.check_arity(closure.fun.arity, arg_count, || {
-      closure.fun.name.to_string()
-    }) {
-      return error;
+  /// Finish the running fiber handing its result to the fiber that
+  /// resumed it, otherwise letting the scheduler run the next fiber
+  fn finish_fiber(&mut self, result: Value) -> Signal {
+    let mut fiber = self.fiber;
+    let caller = fiber.caller.take();
+    self.store_fiber(FiberState::Done, 0);
+
+    // the main script's stack belongs to the vm
+    if fiber != self.main_fiber {
+      fiber.stack = Vec::new();
+      fiber.frames = Vec::new();
+      fiber.stack_top = 0;
+      fiber.frame_count = 0;
     }
+    fiber.upvalues.clear();
+    fiber.handlers.clear();
 
-    // set the current current instruction pointer. check for overflow
-    match self.frame_count {
-      0 => (),
-      FRAME_MAX => {
-        return self.runtime_error("Stack overflow.");
+    match caller {
+      Some(caller) => self.enter_fiber(caller, result),
+      None => self.run_next(),
+    }
+  }
+
+  /// Run the next fiber the scheduler has ready, moving the clock forward
+  /// to the next sleeping fiber if none are. The running fiber must have
+  /// been stored already
+  fn run_next(&mut self) -> Signal {
+    let mut fiber = match self.ready.pop_front() {
+      Some(fiber) => fiber,
+      None if !self.sleeping.is_empty() => {
+        let (wake, fiber) = self.sleeping.remove(0);
+        self.clock = wake;
+        fiber
       }
-      _ => self.current_mut_frame().ip = ip,
+      None => {
+        // with nothing left to run the script is finished unless it is stuck waiting
+        let finished = self.main_fiber.is_done();
+        self.load_fiber(self.main_fiber);
+
+        if finished {
+          return Signal::Exit;
+        }
+
+        // a deadlock cannot be caught as the waiting fibers can never run
+        self.runtime_error("Deadlock, every fiber is waiting.");
+        let error = self.pop();
+        self.uncaught(error);
+        return Signal::RuntimeError;
+      }
+    };
+
+    let value = mem::replace(&mut fiber.value, Value::Nil);
+    self.enter_fiber(fiber, value)
+  }
+
+  /// Return the main script's stack to the vm once execution ends
+  fn restore_main(&mut self) {
+    if self.fiber != self.main_fiber {
+      self.store_fiber(FiberState::Done, 0);
+      self.load_fiber(self.main_fiber);
     }
+  }
 
-    let frame = &mut self.frames[self.frame_count];
-    frame.closure = closure;
-    frame.ip = 0;
-    frame.slots = self.stack_top as u32 - (arg_count as u32 + 1);
+  /// Collect any rest arguments into a list and find the instruction to
+  /// start at so only the missing default arguments are evaluated
+  fn prepare_args(&mut self, fun: Managed<Fun>, arg_count: u8) -> (u8, u32) {
+    let entry_points = &fun.entry_points;
 
-    self.current_frame = *frame;
-    self.current_fun = closure.fun;
-    self.frame_count += 1;
-    Signal::Ok(0)
+    match fun.arity {
+      ArityKind::Fixed(_) => (arg_count, 0),
+      ArityKind::Default(min_arity, _) => {
+        let start = entry_points[(arg_count - min_arity) as usize];
+        (arg_count, start)
+      }
+      ArityKind::Variadic(min_arity) => {
+        let positional = min_arity as usize + entry_points.len() - 2;
+
+        if arg_count as usize <= positional {
+          let start = entry_points[(arg_count - min_arity) as usize];
+          return (arg_count, start);
+        }
+
+        let rest_start = self.stack_top - (arg_count as usize - positional);
+        let rest = Vec::from(&self.stack[rest_start..self.stack_top]);
+        let rest = Value::List(self.gc.manage(rest, self));
+
+        self.stack_top = rest_start;
+        self.push(rest);
+
+        (positional as u8 + 1, entry_points[entry_points.len() - 1])
+      }
+    }
   }
 
   /// check that the number of args is valid for the function arity
@@ -1190,6 +3386,23 @@ impl<'a, I: Io> VmExecutor<'a, I> {
     }
   }
 
+  /// Bind a static method to the class it was accessed from
+  fn bind_static_method(
+    &mut self,
+    class: Managed<Class>,
+    name: Managed<String>,
+    ip: u32,
+  ) -> Signal {
+    match class.get_static_method(&name) {
+      Some(method) => {
+        let bound = self.gc.manage(Method::new(self.peek(0), *method), self);
+        self.set_val(self.stack_top - 1, Value::Method(bound));
+        Signal::Ok(ip)
+      }
+      None => self.runtime_error(&format!("Undefined property {}", name.as_str())),
+    }
+  }
+
   /// invoke a method from the provided class
   fn invoke_from_class(
     &mut self,
@@ -1274,42 +3487,167 @@ impl<'a, I: Io> VmExecutor<'a, I> {
       stdio.println("");
     }
 
-    disassemble_instruction(&stdio, &self.current_fun.chunk(), ip, last_ip);
+    disassemble_instruction(&stdio, self.current_fun.chunk(), ip, last_ip);
   }
 
-  /// Report a known spacelox runtime error to the user
+  /// Report a known spacelox runtime error by throwing it as an error instance
   fn runtime_error(&mut self, message: &str) -> Signal {
-    self.error(message);
-    Signal::RuntimeError
+    let message = Value::String(self.gc.manage_str(message.to_string(), self));
+    self.push(message);
+
+    let error = Value::Instance(self.gc.manage(Instance::new(self.builtin.error), self));
+    self.push(error);
+
+    let name = self.gc.manage_str(String::from(ERROR_MESSAGE), self);
+    self.push(Value::String(name));
+
+    error
+      .to_instance()
+      .set_field(&Hooks::new(self), name, message);
+
+    self.stack_top -= 3;
+    self.push(error);
+    Signal::Throw
+  }
+
+  /// Throw the value on top of the stack unwinding to the nearest handler
+  fn throw(&mut self, ip: u32) -> Signal {
+    self.current_mut_frame().ip = ip;
+    let error = self.peek(0);
+
+    // record where errors were thrown from, other instances are left untouched
+    if let Some(mut instance) = self.error_instance(error) {
+      let name = self.gc.manage_str(String::from(ERROR_STACK), self);
+      self.push(Value::String(name));
+
+      if instance.get_field(&name).is_none() {
+        let trace = self.stack_trace();
+        let mut stack = self.gc.manage(Vec::with_capacity(trace.len()), self);
+        self.push(Value::List(stack));
+
+        for line in trace {
+          let line = self.gc.manage_str(line, self);
+          stack.push(Value::String(line));
+        }
+
+        instance.set_field(&Hooks::new(self), name, Value::List(stack));
+        self.pop();
+      }
+
+      self.pop();
+    }
+
+    match self.handlers.pop() {
+      Some(handler) => self.enter_handler(handler, error, Value::Bool(true)),
+      None => match self.fiber.caller.take() {
+        // an error escaping a resumed fiber is rethrown where it was resumed
+        Some(caller) => {
+          self.store_fiber(FiberState::Done, ip);
+          self.load_fiber(caller);
+          self.push(error);
+          self.throw(self.current_frame.ip)
+        }
+        None => {
+          self.uncaught(error);
+          Signal::RuntimeError
+        }
+      },
+    }
+  }
+
+  /// Unwind to a handler's try statement storing the value and how the
+  /// statement is being left in the two hidden slots below the handler
+  fn enter_handler(&mut self, handler: Handler, value: Value, completion: Value) -> Signal {
+    self.close_upvalues(NonNull::from(&self.stack[handler.stack_top]));
+
+    self.frame_count = handler.frame_count;
+    self.current_frame = *self.current_frame();
+    self.current_fun = self.current_frame.closure.fun;
+    self.unwind_imports();
+    self.unwind_generators();
+
+    self.stack_top = handler.stack_top;
+    self.set_val(handler.stack_top - 2, value);
+    self.set_val(handler.stack_top - 1, completion);
+    Signal::Ok(handler.ip)
+  }
+
+  /// The instance if this value is an Error or an instance of one of its subclasses
+  fn error_instance(&self, value: Value) -> Option<Managed<Instance>> {
+    if let Value::Instance(instance) = value {
+      let mut class = Some(instance.class);
+      while let Some(current) = class {
+        if current == self.builtin.error {
+          return Some(instance);
+        }
+        class = current.super_class;
+      }
+    }
+
+    None
   }
 
-  /// Print an error message and the current call stack to the user
-  fn error(&mut self, message: &str) {
+  /// Print an uncaught error and where it was thrown from to the user
+  fn uncaught(&mut self, error: Value) {
+    let mut message = format!("Uncaught {}", error);
+    let mut trace = None;
+
+    if let Some(instance) = self.error_instance(error) {
+      let name = self.gc.manage_str(String::from(ERROR_MESSAGE), self);
+      match instance.get_field(&name) {
+        Some(Value::String(string)) => message = string.to_string(),
+        Some(value) => message = value.to_string(),
+        None => (),
+      }
+
+      let name = self.gc.manage_str(String::from(ERROR_STACK), self);
+      if let Some(Value::List(stack)) = instance.get_field(&name) {
+        trace = Some(stack.iter().map(|line| line.to_str().to_string()).collect());
+      }
+    }
+
     let stdio = self.io.stdio();
-    stdio.eprintln(message);
-    stdio.eprintln(&format!(""));
-
-    for frame in self.frames[0..self.frame_count].iter().rev() {
-      let closure = &frame.closure;
-      let location: String = match &**closure.fun.name {
-        SCRIPT => SCRIPT.to_owned(),
-        _ => format!("{}()", closure.fun.name),
-      };
+    stdio.eprintln(&message);
+    stdio.eprintln("");
 
-      stdio.eprintln(&format!(
-        "[line {}] in {}",
-        closure.fun.chunk().get_line(frame.ip as usize),
-        location
-      ));
+    for line in trace.unwrap_or_else(|| self.stack_trace()) {
+      stdio.eprintln(&line);
     }
 
     self.reset_stack();
   }
+
+  /// Describe the current call stack from the innermost frame outward
+  fn stack_trace(&self) -> Vec<String> {
+    self.frames[0..self.frame_count]
+      .iter()
+      .rev()
+      .map(|frame| {
+        let closure = &frame.closure;
+        let location: String = match &**closure.fun.name {
+          SCRIPT => SCRIPT.to_owned(),
+          _ => format!("{}()", closure.fun.name),
+        };
+
+        // name the module the frame's function was defined in when it came from a file
+        let location = match closure.module.path() {
+          Some(path) => format!("{} ({})", location, path.display()),
+          None => location,
+        };
+
+        format!(
+          "[line {}] in {}",
+          closure.fun.chunk().get_line(frame.ip as usize),
+          location
+        )
+      })
+      .collect()
+  }
 }
 
 impl<'a, I: Io> Trace for VmExecutor<'a, I> {
   fn trace(&self) -> bool {
-    self.stack[0..self.stack_top as usize]
+    self.stack[0..self.stack_top]
       .iter()
       .for_each(|value| {
         value.trace();
@@ -1323,18 +3661,38 @@ impl<'a, I: Io> Trace for VmExecutor<'a, I> {
       upvalue.trace();
     });
 
+    self.generators.iter().for_each(|resumed| {
+      resumed.generator.trace();
+    });
+
+    self.fiber.trace();
+    self.main_fiber.trace();
+
+    self.ready.iter().for_each(|fiber| {
+      fiber.trace();
+    });
+
+    self.sleeping.iter().for_each(|(_, fiber)| {
+      fiber.trace();
+    });
+
     self.globals.iter().for_each(|(key, val)| {
       key.trace();
       val.trace();
     });
 
+    self.modules.values().for_each(|module| {
+      module.trace();
+    });
+
     self.builtin.trace();
+    self.special.trace();
 
     true
   }
 
   fn trace_debug(&self, stdio: &dyn StdIo) -> bool {
-    self.stack[0..self.stack_top as usize]
+    self.stack[0..self.stack_top]
       .iter()
       .for_each(|value| {
         value.trace_debug(stdio);
@@ -1348,12 +3706,32 @@ impl<'a, I: Io> Trace for VmExecutor<'a, I> {
       upvalue.trace_debug(stdio);
     });
 
+    self.generators.iter().for_each(|resumed| {
+      resumed.generator.trace_debug(stdio);
+    });
+
+    self.fiber.trace_debug(stdio);
+    self.main_fiber.trace_debug(stdio);
+
+    self.ready.iter().for_each(|fiber| {
+      fiber.trace_debug(stdio);
+    });
+
+    self.sleeping.iter().for_each(|(_, fiber)| {
+      fiber.trace_debug(stdio);
+    });
+
     self.globals.iter().for_each(|(key, val)| {
       key.trace_debug(stdio);
       val.trace_debug(stdio);
     });
 
+    self.modules.values().for_each(|module| {
+      module.trace_debug(stdio);
+    });
+
     self.builtin.trace_debug(stdio);
+    self.special.trace_debug(stdio);
     true
   }
 }
@@ -1362,6 +3740,214 @@ impl<'a, I: Io> HookContext for VmExecutor<'a, I> {
   fn gc(&self) -> &Gc { self.gc }
 }
 
+/// Convert an int or a number without a fractional part to an integer
+fn to_integer(value: Value) -> Option<i64> {
+  match value {
+    Value::Int(int) => Some(int),
+    Value::Number(num) => exact_integer(num),
+    _ => None,
+  }
+}
+
+/// Convert an int, a big int or a number without a fractional part to a big int
+fn to_big_integer(value: Value) -> Option<BigInt> {
+  match value {
+    Value::BigInt(big) => Some((*big).clone()),
+    value => to_integer(value).map(BigInt::from),
+  }
+}
+
+/// Resolve a possibly negative index against a collection of length `len`
+fn resolve_index(kind: &str, index: Value, len: usize) -> Result<usize, String> {
+  let int = match to_integer(index) {
+    Some(int) => int,
+    None => return Err(String::from("Index must be an integer.")),
+  };
+
+  let resolved = if int < 0 { int + len as i64 } else { int };
+  if resolved < 0 || resolved >= len as i64 {
+    return Err(format!(
+      "Index out of bounds. {} was length {} but attempted to index with {}.",
+      kind, len, int
+    ));
+  }
+
+  Ok(resolved as usize)
+}
+
+/// Compute the indices a slice selects from a collection of length `len`.
+/// Bounds follow python semantics, negative bounds count from the end and
+/// out of range bounds are clamped.
+fn slice_indices(len: usize, start: Value, stop: Value, step: Value) -> Result<Vec<usize>, String> {
+  let len = len as i64;
+  let step = match step {
+    Value::Nil => 1,
+    step => match to_integer(step) {
+      Some(0) => return Err(String::from("Slice step cannot be zero.")),
+      Some(step) => step,
+      None => return Err(String::from("Slice step must be an integer.")),
+    },
+  };
+
+  let bound = |value: Value, default: i64| match value {
+    Value::Nil => Ok(default),
+    value => match to_integer(value) {
+      Some(int) => {
+        let int = if int < 0 {
+          int.saturating_add(len)
+        } else {
+          int
+        };
+        if step > 0 {
+          Ok(int.max(0).min(len))
+        } else {
+          Ok(int.max(-1).min(len - 1))
+        }
+      }
+      None => Err(String::from("Slice bounds must be integers.")),
+    },
+  };
+
+  let (start, stop) = if step > 0 {
+    (bound(start, 0)?, bound(stop, len)?)
+  } else {
+    (bound(start, len - 1)?, bound(stop, -1)?)
+  };
+
+  let mut indices = Vec::new();
+  let mut index = start;
+  while (step > 0 && index < stop) || (step < 0 && index > stop) {
+    indices.push(index as usize);
+    index = match index.checked_add(step) {
+      Some(index) => index,
+      None => break,
+    };
+  }
+
+  Ok(indices)
+}
+
+/// Convert an int, big int or number to a float
+fn to_float(value: Value) -> Option<f64> {
+  match value {
+    Value::Int(int) => Some(int as f64),
+    Value::BigInt(big) => big.to_f64(),
+    Value::Number(num) => Some(num),
+    _ => None,
+  }
+}
+
+/// Convert a pair of operands to big ints if either is a big int and
+/// neither is a float
+fn big_operands(left: Value, right: Value) -> Option<(BigInt, BigInt)> {
+  match (left, right) {
+    (Value::BigInt(left), Value::BigInt(right)) => Some(((*left).clone(), (*right).clone())),
+    (Value::BigInt(left), Value::Int(right)) => Some(((*left).clone(), BigInt::from(right))),
+    (Value::Int(left), Value::BigInt(right)) => Some((BigInt::from(left), (*right).clone())),
+    _ => None,
+  }
+}
+
+/// Compare an int with a float without rounding the int to the nearest
+/// float. Returns None when the float is NaN
+fn compare_int_float(int: i64, num: f64) -> Option<Ordering> {
+  if num.is_nan() {
+    return None;
+  }
+
+  match exact_integer(num.floor()) {
+    Some(floor) => match int.cmp(&floor) {
+      Ordering::Equal if num.fract() != 0.0 => Some(Ordering::Less),
+      ordering => Some(ordering),
+    },
+    // the float is beyond the range of any int
+    None if num > 0.0 => Some(Ordering::Less),
+    None => Some(Ordering::Greater),
+  }
+}
+
+/// Convert a pair of numeric operands to floats
+fn float_operands(left: Value, right: Value) -> Option<(f64, f64)> {
+  Some((to_float(left)?, to_float(right)?))
+}
+
+/// Big int division rounding towards negative infinity
+fn big_floor_div(left: BigInt, right: BigInt) -> BigInt {
+  let quotient = &left / &right;
+  if !(&left % &right).is_zero() && (left.sign() == Sign::Minus) != (right.sign() == Sign::Minus) {
+    quotient - 1
+  } else {
+    quotient
+  }
+}
+
+/// Big int remainder taking the sign of the divisor to agree with `big_floor_div`
+fn big_floor_mod(left: BigInt, right: BigInt) -> BigInt {
+  let remainder = &left % &right;
+  if !remainder.is_zero() && (remainder.sign() == Sign::Minus) != (right.sign() == Sign::Minus) {
+    remainder + right
+  } else {
+    remainder
+  }
+}
+
+/// Integer division rounding towards negative infinity
+fn floor_div(left: i64, right: i64) -> Option<i64> {
+  let quotient = left.checked_div(right)?;
+  if left % right != 0 && (left < 0) != (right < 0) {
+    Some(quotient - 1)
+  } else {
+    Some(quotient)
+  }
+}
+
+/// Integer remainder taking the sign of the divisor to agree with `floor_div`
+fn floor_mod(left: i64, right: i64) -> Option<i64> {
+  let remainder = left.checked_rem(right)?;
+  if remainder != 0 && (remainder < 0) != (right < 0) {
+    Some(remainder + right)
+  } else {
+    Some(remainder)
+  }
+}
+
+/// Float remainder taking the sign of the divisor to agree with floored division
+fn float_floor_mod(left: f64, right: f64) -> f64 {
+  let remainder = left % right;
+  if remainder != 0.0 && (remainder < 0.0) != (right < 0.0) {
+    remainder + right
+  } else {
+    remainder
+  }
+}
+
+/// Shift left returning None if the shift is out of range
+fn shift_left(left: i64, right: i64) -> Option<i64> {
+  u32::try_from(right)
+    .ok()
+    .and_then(|right| left.checked_shl(right))
+}
+
+/// Shift right returning None if the shift is out of range
+fn shift_right(left: i64, right: i64) -> Option<i64> {
+  u32::try_from(right)
+    .ok()
+    .and_then(|right| left.checked_shr(right))
+}
+
+/// Shift a big int left returning None if the shift is out of range. Like
+/// the exponent of `**` the shift is capped to a u32
+fn big_shift_left(left: &BigInt, right: &BigInt) -> Option<BigInt> {
+  u32::try_from(right)
+    .ok()
+    .map(|right| left << right as usize)
+}
+
+/// Shift a big int right returning None if the shift is out of range
+fn big_shift_right(left: &BigInt, right: &BigInt) -> Option<BigInt> {
+  usize::try_from(right).ok().map(|right| left >> right)
+}
+
 /// Is the provided `value` falsey according to spacelox rules
 #[inline]
 fn is_falsey(value: Value) -> bool {
@@ -1,3 +1,9 @@
+#![allow(
+  clippy::assertions_on_constants,
+  clippy::bind_instead_of_map,
+  clippy::useless_vec
+)]
+
 use spacelox_vm::vm::{default_native_vm, ExecuteResult};
 use std::fs::File;
 use std::io::prelude::*;
@@ -19,11 +25,12 @@ fn test_files(paths: &[&str], result: ExecuteResult) -> Result<(), std::io::Erro
 
     let assert = fixture_path(path).expect("No parent directory");
     let debug_path = assert.to_str().map(|s| s.to_string());
-    let mut file = File::open(assert)?;
+    let mut file = File::open(&assert)?;
     let mut source = String::new();
     file.read_to_string(&mut source)?;
 
-    assert_eq!(vm.run(&source), result, "Failing file {:?}", debug_path);
+    let actual = vm.run_module(&source, &assert);
+    assert_eq!(actual, result, "Failing file {:?}", debug_path);
   }
 
   Ok(())
@@ -91,6 +98,33 @@ fn assert_ne() -> Result<(), std::io::Error> {
   Ok(())
 }
 
+#[test]
+fn accessor() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "accessor/getter.lox",
+      "accessor/inherited.lox",
+      "accessor/setter.lox",
+      "accessor/super_getter.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "accessor/getter_with_parameters.lox",
+      "accessor/setter_parameters.lox",
+      "accessor/setter_return_value.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec!["accessor/set_getter_only.lox"],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn assignment() -> Result<(), std::io::Error> {
   test_files(
@@ -116,6 +150,35 @@ fn assignment() -> Result<(), std::io::Error> {
   test_files(&vec!["assignment/undefined.lox"], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn big_int() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "big_int/arithmetic.lox",
+      "big_int/bitwise.lox",
+      "big_int/bound_method.lox",
+      "big_int/comparison.lox",
+      "big_int/conversion.lox",
+      "big_int/map_key.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(&vec![], ExecuteResult::CompileError)?;
+
+  test_files(
+    &vec![
+      "big_int/bitwise_nonintegral.lox",
+      "big_int/division_by_zero.lox",
+      "big_int/invalid_number.lox",
+      "big_int/invalid_string.lox",
+      "big_int/shift_negative.lox",
+      "big_int/shift_too_large.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn block() -> Result<(), std::io::Error> {
   test_files(&vec!["block/empty.lox", "block/empty.lox"], ExecuteResult::Ok)
@@ -126,6 +189,33 @@ fn bool() -> Result<(), std::io::Error> {
   test_files(&vec!["bool/equality.lox", "bool/not.lox"], ExecuteResult::Ok)
 }
 
+#[test]
+fn break_stmt() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "break/closure.lox",
+      "break/finally.lox",
+      "break/for.lox",
+      "break/labeled.lox",
+      "break/try.lox",
+      "break/while.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "break/in_nested_function.lox",
+      "break/label_without_loop.lox",
+      "break/outside_loop.lox",
+      "break/unknown_label.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(&vec![], ExecuteResult::RuntimeError)
+}
+
 #[test]
 fn call() -> Result<(), std::io::Error> {
   test_files(
@@ -187,19 +277,146 @@ fn closure() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn coalesce() -> Result<(), std::io::Error> {
+  test_files(
+    &vec!["coalesce/basic.lox", "coalesce/short_circuit.lox"],
+    ExecuteResult::Ok,
+  )
+}
+
 #[test]
 fn comments() -> Result<(), std::io::Error> {
   test_files(
     &vec![
+      "comments/block.lox",
+      "comments/block_nested.lox",
+      "comments/doc_class.lox",
+      "comments/doc_fun.lox",
       "comments/line_at_eof.lox",
       "comments/only_line_comment_and_line.lox",
       "comments/only_line_comment.lox",
       "comments/unicode.lox",
     ],
     ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec!["comments/block_unterminated.lox"],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec!["comments/block_line_count.lox"],
+    ExecuteResult::RuntimeError,
+  )
+}
+
+#[test]
+fn compound_assignment() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "compound_assignment/global.lox",
+      "compound_assignment/index.lox",
+      "compound_assignment/local.lox",
+      "compound_assignment/property.lox",
+      "compound_assignment/single_evaluation.lox",
+      "compound_assignment/upvalue.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "compound_assignment/const.lox",
+      "compound_assignment/invalid_target.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec!["compound_assignment/not_number.lox"],
+    ExecuteResult::RuntimeError,
   )
 }
 
+#[test]
+fn conditional() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "conditional/basic.lox",
+      "conditional/nested.lox",
+      "conditional/precedence.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "conditional/assign_to_branch.lox",
+      "conditional/missing_colon.lox",
+    ],
+    ExecuteResult::CompileError,
+  )
+}
+
+#[test]
+fn continue_stmt() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "continue/closure.lox",
+      "continue/finally.lox",
+      "continue/for.lox",
+      "continue/labeled.lox",
+      "continue/while.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "continue/in_nested_function.lox",
+      "continue/outside_loop.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(&vec![], ExecuteResult::RuntimeError)
+}
+
+#[test]
+fn const_() -> Result<(), std::io::Error> {
+  test_files(
+    &vec!["const/closure.lox", "const/global.lox", "const/local.lox"],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "const/assign_global.lox",
+      "const/assign_global_before_declaration.lox",
+      "const/assign_local.lox",
+      "const/assign_upvalue.lox",
+      "const/missing_initializer.lox",
+      "const/redeclare.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(&vec![], ExecuteResult::RuntimeError)
+}
+
+#[test]
+fn const_across_runs() {
+  let mut vm = default_native_vm();
+
+  // each line of the repl is compiled on its own
+  assert_eq!(vm.run("const x = 1;"), ExecuteResult::Ok);
+  assert_eq!(vm.run("x = 2;"), ExecuteResult::CompileError);
+  assert_eq!(vm.run("var x = 3;"), ExecuteResult::CompileError);
+  assert_eq!(vm.run("assertEq(x, 1);"), ExecuteResult::Ok);
+}
+
 #[test]
 fn constructor() -> Result<(), std::io::Error> {
   test_files(
@@ -208,6 +425,7 @@ fn constructor() -> Result<(), std::io::Error> {
       "constructor/call_init_early_return.lox",
       "constructor/call_init_explicitly.lox",
       "constructor/default.lox",
+      "constructor/default_parameters.lox",
       "constructor/early_return.lox",
       "constructor/return_in_nested_function.lox",
     ],
@@ -229,6 +447,79 @@ fn constructor() -> Result<(), std::io::Error> {
   )
 }
 
+#[test]
+fn destructuring() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "destructuring/assignment.lox",
+      "destructuring/global.lox",
+      "destructuring/local.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "destructuring/const_target.lox",
+      "destructuring/duplicate_name.lox",
+      "destructuring/invalid_target.lox",
+      "destructuring/literal_pattern.lox",
+      "destructuring/own_initializer.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "destructuring/assignment_mismatch.lox",
+      "destructuring/missing_element.lox",
+      "destructuring/missing_key.lox",
+      "destructuring/missing_rest_element.lox",
+      "destructuring/not_list.lox",
+      "destructuring/not_map.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
+#[test]
+fn exception() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "exception/catch_error_instance.lox",
+      "exception/catch_native_error.lox",
+      "exception/catch_runtime_error.lox",
+      "exception/catch_thrown_value.lox",
+      "exception/close_upvalues.lox",
+      "exception/finally.lox",
+      "exception/finally_on_return.lox",
+      "exception/rethrow.lox",
+      "exception/return_from_try.lox",
+      "exception/subclass_error.lox",
+      "exception/throw_instance.lox",
+      "exception/unwind_frames.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "exception/missing_catch_and_finally.lox",
+      "exception/missing_catch_name.lox",
+      "exception/throw_missing_value.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "exception/uncaught.lox",
+      "exception/uncaught_in_finally.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn expressions() -> Result<(), std::io::Error> {
   test_files(&vec!["expressions/evaluate.lox"], ExecuteResult::Ok)?;
@@ -238,6 +529,43 @@ fn expressions() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn fiber() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "fiber/channel.lox",
+      "fiber/closure.lox",
+      "fiber/exception.lox",
+      "fiber/grow_stack.lox",
+      "fiber/nested.lox",
+      "fiber/resume.lox",
+      "fiber/scheduler.lox",
+      "fiber/sleep.lox",
+      "fiber/spawn_after_main.lox",
+      "fiber/stack.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "fiber/bad_capacity.lox",
+      "fiber/deadlock.lox",
+      "fiber/inherit_channel.lox",
+      "fiber/inherit_fiber.lox",
+      "fiber/not_function.lox",
+      "fiber/resume_finished.lox",
+      "fiber/resume_running.lox",
+      "fiber/resume_scheduled.lox",
+      "fiber/too_many_parameters.lox",
+      "fiber/uncaught_in_spawned.lox",
+      "fiber/yield_outside_fiber.lox",
+      "fiber/yield_value_outside_fiber.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn field() -> Result<(), std::io::Error> {
   test_files(
@@ -248,6 +576,7 @@ fn field() -> Result<(), std::io::Error> {
       "field/method_binds_this.lox",
       "field/method.lox",
       "field/on_instance.lox",
+      "field/set_on_class.lox",
     ],
     ExecuteResult::Ok,
   )?;
@@ -265,7 +594,6 @@ fn field() -> Result<(), std::io::Error> {
       "field/get_on_string.lox",
       "field/set_evaluation_order.lox",
       "field/set_on_bool.lox",
-      "field/set_on_class.lox",
       "field/set_on_function.lox",
       "field/set_on_nil.lox",
       "field/set_on_num.lox",
@@ -284,6 +612,12 @@ fn for_loop() -> Result<(), std::io::Error> {
       "for/scope.lox",
       "for/syntax.lox",
       "for/closure_in_body.lox",
+      "for/in_break_continue.lox",
+      "for/in_class.lox",
+      "for/in_closure.lox",
+      "for/in_list.lox",
+      "for/in_map.lox",
+      "for/in_string.lox",
     ],
     ExecuteResult::Ok,
   )?;
@@ -292,6 +626,7 @@ fn for_loop() -> Result<(), std::io::Error> {
     &vec![
       "for/class_in_body.lox",
       "for/fun_in_body.lox",
+      "for/in_missing_variable.lox",
       "for/statement_condition.lox",
       "for/statement_increment.lox",
       "for/statement_initializer.lox",
@@ -300,27 +635,39 @@ fn for_loop() -> Result<(), std::io::Error> {
     ExecuteResult::CompileError,
   )?;
 
-  test_files(&vec![], ExecuteResult::RuntimeError)
+  test_files(
+    &vec!["for/in_not_iterable.lox"],
+    ExecuteResult::RuntimeError,
+  )
 }
 
 #[test]
 fn function() -> Result<(), std::io::Error> {
   test_files(
     &vec![
+      "function/anonymous.lox",
+      "function/arrow.lox",
+      "function/default_parameters.lox",
       "function/empty_body.lox",
+      "function/lambda_closure.lox",
       "function/local_recursion.lox",
       "function/mutual_recursion.lox",
       "function/parameters.lox",
       "function/print.lox",
       "function/recursion.lox",
+      "function/rest_parameters.lox",
     ],
     ExecuteResult::Ok,
   )?;
 
   test_files(
     &vec![
+      "function/arrow_missing_body.lox",
       "function/body_must_be_block.lox",
+      "function/lambda_missing_paren.lox",
       "function/missing_comma_in_parameters.lox",
+      "function/required_after_default.lox",
+      "function/rest_not_last.lox",
       "function/too_many_arguments.lox",
       "function/too_many_parameters.lox",
     ],
@@ -330,8 +677,46 @@ fn function() -> Result<(), std::io::Error> {
   test_files(
     &vec![
       "function/extra_arguments.lox",
+      "function/extra_default_arguments.lox",
       "function/local_mutual_recursion.lox",
       "function/missing_arguments.lox",
+      "function/missing_default_arguments.lox",
+      "function/missing_rest_arguments.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
+#[test]
+fn generator() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "generator/arguments.lox",
+      "generator/basic.lox",
+      "generator/closure.lox",
+      "generator/exception.lox",
+      "generator/for_in.lox",
+      "generator/for_in_early_exit.lox",
+      "generator/return.lox",
+      "generator/send.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "generator/yield_at_top_level.lox",
+      "generator/yield_in_initializer.lox",
+      "generator/yield_in_setter.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "generator/already_running.lox",
+      "generator/undefined_method.lox",
+      "generator/wrong_arity.lox",
     ],
     ExecuteResult::RuntimeError,
   )
@@ -364,6 +749,34 @@ fn if_stmt() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn increment() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "increment/global.lox",
+      "increment/index.lox",
+      "increment/local.lox",
+      "increment/property.lox",
+      "increment/upvalue.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "increment/call_target.lox",
+      "increment/const.lox",
+      "increment/invalid_target.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec!["increment/not_number.lox"],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn indexing() -> Result<(), std::io::Error> {
   test_files(
@@ -438,7 +851,13 @@ fn limit() -> Result<(), std::io::Error> {
 #[test]
 fn list() -> Result<(), std::io::Error> {
   test_files(
-    &vec!["list/empty.lox", "list/homogeneous.lox", "list/mixed.lox"],
+    &vec![
+      "list/empty.lox",
+      "list/homogeneous.lox",
+      "list/mixed.lox",
+      "list/negative_index.lox",
+      "list/slice.lox",
+    ],
     ExecuteResult::Ok,
   )?;
 
@@ -446,11 +865,19 @@ fn list() -> Result<(), std::io::Error> {
     &vec![
       "list/missing_comma_in_initializer.lox",
       "list/missing_closing_bracket.lox",
+      "list/slice_assignment.lox",
     ],
     ExecuteResult::CompileError,
   )?;
 
-  test_files(&vec![], ExecuteResult::RuntimeError)
+  test_files(
+    &vec![
+      "list/index_not_integer.lox",
+      "list/index_out_of_bounds.lox",
+      "list/slice_zero_step.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
 }
 
 #[test]
@@ -490,6 +917,35 @@ fn map() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn match_stmt() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "match/guard.lox",
+      "match/guard_parenthesized.lox",
+      "match/instance.lox",
+      "match/list.lox",
+      "match/literal.lox",
+      "match/map.lox",
+      "match/scope.lox",
+      "match/wildcard.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "match/duplicate_binding.lox",
+      "match/invalid_pattern.lox",
+      "match/missing_arrow.lox",
+      "match/missing_case.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(&vec!["match/not_class.lox"], ExecuteResult::RuntimeError)
+}
+
 #[test]
 fn method() -> Result<(), std::io::Error> {
   test_files(
@@ -520,6 +976,51 @@ fn method() -> Result<(), std::io::Error> {
   )
 }
 
+#[test]
+fn module() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "module/cached.lox",
+      "module/from_import.lox",
+      "module/import_as.lox",
+      "module/local.lox",
+      "module/namespace.lox",
+      "module/nested.lox",
+      "module/stack_trace.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "module/assign_import.lox",
+      "module/duplicate_export.lox",
+      "module/export_in_function.lox",
+      "module/missing_as.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "module/broken.lox",
+      "module/cyclic.lox",
+      "module/missing.lox",
+      "module/not_exported.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
+#[test]
+fn module_search_path() {
+  let mut vm = default_native_vm();
+  vm.add_search_path(fixture_path("module").expect("No parent directory"));
+
+  let source = "from \"lib/math.lox\" import square; assertEq(square(3), 9);";
+  assert_eq!(vm.run(source), ExecuteResult::Ok);
+}
+
 #[test]
 fn nil() -> Result<(), std::io::Error> {
   test_files(&vec!["nil/literal.lox"], ExecuteResult::Ok)?;
@@ -531,18 +1032,30 @@ fn nil() -> Result<(), std::io::Error> {
 
 #[test]
 fn number() -> Result<(), std::io::Error> {
-  test_files(&vec!["number/literals.lox"], ExecuteResult::Ok)?;
+  test_files(
+    &vec![
+      "number/integer.lox",
+      "number/literals.lox",
+      "number/map_keys.lox",
+      "number/mixed.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
 
   test_files(
     &vec![
       "number/decimal_point_at_eof.lox",
       "number/leading_dot.lox",
+      "number/literal_too_large.lox",
       "number/trailing_dot.lox",
     ],
     ExecuteResult::CompileError,
   )?;
 
-  test_files(&vec![], ExecuteResult::RuntimeError)
+  test_files(
+    &vec!["number/division_by_zero.lox", "number/overflow.lox"],
+    ExecuteResult::RuntimeError,
+  )
 }
 
 #[test]
@@ -550,16 +1063,27 @@ fn operator() -> Result<(), std::io::Error> {
   test_files(
     &vec![
       "operator/add.lox",
+      "operator/bitwise.lox",
       "operator/comparison.lox",
       "operator/divide.lox",
       "operator/equals_class.lox",
       "operator/equals_method.lox",
       "operator/equals.lox",
+      "operator/exponent.lox",
+      "operator/integer_divide.lox",
+      "operator/modulo.lox",
       "operator/multiply.lox",
       "operator/negate.lox",
       "operator/not_class.lox",
       "operator/not_equals.lox",
       "operator/not.lox",
+      "operator/overload_arithmetic.lox",
+      "operator/overload_bitwise.lox",
+      "operator/overload_comparison.lox",
+      "operator/overload_equals.lox",
+      "operator/overload_index.lox",
+      "operator/overload_negate.lox",
+      "operator/overload_reflected.lox",
       "operator/subtract.lox",
     ],
     ExecuteResult::Ok,
@@ -575,19 +1099,28 @@ fn operator() -> Result<(), std::io::Error> {
       "operator/add_nil_nil.lox",
       "operator/add_num_nil.lox",
       "operator/add_string_nil.lox",
+      "operator/bit_not_nonintegral.lox",
+      "operator/bitwise_nonintegral.lox",
+      "operator/bitwise_nonnum.lox",
       "operator/divide_nonnum_num.lox",
       "operator/divide_num_nonnum.lox",
+      "operator/exponent_nonnum_num.lox",
       "operator/greater_nonnum_num.lox",
       "operator/greater_num_nonnum.lox",
       "operator/greater_or_equal_nonnum_num.lox",
       "operator/greater_or_equal_num_nonnum.lox",
+      "operator/integer_divide_nonnum_num.lox",
       "operator/less_nonnum_num.lox",
       "operator/less_num_nonnum.lox",
       "operator/less_or_equal_nonnum_num.lox",
       "operator/less_or_equal_num_nonnum.lox",
+      "operator/modulo_num_nonnum.lox",
       "operator/multiply_nonnum_num.lox",
       "operator/multiply_num_nonnum.lox",
       "operator/negate_nonnum.lox",
+      "operator/overload_missing_index.lox",
+      "operator/overload_missing.lox",
+      "operator/shift_out_of_range.lox",
       "operator/subtract_nonnum_num.lox",
       "operator/subtract_num_nonnum.lox",
     ],
@@ -595,6 +1128,27 @@ fn operator() -> Result<(), std::io::Error> {
   )
 }
 
+#[test]
+fn optional_chaining() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "optional_chaining/method.lox",
+      "optional_chaining/property.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec!["optional_chaining/assignment.lox"],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec!["optional_chaining/not_nil.lox"],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn print() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::Ok)?;
@@ -604,6 +1158,39 @@ fn print() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn range() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "range/bound_method.lox",
+      "range/contains.lox",
+      "range/extreme_bounds.lox",
+      "range/for_in.lox",
+      "range/literal.lox",
+      "range/reverse.lox",
+      "range/size.lox",
+      "range/step.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec!["range/missing_bound.lox"],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "range/non_integer_bound.lox",
+      "range/non_number_bound.lox",
+      "range/size_too_large.lox",
+      "range/step_negative.lox",
+      "range/step_zero.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn regression() -> Result<(), std::io::Error> {
   test_files(
@@ -631,17 +1218,82 @@ fn return_test() -> Result<(), std::io::Error> {
   test_files(&vec![], ExecuteResult::RuntimeError)
 }
 
+#[test]
+fn spread() -> Result<(), std::io::Error> {
+  test_files(
+    &vec!["spread/call.lox", "spread/list.lox", "spread/map.lox"],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec!["spread/missing_expression.lox"],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "spread/arity.lox",
+      "spread/call_not_list.lox",
+      "spread/map_not_map.lox",
+      "spread/too_many_arguments.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
+#[test]
+fn static_() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "static/field.lox",
+      "static/inherited.lox",
+      "static/method.lox",
+      "static/this.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec!["static/missing_name.lox"],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec!["static/not_on_instance.lox", "static/undefined.lox"],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn string() -> Result<(), std::io::Error> {
   test_files(
-    &vec!["string/literals.lox", "string/multiline.lox"],
+    &vec![
+      "string/escapes.lox",
+      "string/index.lox",
+      "string/interpolation.lox",
+      "string/literals.lox",
+      "string/multiline.lox",
+      "string/slice.lox",
+    ],
     ExecuteResult::Ok,
   )?;
 
-  test_files(&vec!["string/unterminated.lox"], ExecuteResult::CompileError)?;
+  test_files(
+    &vec![
+      "string/invalid_escape.lox",
+      "string/invalid_unicode_escape.lox",
+      "string/unterminated.lox",
+      "string/unterminated_interpolation.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
 
   test_files(
-    &vec!["string/error_after_multiline.lox"],
+    &vec![
+      "string/error_after_multiline.lox",
+      "string/index_out_of_bounds.lox",
+      "string/set_index.lox",
+    ],
     ExecuteResult::RuntimeError,
   )
 }
@@ -686,6 +1338,40 @@ fn super_() -> Result<(), std::io::Error> {
   )
 }
 
+#[test]
+fn trait_() -> Result<(), std::io::Error> {
+  test_files(
+    &vec![
+      "trait/inherited_required.lox",
+      "trait/mixin.lox",
+      "trait/name.lox",
+      "trait/precedence.lox",
+      "trait/resolved_conflict.lox",
+      "trait/super.lox",
+    ],
+    ExecuteResult::Ok,
+  )?;
+
+  test_files(
+    &vec![
+      "trait/initializer.lox",
+      "trait/missing_trait_name.lox",
+      "trait/super_in_trait.lox",
+    ],
+    ExecuteResult::CompileError,
+  )?;
+
+  test_files(
+    &vec![
+      "trait/conflict.lox",
+      "trait/inherit_from_trait.lox",
+      "trait/missing_required.lox",
+      "trait/mix_in_class.lox",
+    ],
+    ExecuteResult::RuntimeError,
+  )
+}
+
 #[test]
 fn variable() -> Result<(), std::io::Error> {
   test_files(